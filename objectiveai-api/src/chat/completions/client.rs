@@ -1,8 +1,12 @@
 //! Chat completions client implementation.
 
 use futures::{StreamExt, TryStreamExt};
+use tracing::Instrument;
 
-use crate::{ctx, util::StreamOnce};
+use crate::{
+    ctx,
+    util::{StreamOnce, until_closed},
+};
 use std::{sync::Arc, time::Duration};
 
 /// Generates a unique response ID for a chat completion.
@@ -106,6 +110,8 @@ where
     }
 
     /// Creates a streaming chat completion, tracking usage after the stream ends.
+    ///
+    /// Dropping the returned stream cancels the upstream request.
     pub async fn create_streaming_for_chat_handle_usage(
         self: Arc<Self>,
         ctx: ctx::Context<CTXEXT>,
@@ -121,8 +127,19 @@ where
         + 'static,
         super::Error,
     >{
+        let span = tracing::info_span!(
+            "chat_completion",
+            id = tracing::field::Empty,
+            model = tracing::field::Empty,
+            prompt_tokens = tracing::field::Empty,
+            completion_tokens = tracing::field::Empty,
+            cost = tracing::field::Empty,
+            error = tracing::field::Empty,
+            cancelled = tracing::field::Empty,
+        );
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
         let _ = tokio::spawn(async move {
+            let in_flight = crate::metrics::METRICS.in_flight_stream("chat");
             let mut aggregate: Option<
                 objectiveai::chat::completions::response::streaming::ChatCompletionChunk,
             > = None;
@@ -134,10 +151,12 @@ where
             {
                 Ok(stream) => stream,
                 Err(e) => {
+                    record_error(&tracing::Span::current(), &e);
                     let _ = tx.send(Err(e));
                     return;
                 }
             };
+            let stream = until_closed(stream, &tx);
             futures::pin_mut!(stream);
             while let Some(result) = stream.next().await {
                 match &result {
@@ -147,20 +166,31 @@ where
                             aggregate = Some(chunk.clone());
                         }
                     },
-                    Err(_) => {
+                    Err(e) => {
+                        record_error(&tracing::Span::current(), e);
                         error = true;
                     }
                 }
                 let _ = tx.send(result);
             }
             drop(stream);
+            if tx.is_closed() {
+                tracing::Span::current().record("cancelled", true);
+            }
             drop(tx);
-            if !error {
+            drop(in_flight);
+            if let Some(aggregate) = &aggregate {
+                record_chunk(&tracing::Span::current(), aggregate);
+            }
+            // a disconnected client may leave no chunks
+            if let Some(aggregate) = aggregate
+                && !error
+            {
                 self.usage_handler
-                    .handle_usage(ctx, Some(request), aggregate.unwrap().into())
+                    .handle_usage(ctx, Some(request), aggregate.into())
                     .await;
             }
-        });
+        }.instrument(span));
         let mut stream =
             tokio_stream::wrappers::UnboundedReceiverStream::new(rx);
         match stream.next().await {
@@ -192,6 +222,18 @@ where
         + 'static,
         super::Error,
     >{
+        let span = tracing::info_span!(
+            "ensemble_llm",
+            ensemble_llm = %ensemble_llm.inner.id,
+            upstream_model = %ensemble_llm.inner.base.model,
+            id = tracing::field::Empty,
+            model = tracing::field::Empty,
+            prompt_tokens = tracing::field::Empty,
+            completion_tokens = tracing::field::Empty,
+            cost = tracing::field::Empty,
+            error = tracing::field::Empty,
+            cancelled = tracing::field::Empty,
+        );
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
         let _ = tokio::spawn(async move {
             let mut aggregate: Option<
@@ -210,10 +252,12 @@ where
             {
                 Ok(stream) => stream,
                 Err(e) => {
+                    record_error(&tracing::Span::current(), &e);
                     let _ = tx.send(Err(e));
                     return;
                 }
             };
+            let stream = until_closed(stream, &tx);
             futures::pin_mut!(stream);
             while let Some(result) = stream.next().await {
                 match &result {
@@ -223,20 +267,30 @@ where
                             aggregate = Some(chunk.clone());
                         }
                     },
-                    Err(_) => {
+                    Err(e) => {
+                        record_error(&tracing::Span::current(), e);
                         error = true;
                     }
                 }
                 let _ = tx.send(result);
             }
             drop(stream);
+            if tx.is_closed() {
+                tracing::Span::current().record("cancelled", true);
+            }
             drop(tx);
-            if !error {
+            if let Some(aggregate) = &aggregate {
+                record_chunk(&tracing::Span::current(), aggregate);
+            }
+            // a disconnected client may leave no chunks
+            if let Some(aggregate) = aggregate
+                && !error
+            {
                 self.usage_handler
-                    .handle_usage(ctx, None, aggregate.unwrap().into())
+                    .handle_usage(ctx, None, aggregate.into())
                     .await;
             }
-        });
+        }.instrument(span));
         let mut stream =
             tokio_stream::wrappers::UnboundedReceiverStream::new(rx);
         match stream.next().await {
@@ -361,11 +415,15 @@ where
         );
 
         // try each model in order
-        backoff::future::retry(backoff, || async {
+        let mut attempt = 0u64;
+        backoff::future::retry(backoff, || {
+            attempt += 1;
+            async {
             let mut errors = Vec::new();
+            let mut ensemble_llms = Vec::with_capacity(models.len());
             for model in &models {
                 // fetch or validate Ensemble LLM
-                let ensemble_llm = Arc::new(match model {
+                ensemble_llms.push(Arc::new(match model {
                     objectiveai::chat::completions::request::Model::Id(id) => {
                         match self
                             .ensemble_llm_fetcher
@@ -392,7 +450,18 @@ where
                             }
                         }
                     }
-                });
+                }));
+            }
+            // try models with open circuits after their fallbacks
+            ensemble_llms.sort_by_cached_key(|ensemble_llm| {
+                self.upstream_client.circuit_state(
+                    ensemble_llm,
+                    super::upstream::Params::Chat {
+                        request: request.clone(),
+                    },
+                )
+            });
+            for ensemble_llm in ensemble_llms {
                 // try to create streaming completion
                 match self.upstream_client.create_streaming(
                     ctx.clone(),
@@ -420,6 +489,8 @@ where
                     errors,
                 )))
             }
+            }
+            .instrument(tracing::info_span!("backoff_attempt", attempt))
         })
         .await
     }
@@ -500,42 +571,83 @@ where
         );
 
         // try each model in order
-        backoff::future::retry(backoff, || async {
-            let mut errors = Vec::new();
-            for (i, ensemble_llm) in models.iter().cloned().enumerate() {
-                // try to create streaming completion
-                match self
-                    .upstream_client
-                    .create_streaming(
-                        ctx.clone(),
-                        response_id.clone(),
-                        first_chunk_timeout,
-                        other_chunk_timeout,
-                        ensemble_llm.clone(),
+        let mut attempt = 0u64;
+        backoff::future::retry(backoff, || {
+            attempt += 1;
+            async {
+                let mut errors = Vec::new();
+                // try models with open circuits after their fallbacks
+                let mut order = (0..models.len()).collect::<Vec<_>>();
+                order.sort_by_cached_key(|&i| {
+                    self.upstream_client.circuit_state(
+                        &models[i],
                         super::upstream::Params::Vector {
                             request: request.clone(),
                             vector_pfx_indices: vector_pfx_indices[i].clone(),
                         },
                     )
-                    .await
-                {
-                    Ok(Some(stream)) => {
-                        return Ok(stream.map_err(super::Error::UpstreamError));
-                    }
-                    Ok(None) => {}
-                    Err(e) => {
-                        errors.push(super::Error::UpstreamError(e));
+                });
+                for i in order {
+                    let ensemble_llm = models[i].clone();
+                    // try to create streaming completion
+                    match self
+                        .upstream_client
+                        .create_streaming(
+                            ctx.clone(),
+                            response_id.clone(),
+                            first_chunk_timeout,
+                            other_chunk_timeout,
+                            ensemble_llm.clone(),
+                            super::upstream::Params::Vector {
+                                request: request.clone(),
+                                vector_pfx_indices: vector_pfx_indices[i]
+                                    .clone(),
+                            },
+                        )
+                        .await
+                    {
+                        Ok(Some(stream)) => {
+                            return Ok(
+                                stream.map_err(super::Error::UpstreamError)
+                            );
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            errors.push(super::Error::UpstreamError(e));
+                        }
                     }
                 }
+                if errors.is_empty() {
+                    Err(backoff::Error::permanent(
+                        super::Error::NoUpstreamsFound,
+                    ))
+                } else {
+                    Err(backoff::Error::transient(
+                        super::Error::MultipleErrors(errors),
+                    ))
+                }
             }
-            if errors.is_empty() {
-                Err(backoff::Error::permanent(super::Error::NoUpstreamsFound))
-            } else {
-                Err(backoff::Error::transient(super::Error::MultipleErrors(
-                    errors,
-                )))
-            }
+            .instrument(tracing::info_span!("backoff_attempt", attempt))
         })
         .await
     }
 }
+
+/// Records an error on a span.
+fn record_error(span: &tracing::Span, error: &super::Error) {
+    span.record("error", tracing::field::display(error));
+}
+
+/// Records the identity and usage of an aggregated chunk on a span.
+fn record_chunk(
+    span: &tracing::Span,
+    chunk: &objectiveai::chat::completions::response::streaming::ChatCompletionChunk,
+) {
+    span.record("id", chunk.id.as_str());
+    span.record("model", chunk.model.as_str());
+    if let Some(usage) = &chunk.usage {
+        span.record("prompt_tokens", usage.prompt_tokens);
+        span.record("completion_tokens", usage.completion_tokens);
+        span.record("cost", tracing::field::display(usage.cost));
+    }
+}
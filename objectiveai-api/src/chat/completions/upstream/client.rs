@@ -6,17 +6,45 @@ use std::{sync::Arc, time::Duration};
 
 /// Client that manages connections to all upstream providers.
 ///
-/// Handles provider selection, BYOK key injection, and fallback between providers.
+/// Handles provider selection, BYOK key injection, rate limiting, health
+/// tracking, and fallback between providers.
 #[derive(Debug, Clone)]
 pub struct Client {
     /// OpenRouter provider client.
     pub openrouter_client: super::openrouter::Client,
+    /// Concurrency and rate limits applied before each upstream request.
+    pub limiter: Arc<super::Limiter>,
+    /// Health of upstream models, used to try fallbacks of failing models first.
+    pub health: Arc<super::HealthTracker>,
 }
 
 impl Client {
     /// Creates a new upstream client.
-    pub fn new(openrouter_client: super::openrouter::Client) -> Self {
-        Self { openrouter_client }
+    pub fn new(
+        openrouter_client: super::openrouter::Client,
+        limiter: Arc<super::Limiter>,
+        health: Arc<super::HealthTracker>,
+    ) -> Self {
+        Self {
+            openrouter_client,
+            limiter,
+            health,
+        }
+    }
+
+    /// Returns the best circuit state of an Ensemble LLM across the
+    /// upstreams available for the request.
+    pub fn circuit_state(
+        &self,
+        ensemble_llm: &objectiveai::ensemble_llm::EnsembleLlm,
+        request: super::Params,
+    ) -> super::CircuitState {
+        super::upstreams(ensemble_llm, request)
+            .map(|upstream| {
+                self.health.state(upstream, &ensemble_llm.base.model)
+            })
+            .min()
+            .unwrap_or(super::CircuitState::Closed)
     }
 
     /// Creates a streaming completion, trying each upstream provider in order.
@@ -110,6 +138,26 @@ impl Client {
     }
 
     /// Creates a streaming completion with a specific upstream provider.
+    ///
+    /// Waits for the limiter first, counting the wait against
+    /// `first_chunk_timeout`. The wait is reported in the final usage.
+    #[tracing::instrument(
+        name = "upstream_request",
+        skip_all,
+        fields(
+            upstream = ?upstream,
+            model = %ensemble_llm.base.model,
+            byok = byok.is_some(),
+            queue_wait_time = tracing::field::Empty,
+            provider = tracing::field::Empty,
+            prompt_tokens = tracing::field::Empty,
+            completion_tokens = tracing::field::Empty,
+            cost = tracing::field::Empty,
+            error = tracing::field::Empty,
+        ),
+    )]
+    // the stream's items carry the same unboxed error as the upstream clients
+    #[allow(clippy::result_large_err)]
     async fn upstream_create_streaming(
         &self,
         upstream: super::Upstream,
@@ -131,6 +179,30 @@ impl Client {
         + 'static,
         super::Error,
     >{
+        // wait for a free slot and a token
+        let limiter_key = super::LimiterKey::new(
+            upstream,
+            &ensemble_llm.base.model,
+            byok.as_deref(),
+        );
+        let permit = self
+            .limiter
+            .acquire(&limiter_key, first_chunk_timeout)
+            .await
+            .inspect_err(|e| {
+                crate::metrics::METRICS.record_upstream_outcome(
+                    upstream.as_str(),
+                    &ensemble_llm.base.model,
+                    e.kind(),
+                )
+            })?;
+        let first_chunk_timeout =
+            first_chunk_timeout.saturating_sub(permit.waited);
+        let queue_wait_time = permit.waited.as_millis() as u64;
+        let started = std::time::Instant::now();
+        let span = tracing::Span::current();
+        span.record("queue_wait_time", queue_wait_time);
+
         let mut stream = match request {
             super::Params::Chat { request } => self
                 .create_streaming_for_chat(
@@ -161,10 +233,103 @@ impl Client {
                 )
                 .boxed(),
         };
+        let metrics = &crate::metrics::METRICS;
+        let model = ensemble_llm.base.model.clone();
         match stream.try_next().await {
-            Ok(Some(chunk)) => Ok(StreamOnce::new(Ok(chunk)).chain(stream)),
-            Ok(None) => Err(super::Error::EmptyStream),
-            Err(e) => Err(e),
+            Ok(Some(chunk)) => {
+                self.limiter.succeed(&limiter_key);
+                metrics.admit_model(&model);
+                metrics.admit_model(&chunk.model);
+                metrics
+                    .upstream_time_to_first_chunk
+                    .with_label_values(&[
+                        upstream.as_str(),
+                        metrics.model_label(&model),
+                    ])
+                    .observe(started.elapsed().as_secs_f64());
+                metrics.record_upstream_outcome(
+                    upstream.as_str(),
+                    &model,
+                    "ok",
+                );
+                self.health.record_success(
+                    upstream,
+                    &ensemble_llm.base.model,
+                    chunk.provider.as_deref(),
+                    started.elapsed(),
+                );
+                if let Some(provider) = &chunk.provider {
+                    span.record("provider", provider.as_str());
+                }
+                Ok(StreamOnce::new(Ok(chunk)).chain(stream).map(
+                    move |result| {
+                        // hold the permit until the stream is dropped
+                        let _ = &permit;
+                        match &result {
+                            Ok(chunk) => {
+                                if let Some(usage) = &chunk.usage {
+                                    crate::metrics::METRICS
+                                        .record_upstream_usage(&model, usage);
+                                    span.record(
+                                        "prompt_tokens",
+                                        usage.prompt_tokens,
+                                    );
+                                    span.record(
+                                        "completion_tokens",
+                                        usage.completion_tokens,
+                                    );
+                                    span.record(
+                                        "cost",
+                                        tracing::field::display(usage.cost),
+                                    );
+                                }
+                            }
+                            Err(e) => {
+                                span.record(
+                                    "error",
+                                    tracing::field::display(e),
+                                );
+                            }
+                        }
+                        result.map(|mut chunk| {
+                            if let Some(usage) = &mut chunk.usage {
+                                usage.queue_wait_time = Some(queue_wait_time);
+                            }
+                            chunk
+                        })
+                    },
+                ))
+            }
+            Ok(None) => {
+                metrics.record_upstream_outcome(
+                    upstream.as_str(),
+                    &model,
+                    super::Error::EmptyStream.kind(),
+                );
+                Err(super::Error::EmptyStream)
+            }
+            Err(e) => {
+                span.record("error", tracing::field::display(&e));
+                metrics.record_upstream_outcome(
+                    upstream.as_str(),
+                    &model,
+                    e.kind(),
+                );
+                let status = objectiveai::error::StatusError::status(&e);
+                // feed rate limit responses back into the limiter
+                if status == 429 {
+                    self.limiter.throttle(&limiter_key);
+                }
+                // only count failures which reflect the upstream's health
+                if status == 408 || status == 429 || status >= 500 {
+                    self.health.record_failure(
+                        upstream,
+                        &ensemble_llm.base.model,
+                        e.provider_name(),
+                    );
+                }
+                Err(e)
+            }
         }
     }
 
@@ -15,6 +15,32 @@ pub enum Error {
     /// The upstream returned an empty stream.
     #[error("empty upstream stream")]
     EmptyStream,
+    /// Timed out waiting for upstream concurrency or rate limits.
+    #[error("timed out waiting for upstream rate limits")]
+    QueueTimeout,
+}
+
+impl Error {
+    /// Returns the name of the provider which failed, if reported.
+    pub fn provider_name(&self) -> Option<&str> {
+        match self {
+            Error::OpenRouter(
+                super::openrouter::Error::OpenRouterProviderError(e),
+            ) => e.provider_name(),
+            _ => None,
+        }
+    }
+
+    /// Returns a short, stable name for the kind of error.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::OpenRouter(e) => e.kind(),
+            Error::FetchByok(_) => "fetch_byok",
+            Error::MultipleErrors(_) => "multiple_upstream_errors",
+            Error::EmptyStream => "empty_upstream_stream",
+            Error::QueueTimeout => "upstream_queue_timeout",
+        }
+    }
 }
 
 impl objectiveai::error::StatusError for Error {
@@ -24,6 +50,7 @@ impl objectiveai::error::StatusError for Error {
             Error::FetchByok(e) => e.status(),
             Error::MultipleErrors(_) => 500,
             Error::EmptyStream => 500,
+            Error::QueueTimeout => 429,
         }
     }
 
@@ -43,6 +70,22 @@ impl objectiveai::error::StatusError for Error {
             Error::EmptyStream => Some(serde_json::json!({
                 "kind": "empty_upstream_stream",
             })),
+            Error::QueueTimeout => Some(serde_json::json!({
+                "kind": "upstream_queue_timeout",
+            })),
         }
     }
 }
+
+/// Invalid upstream limits configuration.
+#[derive(thiserror::Error, Debug)]
+#[error(
+    "invalid {} upstream limits: {reason}",
+    model.as_deref().unwrap_or("default")
+)]
+pub struct InvalidLimits {
+    /// The model the limits are for, or `None` for the default limits.
+    pub model: Option<String>,
+    /// Why the limits are invalid.
+    pub reason: &'static str,
+}
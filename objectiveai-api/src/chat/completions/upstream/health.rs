@@ -0,0 +1,221 @@
+//! Circuit breaking and health tracking for upstream models.
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Smoothing factor for the exponentially weighted averages.
+const EWMA_ALPHA: f64 = 0.2;
+
+/// Identifies a set of upstream requests tracked together.
+///
+/// Every outcome is recorded both under its provider, if known, and under
+/// `provider: None`, which tracks the model across all providers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HealthKey {
+    /// The upstream provider.
+    pub upstream: super::Upstream,
+    /// The upstream model identifier.
+    pub model: String,
+    /// The provider which served or failed the request, if known.
+    pub provider: Option<String>,
+}
+
+/// State of a circuit, ordered from most to least preferred.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests flow normally.
+    Closed,
+    /// The circuit was open long enough that requests may probe recovery.
+    HalfOpen,
+    /// Requests failed repeatedly, so fallbacks are tried first.
+    Open,
+}
+
+/// Health statistics for a [`HealthKey`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Health {
+    /// The tracked upstream, model, and provider.
+    #[serde(flatten)]
+    pub key: HealthKey,
+    /// The current circuit state.
+    pub state: CircuitState,
+    /// Total successful requests.
+    pub successes: u64,
+    /// Total failed requests.
+    pub failures: u64,
+    /// Failures since the last success.
+    pub consecutive_failures: u64,
+    /// Exponentially weighted moving average of the failure rate.
+    pub error_rate: f64,
+    /// Exponentially weighted moving average of the time to first chunk,
+    /// in milliseconds.
+    pub latency: f64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    health: Health,
+    opened_at: Option<Instant>,
+}
+
+impl Entry {
+    fn refresh(&mut self, open_duration: Duration) {
+        if self.health.state == CircuitState::Open
+            && self
+                .opened_at
+                .is_some_and(|opened_at| opened_at.elapsed() >= open_duration)
+        {
+            self.health.state = CircuitState::HalfOpen;
+        }
+    }
+}
+
+/// Tracks error rates and latencies of upstream requests, opening a
+/// circuit for a model after repeated failures.
+///
+/// An open circuit does not block requests. It moves the model behind its
+/// fallbacks until `open_duration` has passed, after which the circuit is
+/// half-open and the next outcome either closes or re-opens it.
+#[derive(Debug)]
+pub struct HealthTracker {
+    /// Consecutive failures which open a circuit.
+    pub failure_threshold: u64,
+    /// How long a circuit stays open before half-opening.
+    pub open_duration: Duration,
+    entries: DashMap<HealthKey, Entry>,
+}
+
+impl HealthTracker {
+    /// Creates a new health tracker.
+    pub fn new(failure_threshold: u64, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold,
+            open_duration,
+            entries: DashMap::new(),
+        }
+    }
+
+    /// Returns the circuit state of a model across all providers.
+    pub fn state(
+        &self,
+        upstream: super::Upstream,
+        model: &str,
+    ) -> CircuitState {
+        let key = HealthKey {
+            upstream,
+            model: model.to_owned(),
+            provider: None,
+        };
+        match self.entries.get_mut(&key) {
+            Some(mut entry) => {
+                entry.refresh(self.open_duration);
+                entry.health.state
+            }
+            None => CircuitState::Closed,
+        }
+    }
+
+    /// Records a request which produced its first chunk.
+    pub fn record_success(
+        &self,
+        upstream: super::Upstream,
+        model: &str,
+        provider: Option<&str>,
+        latency: Duration,
+    ) {
+        self.record(upstream, model, provider, Some(latency));
+    }
+
+    /// Records a request which failed before producing its first chunk.
+    pub fn record_failure(
+        &self,
+        upstream: super::Upstream,
+        model: &str,
+        provider: Option<&str>,
+    ) {
+        self.record(upstream, model, provider, None);
+    }
+
+    fn record(
+        &self,
+        upstream: super::Upstream,
+        model: &str,
+        provider: Option<&str>,
+        latency: Option<Duration>,
+    ) {
+        let keys = std::iter::once(None)
+            .chain(provider.map(|provider| Some(provider.to_owned())))
+            .map(|provider| HealthKey {
+                upstream,
+                model: model.to_owned(),
+                provider,
+            });
+        for key in keys {
+            let mut entry_ref =
+                self.entries.entry(key.clone()).or_insert_with(|| Entry {
+                    health: Health {
+                        key,
+                        state: CircuitState::Closed,
+                        successes: 0,
+                        failures: 0,
+                        consecutive_failures: 0,
+                        error_rate: 0.0,
+                        latency: 0.0,
+                    },
+                    opened_at: None,
+                });
+            let entry = &mut *entry_ref;
+            entry.refresh(self.open_duration);
+            let health = &mut entry.health;
+            match latency {
+                Some(latency) => {
+                    health.successes += 1;
+                    health.consecutive_failures = 0;
+                    health.error_rate *= 1.0 - EWMA_ALPHA;
+                    let latency = latency.as_secs_f64() * 1000.0;
+                    health.latency = if health.successes == 1 {
+                        latency
+                    } else {
+                        health.latency * (1.0 - EWMA_ALPHA)
+                            + latency * EWMA_ALPHA
+                    };
+                    health.state = CircuitState::Closed;
+                    entry.opened_at = None;
+                }
+                None => {
+                    health.failures += 1;
+                    health.consecutive_failures += 1;
+                    health.error_rate =
+                        health.error_rate * (1.0 - EWMA_ALPHA) + EWMA_ALPHA;
+                    if health.state == CircuitState::HalfOpen
+                        || health.consecutive_failures >= self.failure_threshold
+                    {
+                        health.state = CircuitState::Open;
+                        entry.opened_at = Some(Instant::now());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the health of every tracked key.
+    pub fn snapshot(&self) -> Vec<Health> {
+        let mut healths = self
+            .entries
+            .iter_mut()
+            .map(|mut entry| {
+                entry.refresh(self.open_duration);
+                entry.health.clone()
+            })
+            .collect::<Vec<_>>();
+        healths.sort_by(|a, b| {
+            (&a.key.model, &a.key.provider)
+                .cmp(&(&b.key.model, &b.key.provider))
+        });
+        healths
+    }
+}
@@ -0,0 +1,319 @@
+//! Per-model concurrency and rate limits for upstream requests.
+
+use backoff::backoff::Backoff;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Limits applied to upstream requests sharing a [`LimiterKey`].
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Limits {
+    /// Maximum number of requests in flight at once. Unlimited if `None`.
+    #[serde(default)]
+    pub max_in_flight: Option<usize>,
+    /// Sustained number of requests started per second. Unlimited if `None`.
+    #[serde(default)]
+    pub requests_per_second: Option<f64>,
+    /// Number of requests which may start at once after a quiet period.
+    /// Defaults to `requests_per_second`, and at least 1.
+    #[serde(default)]
+    pub burst: Option<f64>,
+}
+
+impl Limits {
+    /// Checks that every limit which is set is positive.
+    pub fn validate(
+        &self,
+        model: Option<&str>,
+    ) -> Result<(), super::InvalidLimits> {
+        let invalid = |reason| super::InvalidLimits {
+            model: model.map(str::to_owned),
+            reason,
+        };
+        if self.max_in_flight == Some(0) {
+            return Err(invalid("max_in_flight must be at least 1"));
+        }
+        if let Some(requests_per_second) = self.requests_per_second
+            && !(requests_per_second.is_finite() && requests_per_second > 0.0)
+        {
+            return Err(invalid("requests_per_second must be positive"));
+        }
+        if let Some(burst) = self.burst
+            && !(burst.is_finite() && burst > 0.0)
+        {
+            return Err(invalid("burst must be positive"));
+        }
+        Ok(())
+    }
+
+    fn burst(&self) -> f64 {
+        self.burst
+            .or(self.requests_per_second)
+            .unwrap_or(1.0)
+            .max(1.0)
+    }
+}
+
+/// Identifies a set of upstream requests which share limits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LimiterKey {
+    /// The upstream provider.
+    pub upstream: super::Upstream,
+    /// The upstream model identifier.
+    pub model: String,
+    /// SHA-256 hex digest of the BYOK API key, if one is used.
+    pub byok: Option<String>,
+}
+
+impl LimiterKey {
+    /// Creates a new key. The BYOK API key is hashed rather than retained.
+    pub fn new(
+        upstream: super::Upstream,
+        model: &str,
+        byok: Option<&str>,
+    ) -> Self {
+        Self {
+            upstream,
+            model: model.to_owned(),
+            byok: byok.map(|byok| {
+                ring::digest::digest(&ring::digest::SHA256, byok.as_bytes())
+                    .as_ref()
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect()
+            }),
+        }
+    }
+}
+
+/// Queues upstream requests so that they respect per-key concurrency
+/// limits and token-bucket rate limits.
+///
+/// Rate limit responses from an upstream throttle the key with an
+/// exponential backoff, which resets once a request succeeds again.
+#[derive(Debug)]
+pub struct Limiter {
+    /// Limits for models without an entry in `model_limits`.
+    pub default_limits: Limits,
+    /// Limits for specific upstream models.
+    pub model_limits: HashMap<String, Limits>,
+    /// Backoff used to pause a key after the upstream rate limits it.
+    pub throttle_backoff: backoff::ExponentialBackoff,
+    buckets: DashMap<LimiterKey, Arc<Bucket>>,
+    swept_at: Mutex<Instant>,
+}
+
+/// How often idle buckets are evicted.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+struct Bucket {
+    limits: Limits,
+    semaphore: Option<Arc<tokio::sync::Semaphore>>,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    refilled_at: Instant,
+    throttled_until: Option<Instant>,
+    throttle_backoff: backoff::ExponentialBackoff,
+}
+
+impl Bucket {
+    /// Takes a token if one is available, otherwise returns when to retry.
+    fn try_take(&self, now: Instant) -> Option<Instant> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(throttled_until) = state.throttled_until {
+            if throttled_until > now {
+                return Some(throttled_until);
+            }
+            state.throttled_until = None;
+        }
+        let requests_per_second = self.limits.requests_per_second?;
+        state.refill(now, &self.limits);
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            None
+        } else {
+            Some(
+                now + Duration::from_secs_f64(
+                    (1.0 - state.tokens) / requests_per_second,
+                ),
+            )
+        }
+    }
+
+    /// Whether the bucket is in the same state as a new one, so that it
+    /// can be evicted without changing how requests are limited.
+    fn is_idle(&self, now: Instant) -> bool {
+        if let Some(semaphore) = &self.semaphore
+            && Some(semaphore.available_permits()) != self.limits.max_in_flight
+        {
+            return false;
+        }
+        let mut state = self.state.lock().unwrap();
+        if state
+            .throttled_until
+            .is_some_and(|throttled_until| throttled_until > now)
+        {
+            return false;
+        }
+        state.refill(now, &self.limits);
+        state.tokens >= self.limits.burst()
+    }
+}
+
+impl BucketState {
+    /// Adds the tokens accrued since the last refill.
+    fn refill(&mut self, now: Instant, limits: &Limits) {
+        if let Some(requests_per_second) = limits.requests_per_second {
+            let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * requests_per_second)
+                .min(limits.burst());
+        } else {
+            self.tokens = limits.burst();
+        }
+        self.refilled_at = now;
+    }
+}
+
+impl Limiter {
+    /// Creates a new limiter, or returns an error if any limits are invalid.
+    pub fn new(
+        default_limits: Limits,
+        model_limits: HashMap<String, Limits>,
+        throttle_backoff: backoff::ExponentialBackoff,
+    ) -> Result<Self, super::InvalidLimits> {
+        default_limits.validate(None)?;
+        for (model, limits) in &model_limits {
+            limits.validate(Some(model))?;
+        }
+        Ok(Self {
+            default_limits,
+            model_limits,
+            throttle_backoff: backoff::ExponentialBackoff {
+                max_elapsed_time: None,
+                ..throttle_backoff
+            },
+            buckets: DashMap::new(),
+            swept_at: Mutex::new(Instant::now()),
+        })
+    }
+
+    /// Evicts idle buckets, at most once per [`SWEEP_INTERVAL`].
+    fn sweep(&self) {
+        let now = Instant::now();
+        {
+            let mut swept_at = self.swept_at.lock().unwrap();
+            if now.duration_since(*swept_at) < SWEEP_INTERVAL {
+                return;
+            }
+            *swept_at = now;
+        }
+        self.buckets.retain(|_, bucket| {
+            // buckets referenced elsewhere are in use
+            Arc::strong_count(bucket) > 1 || !bucket.is_idle(now)
+        });
+    }
+
+    fn bucket(&self, key: &LimiterKey) -> Arc<Bucket> {
+        self.sweep();
+        self.buckets
+            .entry(key.clone())
+            .or_insert_with(|| {
+                let limits = self
+                    .model_limits
+                    .get(&key.model)
+                    .copied()
+                    .unwrap_or(self.default_limits);
+                Arc::new(Bucket {
+                    limits,
+                    semaphore: limits.max_in_flight.map(|max_in_flight| {
+                        Arc::new(tokio::sync::Semaphore::new(max_in_flight))
+                    }),
+                    state: Mutex::new(BucketState {
+                        tokens: limits.burst(),
+                        refilled_at: Instant::now(),
+                        throttled_until: None,
+                        throttle_backoff: self.throttle_backoff.clone(),
+                    }),
+                })
+            })
+            .clone()
+    }
+
+    /// Waits until a request for `key` may start.
+    ///
+    /// Returns [`super::Error::QueueTimeout`] if that would take longer than
+    /// `timeout`. The returned permit must be held until the request ends.
+    pub async fn acquire(
+        &self,
+        key: &LimiterKey,
+        timeout: Duration,
+    ) -> Result<LimiterPermit, super::Error> {
+        let start = Instant::now();
+        let deadline = start + timeout;
+        let bucket = self.bucket(key);
+
+        // wait for a free slot
+        let permit = match &bucket.semaphore {
+            Some(semaphore) => match tokio::time::timeout_at(
+                deadline.into(),
+                semaphore.clone().acquire_owned(),
+            )
+            .await
+            {
+                Ok(Ok(permit)) => Some(permit),
+                // the semaphore is never closed
+                Ok(Err(_)) => None,
+                Err(_) => return Err(super::Error::QueueTimeout),
+            },
+            None => None,
+        };
+
+        // wait for a token
+        while let Some(retry_at) = bucket.try_take(Instant::now()) {
+            if retry_at > deadline {
+                return Err(super::Error::QueueTimeout);
+            }
+            tokio::time::sleep_until(retry_at.into()).await;
+        }
+
+        Ok(LimiterPermit {
+            _permit: permit,
+            waited: start.elapsed(),
+        })
+    }
+
+    /// Pauses requests for `key` after the upstream rate limited one.
+    pub fn throttle(&self, key: &LimiterKey) {
+        let bucket = self.bucket(key);
+        let mut state = bucket.state.lock().unwrap();
+        let pause = state
+            .throttle_backoff
+            .next_backoff()
+            .unwrap_or(state.throttle_backoff.max_interval);
+        state.tokens = 0.0;
+        state.throttled_until = Some(Instant::now() + pause);
+    }
+
+    /// Resets the throttle backoff for `key` after a request succeeded.
+    pub fn succeed(&self, key: &LimiterKey) {
+        let bucket = self.bucket(key);
+        bucket.state.lock().unwrap().throttle_backoff.reset();
+    }
+}
+
+/// Permission for a single upstream request to run.
+#[derive(Debug)]
+pub struct LimiterPermit {
+    _permit: Option<tokio::sync::OwnedSemaphorePermit>,
+    /// How long the request waited in the queue.
+    pub waited: Duration,
+}
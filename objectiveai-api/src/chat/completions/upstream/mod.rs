@@ -5,6 +5,8 @@
 
 mod client;
 mod error;
+mod health;
+mod limiter;
 /// OpenRouter provider client and types.
 pub mod openrouter;
 mod params;
@@ -12,5 +14,7 @@ mod upstream;
 
 pub use client::*;
 pub use error::*;
+pub use health::*;
+pub use limiter::*;
 pub use params::*;
 pub use upstream::*;
@@ -42,6 +42,24 @@ pub enum Error {
     InvalidEnsembleLlm(String),
 }
 
+impl Error {
+    /// Returns a short, stable name for the kind of error.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::OpenRouterProviderError(_) => "provider_error",
+            Error::EmptyStream => "empty_stream",
+            Error::DeserializationError(_) => "deserialization",
+            Error::BadStatus { .. } => "bad_status",
+            Error::StreamError(_) => "stream_error",
+            Error::StreamTimeout => "stream_timeout",
+            Error::FetchEnsembleLlm(_) => "fetch_ensemble_llm",
+            Error::InsufficientCredits => "insufficient_credits",
+            Error::InvalidEnsembleLlm(_) => "invalid_ensemble_llm",
+            Error::EnsembleLlmNotFound => "ensemble_llm_not_found",
+        }
+    }
+}
+
 impl objectiveai::error::StatusError for Error {
     fn status(&self) -> u16 {
         match self {
@@ -123,6 +141,13 @@ pub struct OpenRouterProviderError {
     pub user_id: Option<String>,
 }
 
+impl OpenRouterProviderError {
+    /// Returns the name of the provider which failed, if reported.
+    pub fn provider_name(&self) -> Option<&str> {
+        self.error.metadata.as_ref()?.get("provider_name")?.as_str()
+    }
+}
+
 impl objectiveai::error::StatusError for OpenRouterProviderError {
     fn status(&self) -> u16 {
         self.error.status()
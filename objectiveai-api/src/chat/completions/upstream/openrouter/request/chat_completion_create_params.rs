@@ -84,6 +84,9 @@ pub struct ChatCompletionCreateParams {
     /// Prediction hints from request.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prediction: Option<objectiveai::chat::completions::request::Prediction>,
+    /// Decoding grammar for self-hosted servers, from the Ensemble LLM.
+    #[serde(flatten)]
+    pub grammar: super::grammar::Grammar,
 
     /// Always true for streaming requests.
     pub stream: bool,
@@ -138,6 +141,7 @@ impl ChatCompletionCreateParams {
             tools: request.tools.clone(),
             parallel_tool_calls: request.parallel_tool_calls,
             prediction: request.prediction.clone(),
+            grammar: super::grammar::Grammar::default(),
             stream: true,
             stream_options: super::StreamOptions {
                 include_usage: Some(true),
@@ -207,6 +211,11 @@ impl ChatCompletionCreateParams {
             ),
             parallel_tool_calls: None,
             prediction: None,
+            grammar: super::grammar::new_for_vector(
+                vector_pfx_indices,
+                ensemble_llm.base.output_mode,
+                ensemble_llm.base.synthetic_reasoning,
+            ),
             stream: true,
             stream_options: super::StreamOptions {
                 include_usage: Some(true),
@@ -0,0 +1,58 @@
+//! Grammar construction for vector completions.
+
+use crate::vector;
+use serde::{Deserialize, Serialize};
+
+/// Decoding grammar fields for self-hosted OpenAI-compatible servers.
+///
+/// `grammar` is read by llama.cpp, while `guided_regex` and `guided_choice`
+/// are read by vLLM. Each server ignores the fields meant for the other.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Grammar {
+    /// GBNF grammar constraining the output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grammar: Option<String>,
+    /// Regular expression constraining the output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guided_regex: Option<String>,
+    /// Exact set of valid outputs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guided_choice: Option<Vec<String>>,
+}
+
+/// Creates a decoding grammar for vector completion voting.
+///
+/// When the output mode is `Grammar`, returns constraints that force the LLM
+/// to output exactly one of the available response keys. vLLM rejects
+/// requests with more than one guided decoding field, so `guided_regex` is
+/// only set when `guided_choice` cannot be used.
+/// Returns an empty grammar for other output modes.
+pub fn new_for_vector(
+    vector_pfx_indices: &[(String, usize)],
+    ensemble_llm_output_mode: objectiveai::ensemble_llm::OutputMode,
+    ensemble_llm_synthetic_reasoning: Option<bool>,
+) -> Grammar {
+    if let objectiveai::ensemble_llm::OutputMode::Grammar = ensemble_llm_output_mode {
+        let vector::completions::ResponseKeyGrammar {
+            gbnf,
+            regex,
+            choice,
+        } = vector::completions::ResponseKey::grammar(
+            vector_pfx_indices
+                .iter()
+                .map(|(key, _)| key.clone())
+                .collect(),
+            ensemble_llm_synthetic_reasoning.unwrap_or(false),
+        );
+        Grammar {
+            grammar: Some(gbnf),
+            guided_regex: match choice {
+                Some(_) => None,
+                None => Some(regex),
+            },
+            guided_choice: choice,
+        }
+    } else {
+        Grammar::default()
+    }
+}
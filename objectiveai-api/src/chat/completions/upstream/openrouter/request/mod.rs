@@ -4,6 +4,8 @@
 //! by the OpenRouter API, applying Ensemble LLM configurations.
 
 mod chat_completion_create_params;
+/// Grammar construction for vector completions.
+pub mod grammar;
 /// Prompt construction for chat and vector completions.
 pub mod prompt;
 mod provider;
@@ -67,6 +67,7 @@ impl Usage {
             total_cost,
             cost_multiplier,
             is_byok,
+            queue_wait_time: None,
         }
     }
 
@@ -10,11 +10,22 @@ pub enum Upstream {
     OpenRouter,
 }
 
+impl Upstream {
+    /// Every supported upstream provider.
+    pub const ALL: [Upstream; 1] = [Upstream::OpenRouter];
+
+    /// Returns the name of the provider, as serialized.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Upstream::OpenRouter => "open_router",
+        }
+    }
+}
+
 /// Returns an iterator over available upstream providers for a request.
 pub fn upstreams(
     _ensemble_llm: &objectiveai::ensemble_llm::EnsembleLlm,
     _request: super::Params,
 ) -> impl Iterator<Item = Upstream> {
-    const ALL_UPSTREAMS: [Upstream; 1] = [Upstream::OpenRouter];
-    ALL_UPSTREAMS.iter().copied()
+    Upstream::ALL.into_iter()
 }
@@ -2,7 +2,7 @@
 
 use dashmap::DashMap;
 use futures::future::Shared;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 /// Per-request context containing user-specific state and deduplication caches.
 ///
@@ -48,6 +48,19 @@ pub struct Context<CTXEXT> {
             >,
         >,
     >,
+    /// Votes of the stored Function execution being replayed, keyed by the ID
+    /// of the vector completion which produced them.
+    ///
+    /// Set only while replaying. Retried votes found here are used instead of
+    /// being fetched, and are re-assigned to the LLMs of the replayed Profile.
+    pub replay_votes: Option<
+        Arc<
+            HashMap<
+                String,
+                Vec<objectiveai::vector::completions::response::Vote>,
+            >,
+        >,
+    >,
 }
 
 impl<CTXEXT> Clone for Context<CTXEXT> {
@@ -57,6 +70,7 @@ impl<CTXEXT> Clone for Context<CTXEXT> {
             cost_multiplier: self.cost_multiplier,
             ensemble_cache: self.ensemble_cache.clone(),
             ensemble_llm_cache: self.ensemble_llm_cache.clone(),
+            replay_votes: self.replay_votes.clone(),
         }
     }
 }
@@ -69,6 +83,20 @@ impl<CTXEXT> Context<CTXEXT> {
             cost_multiplier,
             ensemble_cache: Arc::new(DashMap::new()),
             ensemble_llm_cache: Arc::new(DashMap::new()),
+            replay_votes: None,
         }
     }
+
+    /// Marks the context as replaying a Function execution, reusing the given
+    /// votes of its vector completions.
+    pub fn with_replay_votes(
+        mut self,
+        votes: HashMap<
+            String,
+            Vec<objectiveai::vector::completions::response::Vote>,
+        >,
+    ) -> Self {
+        self.replay_votes = Some(Arc::new(votes));
+        self
+    }
 }
@@ -6,7 +6,9 @@ use crate::chat;
 ///
 /// Implementations can provide BYOK (Bring Your Own Key) API keys
 /// for upstream providers, allowing users to use their own API keys
-/// instead of ObjectiveAI's pooled keys.
+/// instead of ObjectiveAI's pooled keys, and identify the caller which
+/// resources kept by the server, such as jobs and stored executions,
+/// belong to.
 #[async_trait::async_trait]
 pub trait ContextExt {
     /// Returns the user's BYOK API key for the given upstream provider.
@@ -17,4 +19,15 @@ pub trait ContextExt {
         &self,
         upstream: chat::completions::upstream::Upstream,
     ) -> Result<Option<String>, objectiveai::error::ResponseError>;
+
+    /// Returns an opaque identifier of the caller.
+    ///
+    /// Resources the server keeps on behalf of a caller are only accessible
+    /// to callers with the same identifier. Returns `None` for anonymous
+    /// callers, whose resources are shared by every anonymous caller.
+    ///
+    /// By default every caller is anonymous.
+    fn owner(&self) -> Option<String> {
+        None
+    }
 }
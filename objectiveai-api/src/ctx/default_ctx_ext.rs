@@ -1,32 +1,48 @@
 use crate::chat;
 use axum::http::HeaderMap;
+use serde::{Deserialize, Serialize};
 
 /// Default context extension that extracts OpenRouter BYOK from request headers.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct DefaultContextExt {
     /// OpenRouter API key from the `authorization_openrouter` header.
     /// None if the header was not provided.
     pub openrouter_byok: Option<String>,
+    /// SHA-256 digest of the API key from the `authorization` header,
+    /// identifying the caller without retaining its key.
+    /// None if the header was not provided.
+    #[serde(default)]
+    pub owner: Option<String>,
 }
 
 impl DefaultContextExt {
-    /// Extracts the OpenRouter BYOK from request headers.
+    /// Extracts the OpenRouter BYOK and the caller from request headers.
     ///
-    /// Looks for the `authorization_openrouter` header and strips the "Bearer " prefix
-    /// if present.
+    /// Looks for the `authorization_openrouter` and `authorization` headers
+    /// and strips the "Bearer " prefix if present.
     pub fn from_headers(headers: &HeaderMap) -> Self {
-        let openrouter_byok = headers
-            .get("authorization_openrouter")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| {
+        let bearer = |name: &str| {
+            headers.get(name).and_then(|v| v.to_str().ok()).map(|s| {
                 if let Some(stripped) = s.strip_prefix("Bearer ") {
                     stripped.to_string()
                 } else {
                     s.to_string()
                 }
-            });
+            })
+        };
+        let openrouter_byok = bearer("authorization_openrouter");
+        let owner = bearer("authorization").map(|api_key| {
+            ring::digest::digest(&ring::digest::SHA256, api_key.as_bytes())
+                .as_ref()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect()
+        });
 
-        Self { openrouter_byok }
+        Self {
+            openrouter_byok,
+            owner,
+        }
     }
 }
 
@@ -42,4 +58,8 @@ impl super::ContextExt for DefaultContextExt {
             }
         }
     }
+
+    fn owner(&self) -> Option<String> {
+        self.owner.clone()
+    }
 }
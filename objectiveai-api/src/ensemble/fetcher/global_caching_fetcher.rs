@@ -0,0 +1,62 @@
+//! Process-wide caching wrapper for ensemble fetchers.
+
+use crate::{ctx, lru};
+use std::sync::Arc;
+
+/// Wraps an ensemble fetcher with a process-wide LRU cache.
+///
+/// Ensembles are content-addressed, so a fetched ensemble never changes and
+/// entries have no TTL. Not-found results and errors are not cached. Intended
+/// to sit under [`CachingFetcher`](super::CachingFetcher), which deduplicates
+/// concurrent fetches within a request.
+#[derive(Debug)]
+pub struct GlobalCachingFetcher<FENS> {
+    /// The underlying fetcher to delegate to on cache miss.
+    pub inner: Arc<FENS>,
+    /// Cached ensembles and their creation timestamps, keyed by ensemble ID.
+    pub cache: lru::LruCache<String, (objectiveai::ensemble::Ensemble, u64)>,
+}
+
+impl<FENS> GlobalCachingFetcher<FENS> {
+    /// Creates a new global caching fetcher wrapping the given inner fetcher.
+    pub fn new(inner: Arc<FENS>, max_entries: usize, max_bytes: usize) -> Self {
+        Self {
+            inner,
+            cache: lru::LruCache::new(max_entries, max_bytes),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<CTXEXT, FENS> super::Fetcher<CTXEXT> for GlobalCachingFetcher<FENS>
+where
+    CTXEXT: Send + Sync + 'static,
+    FENS: super::Fetcher<CTXEXT> + Send + Sync + 'static,
+{
+    async fn fetch(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        id: &str,
+    ) -> Result<
+        Option<(objectiveai::ensemble::Ensemble, u64)>,
+        objectiveai::error::ResponseError,
+    > {
+        let id = id.to_owned();
+        let cached = self.cache.get(&id);
+        crate::metrics::METRICS
+            .record_cache_lookup("ensemble", cached.is_some());
+        if let Some(cached) = cached {
+            return Ok(Some(cached));
+        }
+        let result = self.inner.fetch(ctx, &id).await?;
+        if let Some(ensemble) = &result {
+            self.cache.insert(
+                id,
+                ensemble.clone(),
+                lru::json_size(ensemble),
+                None,
+            );
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,67 @@
+//! Process-wide caching wrapper for Ensemble LLM fetchers.
+
+use crate::{ctx, lru};
+use std::sync::Arc;
+
+/// Wraps an Ensemble LLM fetcher with a process-wide LRU cache.
+///
+/// Ensemble LLMs are content-addressed, so a fetched Ensemble LLM never changes and
+/// entries have no TTL. Not-found results and errors are not cached. Intended
+/// to sit under [`CachingFetcher`](super::CachingFetcher), which deduplicates
+/// concurrent fetches within a request.
+#[derive(Debug)]
+pub struct GlobalCachingFetcher<FENSLLM> {
+    /// The underlying fetcher to delegate to on cache miss.
+    pub inner: Arc<FENSLLM>,
+    /// Cached Ensemble LLMs and their creation timestamps, keyed by ID.
+    pub cache:
+        lru::LruCache<String, (objectiveai::ensemble_llm::EnsembleLlm, u64)>,
+}
+
+impl<FENSLLM> GlobalCachingFetcher<FENSLLM> {
+    /// Creates a new global caching fetcher wrapping the given inner fetcher.
+    pub fn new(
+        inner: Arc<FENSLLM>,
+        max_entries: usize,
+        max_bytes: usize,
+    ) -> Self {
+        Self {
+            inner,
+            cache: lru::LruCache::new(max_entries, max_bytes),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<CTXEXT, FENSLLM> super::Fetcher<CTXEXT> for GlobalCachingFetcher<FENSLLM>
+where
+    CTXEXT: Send + Sync + 'static,
+    FENSLLM: super::Fetcher<CTXEXT> + Send + Sync + 'static,
+{
+    async fn fetch(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        id: &str,
+    ) -> Result<
+        Option<(objectiveai::ensemble_llm::EnsembleLlm, u64)>,
+        objectiveai::error::ResponseError,
+    > {
+        let id = id.to_owned();
+        let cached = self.cache.get(&id);
+        crate::metrics::METRICS
+            .record_cache_lookup("ensemble_llm", cached.is_some());
+        if let Some(cached) = cached {
+            return Ok(Some(cached));
+        }
+        let result = self.inner.fetch(ctx, &id).await?;
+        if let Some(ensemble_llm) = &result {
+            self.cache.insert(
+                id,
+                ensemble_llm.clone(),
+                lru::json_size(ensemble_llm),
+                None,
+            );
+        }
+        Ok(result)
+    }
+}
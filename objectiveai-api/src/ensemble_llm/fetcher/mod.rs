@@ -2,8 +2,10 @@
 
 mod caching_fetcher;
 mod fetcher;
+mod global_caching_fetcher;
 mod objectiveai;
 
 pub use caching_fetcher::*;
 pub use fetcher::*;
+pub use global_caching_fetcher::*;
 pub use objectiveai::*;
@@ -0,0 +1,170 @@
+//! Batch Function execution.
+
+use crate::{chat, ctx, functions, vector};
+use futures::{Stream, StreamExt};
+use std::sync::Arc;
+use tracing::Instrument;
+
+impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG, FFN, FPFL, FUSG>
+    super::Client<
+        CTXEXT,
+        FENSLLM,
+        CUSG,
+        FENS,
+        FVVOTE,
+        FCVOTE,
+        VUSG,
+        FFN,
+        FPFL,
+        FUSG,
+    >
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+    FENSLLM:
+        crate::ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+    CUSG: chat::completions::usage_handler::UsageHandler<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    FENS: crate::ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+    FVVOTE: vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    FCVOTE: vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    VUSG: vector::completions::usage_handler::UsageHandler<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    FFN: functions::function_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+    FPFL: functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+    FUSG: super::usage_handler::UsageHandler<CTXEXT> + Send + Sync + 'static,
+{
+    /// Executes a Function over many inputs and records usage.
+    ///
+    /// Items are executed with up to `concurrency` at once, clamped to the
+    /// client's `max_batch_concurrency`, and each is streamed as soon as it
+    /// finishes, followed by a summary with a retry token combining the retry
+    /// token of every item. Dropping the returned stream cancels the items
+    /// still running.
+    pub fn create_batch_streaming_handle_usage(
+        self: Arc<Self>,
+        ctx: ctx::Context<CTXEXT>,
+        params: objectiveai::functions::executions::batch::FunctionExecutionBatchCreateParams,
+    ) -> Result<
+        impl Stream<Item = objectiveai::functions::executions::batch::FunctionExecutionBatchChunk>
+        + Send
+        + 'static,
+        super::Error,
+    >{
+        let objectiveai::functions::executions::batch::FunctionExecutionBatchCreateParams {
+            request,
+            inputs,
+            concurrency,
+            retry_token,
+        } = params;
+        let inputs = inputs
+            .into_inputs()
+            .map_err(|(line, e)| super::Error::InvalidBatchInput(line, e))?;
+        let mut retry_token = match retry_token {
+            Some(retry_token) => {
+                objectiveai::functions::executions::batch::BatchRetryToken::try_from_string(
+                    &retry_token,
+                )
+                .ok_or(super::Error::InvalidRetryToken)?
+            }
+            None => objectiveai::functions::executions::batch::BatchRetryToken(
+                Vec::new(),
+            ),
+        };
+        let concurrency = concurrency
+            .unwrap_or(
+                objectiveai::functions::executions::batch::DEFAULT_BATCH_CONCURRENCY,
+            )
+            .clamp(1, self.max_batch_concurrency.max(1))
+            as usize;
+
+        // build one request per item from the shared request
+        let requests = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(index, input)| {
+                let mut request = request.clone();
+                let base = request.base_mut();
+                base.input = input;
+                base.retry_token = retry_token.get(index).map(str::to_owned);
+                (index, Arc::new(request))
+            })
+            .collect::<Vec<_>>();
+
+        let span = tracing::info_span!(
+            "function_execution_batch",
+            items = requests.len(),
+            concurrency,
+            succeeded = tracing::field::Empty,
+            failed = tracing::field::Empty,
+        );
+        let item_span = span.clone();
+        let mut items = futures::stream::iter(requests)
+            .map(move |(index, request)| {
+                let client = self.clone();
+                let ctx = ctx.clone();
+                let span = item_span.clone();
+                async move {
+                    match client.create_unary_handle_usage(ctx, request).await
+                    {
+                        Ok(execution) => objectiveai::functions::executions::batch::FunctionExecutionBatchItem {
+                            index: index as u64,
+                            execution: Some(execution),
+                            error: None,
+                        },
+                        Err(e) => objectiveai::functions::executions::batch::FunctionExecutionBatchItem {
+                            index: index as u64,
+                            execution: None,
+                            error: Some(objectiveai::error::ResponseError::from(&e)),
+                        },
+                    }
+                }
+                .instrument(span)
+            })
+            .buffer_unordered(concurrency);
+
+        Ok(async_stream::stream! {
+            let _in_flight =
+                crate::metrics::METRICS.in_flight_stream("function_batch");
+            let mut succeeded = 0;
+            let mut failed = 0;
+            while let Some(item) = items.next().await {
+                if item.error().is_some() {
+                    failed += 1;
+                } else {
+                    succeeded += 1;
+                }
+                // items which could not be started keep their previous token
+                if let Some(execution) = &item.execution {
+                    retry_token.set(
+                        item.index as usize,
+                        execution.retry_token.clone(),
+                    );
+                }
+                yield objectiveai::functions::executions::batch::FunctionExecutionBatchChunk::Item(Box::new(item));
+            }
+            span.record("succeeded", succeeded);
+            span.record("failed", failed);
+            yield objectiveai::functions::executions::batch::FunctionExecutionBatchChunk::Done(
+                objectiveai::functions::executions::batch::FunctionExecutionBatchSummary {
+                    succeeded,
+                    failed,
+                    retry_token: if retry_token.0.iter().any(Option::is_some) {
+                        Some(retry_token.to_string())
+                    } else {
+                        None
+                    },
+                },
+            );
+        })
+    }
+}
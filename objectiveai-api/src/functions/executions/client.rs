@@ -2,7 +2,7 @@
 
 use crate::{
     chat, ctx, functions,
-    util::{ChoiceIndexer, StreamOnce},
+    util::{ChoiceIndexer, InstrumentedStream, StreamOnce, until_closed},
     vector,
 };
 use futures::{Stream, StreamExt, TryStreamExt};
@@ -13,6 +13,7 @@ use std::{
     sync::{Arc, LazyLock},
     time,
 };
+use tracing::Instrument;
 
 /// Generates a unique response ID for scalar Function executions.
 pub fn scalar_response_id(created: u64) -> String {
@@ -66,6 +67,9 @@ pub struct Client<
     pub profile_fetcher: Arc<FPFL>,
     /// Handler for recording usage after execution.
     pub usage_handler: Arc<FUSG>,
+    /// Maximum number of batch items executed at once, whatever a batch
+    /// request asks for.
+    pub max_batch_concurrency: u64,
 }
 
 impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG, FFN, FPFL, FUSG>
@@ -91,6 +95,7 @@ impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG, FFN, FPFL, FUSG>
         function_fetcher: Arc<FFN>,
         profile_fetcher: Arc<FPFL>,
         usage_handler: Arc<FUSG>,
+        max_batch_concurrency: u64,
     ) -> Self {
         Self {
             chat_client,
@@ -99,6 +104,7 @@ impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG, FFN, FPFL, FUSG>
             function_fetcher,
             profile_fetcher,
             usage_handler,
+            max_batch_concurrency,
         }
     }
 }
@@ -158,6 +164,8 @@ where
     /// Executes a Function with streaming output and records usage.
     ///
     /// Streams chunks as they become available and records usage after completion.
+    /// Dropping the returned stream cancels the remaining tasks, and usage of
+    /// the tasks which already finished is still recorded.
     pub async fn create_streaming_handle_usage(
         self: Arc<Self>,
         ctx: ctx::Context<CTXEXT>,
@@ -169,12 +177,34 @@ where
         + 'static,
         super::Error,
     >{
+        let span = tracing::info_span!(
+            "function_execution",
+            function = request
+                .remote_function()
+                .map(|(owner, repository, _)| format!("{owner}/{repository}"))
+                .unwrap_or_else(|| "inline".to_string()),
+            profile = request
+                .remote_profile()
+                .map(|(owner, repository, _)| format!("{owner}/{repository}"))
+                .unwrap_or_else(|| "inline".to_string()),
+            id = tracing::field::Empty,
+            prompt_tokens = tracing::field::Empty,
+            completion_tokens = tracing::field::Empty,
+            cost = tracing::field::Empty,
+            error = tracing::field::Empty,
+            cancelled = tracing::field::Empty,
+        );
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
         tokio::spawn(async move {
+            let in_flight =
+                crate::metrics::METRICS.in_flight_stream("function");
             let mut aggregate: Option<
                 objectiveai::functions::executions::response::streaming::FunctionExecutionChunk,
             > = None;
-            let mut any_usage = false;
+            // usage of the completions which finished, for when the client
+            // disconnects before the final chunk carrying the total
+            let mut usage =
+                objectiveai::vector::completions::response::Usage::default();
             let stream = match self
                 .clone()
                 .create_streaming(ctx.clone(), request.clone())
@@ -182,13 +212,30 @@ where
             {
                 Ok(stream) => stream,
                 Err(e) => {
+                    tracing::Span::current()
+                        .record("error", tracing::field::display(&e));
                     let _ = tx.send(Err(e));
                     return;
                 }
             };
+            let stream = until_closed(stream, &tx);
             futures::pin_mut!(stream);
             while let Some(chunk) = stream.next().await {
-                any_usage |= chunk.any_usage();
+                for task in chunk.vector_completion_tasks() {
+                    for completion in &task.inner.completions {
+                        if let Some(completion_usage) = &completion.inner.usage
+                        {
+                            usage.push_chat_completion_usage(completion_usage);
+                        }
+                    }
+                }
+                if let Some(reasoning_usage) = chunk
+                    .reasoning
+                    .as_ref()
+                    .and_then(|reasoning| reasoning.inner.usage.as_ref())
+                {
+                    usage.push_chat_completion_usage(reasoning_usage);
+                }
                 match &mut aggregate {
                     Some(aggregate) => aggregate.push(&chunk),
                     None => aggregate = Some(chunk.clone()),
@@ -196,13 +243,37 @@ where
                 let _ = tx.send(Ok(chunk));
             }
             drop(stream);
+            let completed = !tx.is_closed();
+            if !completed {
+                tracing::Span::current().record("cancelled", true);
+            }
             drop(tx);
-            if any_usage {
+            drop(in_flight);
+            if let Some(aggregate) = &mut aggregate
+                && aggregate.usage.is_none()
+            {
+                aggregate.usage = Some(usage);
+            }
+            if let Some(aggregate) = &aggregate {
+                let span = tracing::Span::current();
+                span.record("id", aggregate.id.as_str());
+                if let Some(usage) = &aggregate.usage {
+                    span.record("prompt_tokens", usage.prompt_tokens);
+                    span.record("completion_tokens", usage.completion_tokens);
+                    span.record("cost", tracing::field::display(usage.cost));
+                }
+                if let Some(error) = &aggregate.error {
+                    span.record("error", tracing::field::display(&error.message));
+                }
+            }
+            if let Some(aggregate) = aggregate
+                && aggregate.any_usage()
+            {
                 self.usage_handler
-                    .handle_usage(ctx, request, aggregate.unwrap().into())
+                    .handle_usage(ctx, request, aggregate.into(), completed)
                     .await;
             }
-        });
+        }.instrument(span));
         let mut stream =
             tokio_stream::wrappers::UnboundedReceiverStream::new(rx);
         match stream.next().await {
@@ -1390,7 +1461,21 @@ where
         swiss_round: Option<u64>,
         swiss_pool_index: Option<u64>,
     ) -> futures::stream::BoxStream<'static, FtpStreamChunk> {
-        match ftp {
+        let span = tracing::info_span!(
+            "task",
+            task_index,
+            kind = match &ftp {
+                functions::FlatTaskProfile::Function(_) => "function",
+                functions::FlatTaskProfile::MapFunction(_) => "map_function",
+                functions::FlatTaskProfile::VectorCompletion(_) =>
+                    "vector_completion",
+                functions::FlatTaskProfile::MapVectorCompletion(_) =>
+                    "map_vector_completion",
+            },
+            swiss_round,
+            swiss_pool_index,
+        );
+        let stream = match ftp {
             functions::FlatTaskProfile::Function(function_ftp) => self
                 .clone()
                 .execute_function_ftp_streaming(
@@ -1447,7 +1532,8 @@ where
                 .flatten()
                 .boxed()
             }
-        }
+        };
+        InstrumentedStream::new(stream, span).boxed()
     }
 
     fn execute_map_function_ftp_streaming(
@@ -1904,6 +1990,7 @@ where
                     votes: Vec::new(),
                     scores: Vec::new(),
                     weights: Vec::new(),
+                    uncertainty: None,
                 },
             );
         }
@@ -2015,6 +2102,7 @@ where
                         first_chunk_timeout: request_base.first_chunk_timeout,
                         other_chunk_timeout: request_base.other_chunk_timeout,
                         responses: ftp.responses,
+                        uncertainty: request_base.uncertainty,
                     },
                 ),
             )
@@ -47,8 +47,13 @@ pub enum Error {
     #[error("vector completion error: {0}")]
     Vector(#[from] vector::completions::Error),
     /// The input does not match the Function's input schema.
-    #[error("Input does not match function input schema")]
-    InputSchemaMismatch,
+    #[error(
+        "Input does not match function input schema: {}",
+        join_violations(.0)
+    )]
+    InputSchemaMismatch(
+        Vec<objectiveai::functions::expression::InputSchemaViolation>,
+    ),
     /// Scalar output is not in [0, 1] range.
     #[error("invalid scalar output, expected number between 0 and 1")]
     InvalidScalarOutput,
@@ -63,6 +68,19 @@ pub enum Error {
     /// Invalid Strategy
     #[error("invalid strategy: {0}")]
     InvalidStrategy(String),
+    /// A line of a batch's JSONL inputs is not a valid input.
+    #[error("invalid batch input on line {0}: {1}")]
+    InvalidBatchInput(usize, serde_json::Error),
+}
+
+fn join_violations(
+    violations: &[objectiveai::functions::expression::InputSchemaViolation],
+) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 impl objectiveai::error::StatusError for Error {
@@ -81,11 +99,12 @@ impl objectiveai::error::StatusError for Error {
             Error::InvalidRetryToken => 400,
             Error::InvalidAppExpression(_) => 400,
             Error::Vector(e) => e.status(),
-            Error::InputSchemaMismatch => 400,
+            Error::InputSchemaMismatch(_) => 400,
             Error::InvalidScalarOutput => 400,
             Error::InvalidVectorOutput(_) => 400,
             Error::InvalidFunctionForStrategy(_) => 400,
             Error::InvalidStrategy(_) => 400,
+            Error::InvalidBatchInput(_, _) => 400,
         }
     }
 
@@ -145,9 +164,10 @@ impl objectiveai::error::StatusError for Error {
                     "kind": "vector_completion",
                     "error": e.message(),
                 }),
-                Error::InputSchemaMismatch => serde_json::json!({
+                Error::InputSchemaMismatch(violations) => serde_json::json!({
                     "kind": "input_schema_mismatch",
                     "error": "Input does not match function input schema",
+                    "violations": violations,
                 }),
                 Error::InvalidScalarOutput => serde_json::json!({
                     "kind": "invalid_scalar_output",
@@ -165,6 +185,10 @@ impl objectiveai::error::StatusError for Error {
                     "kind": "invalid_strategy",
                     "error": msg,
                 }),
+                Error::InvalidBatchInput(line, e) => serde_json::json!({
+                    "kind": "invalid_batch_input",
+                    "error": format!("invalid batch input on line {}: {}", line, e),
+                }),
             }
         }))
     }
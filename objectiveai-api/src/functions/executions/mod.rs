@@ -4,8 +4,10 @@
 //! the tasks (Vector Completions or nested Functions) in parallel. Handles
 //! streaming output, retry tokens, and reasoning summaries.
 
+mod batch;
 mod client;
 mod error;
+pub mod store;
 pub mod usage_handler;
 
 pub use client::*;
@@ -0,0 +1,53 @@
+//! Error types for the Function execution store.
+
+/// Errors that can occur when retrieving persisted Function executions.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The requested execution was not found.
+    #[error("function execution not found")]
+    ExecutionNotFound,
+    /// The execution ID cannot be persisted.
+    #[error("invalid function execution id: {0}")]
+    InvalidExecutionId(String),
+    /// Failed to read or write persisted executions.
+    #[error("function execution store error: {0}")]
+    Store(#[from] std::io::Error),
+    /// A persisted execution could not be deserialized.
+    #[error("invalid stored function execution: {0}")]
+    InvalidStoredExecution(#[from] serde_json::Error),
+}
+
+impl objectiveai::error::StatusError for Error {
+    fn status(&self) -> u16 {
+        match self {
+            Error::ExecutionNotFound => 404,
+            Error::InvalidExecutionId(_) => 400,
+            Error::Store(_) => 500,
+            Error::InvalidStoredExecution(_) => 500,
+        }
+    }
+
+    fn message(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "kind": "function_executions",
+            "error": match self {
+                Error::ExecutionNotFound => serde_json::json!({
+                    "kind": "function_execution_not_found",
+                    "error": "function execution not found",
+                }),
+                Error::InvalidExecutionId(id) => serde_json::json!({
+                    "kind": "invalid_function_execution_id",
+                    "error": format!("invalid function execution id: {id}"),
+                }),
+                Error::Store(e) => serde_json::json!({
+                    "kind": "store",
+                    "error": e.to_string(),
+                }),
+                Error::InvalidStoredExecution(e) => serde_json::json!({
+                    "kind": "invalid_stored_function_execution",
+                    "error": e.to_string(),
+                }),
+            },
+        }))
+    }
+}
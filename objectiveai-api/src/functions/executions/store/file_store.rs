@@ -0,0 +1,128 @@
+//! Directory-backed Function execution store.
+
+use std::path::PathBuf;
+
+/// Function execution store which persists each execution as a JSON file in
+/// a directory.
+pub struct FileStore {
+    /// The directory holding the execution files.
+    pub directory: PathBuf,
+}
+
+impl FileStore {
+    /// Creates a new file store, creating `directory` if needed.
+    pub async fn new(
+        directory: impl Into<PathBuf>,
+    ) -> Result<Self, super::Error> {
+        let directory = directory.into();
+        tokio::fs::create_dir_all(&directory).await?;
+        Ok(Self { directory })
+    }
+
+    /// Returns the path of an execution's file, or `None` if the ID is not
+    /// one this server could have generated.
+    fn path(&self, id: &str) -> Option<PathBuf> {
+        if id.is_empty()
+            || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return None;
+        }
+        Some(self.directory.join(format!("{id}.json")))
+    }
+}
+
+#[async_trait::async_trait]
+impl super::Store for FileStore {
+    async fn put(
+        &self,
+        owner: Option<&str>,
+        execution: &objectiveai::functions::executions::StoredFunctionExecution,
+    ) -> Result<(), super::Error> {
+        let Some(path) = self.path(&execution.inner.id) else {
+            return Err(super::Error::InvalidExecutionId(
+                execution.inner.id.clone(),
+            ));
+        };
+        let json = serde_json::to_vec(&super::store::Record {
+            owner: owner.map(str::to_owned),
+            execution: execution.clone(),
+        })?;
+        // write then rename, so a crash never leaves a partial file
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, json).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        owner: Option<&str>,
+        id: &str,
+    ) -> Result<
+        Option<objectiveai::functions::executions::StoredFunctionExecution>,
+        super::Error,
+    > {
+        let Some(path) = self.path(id) else {
+            return Ok(None);
+        };
+        let json = match tokio::fs::read(&path).await {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let record: super::store::Record = serde_json::from_slice(&json)?;
+        Ok((record.owner.as_deref() == owner).then_some(record.execution))
+    }
+
+    async fn list(
+        &self,
+        owner: Option<&str>,
+        params: &objectiveai::functions::executions::ListFunctionExecutionsParams,
+    ) -> Result<
+        Vec<objectiveai::functions::executions::StoredFunctionExecution>,
+        super::Error,
+    > {
+        let mut executions = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            // skip unreadable files rather than failing the whole listing
+            let record = match tokio::fs::read(&path).await {
+                Ok(json) => {
+                    match serde_json::from_slice::<super::store::Record>(&json)
+                    {
+                        Ok(record) => record,
+                        Err(e) => {
+                            tracing::warn!(
+                                path = %path.display(),
+                                error = %e,
+                                "skipping invalid stored function execution"
+                            );
+                            continue;
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        path = %path.display(),
+                        error = %e,
+                        "skipping unreadable stored function execution"
+                    );
+                    continue;
+                }
+            };
+            if record.owner.as_deref() == owner
+                && params.matches(&record.execution)
+            {
+                executions.push(record.execution);
+            }
+        }
+        super::store::sort_and_limit(&mut executions, params);
+        Ok(executions)
+    }
+}
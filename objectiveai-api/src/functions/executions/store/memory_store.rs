@@ -0,0 +1,94 @@
+//! In-memory Function execution store.
+
+use indexmap::IndexMap;
+use std::sync::Mutex;
+
+/// Function execution store which keeps executions in memory.
+///
+/// Executions are lost when the server restarts. Once `max_entries`
+/// executions are stored, the oldest are evicted to make room.
+pub struct MemoryStore {
+    /// Maximum number of executions kept.
+    pub max_entries: usize,
+    executions: Mutex<IndexMap<String, super::store::Record>>,
+}
+
+impl MemoryStore {
+    /// Creates a new, empty in-memory execution store which keeps at most
+    /// `max_entries` executions.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            executions: Mutex::new(IndexMap::new()),
+        }
+    }
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+#[async_trait::async_trait]
+impl super::Store for MemoryStore {
+    async fn put(
+        &self,
+        owner: Option<&str>,
+        execution: &objectiveai::functions::executions::StoredFunctionExecution,
+    ) -> Result<(), super::Error> {
+        let mut executions = self.executions.lock().unwrap();
+        executions.shift_remove(&execution.inner.id);
+        executions.insert(
+            execution.inner.id.clone(),
+            super::store::Record {
+                owner: owner.map(str::to_owned),
+                execution: execution.clone(),
+            },
+        );
+        while executions.len() > self.max_entries {
+            executions.shift_remove_index(0);
+        }
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        owner: Option<&str>,
+        id: &str,
+    ) -> Result<
+        Option<objectiveai::functions::executions::StoredFunctionExecution>,
+        super::Error,
+    > {
+        Ok(self
+            .executions
+            .lock()
+            .unwrap()
+            .get(id)
+            .filter(|record| record.owner.as_deref() == owner)
+            .map(|record| record.execution.clone()))
+    }
+
+    async fn list(
+        &self,
+        owner: Option<&str>,
+        params: &objectiveai::functions::executions::ListFunctionExecutionsParams,
+    ) -> Result<
+        Vec<objectiveai::functions::executions::StoredFunctionExecution>,
+        super::Error,
+    > {
+        let mut executions = self
+            .executions
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|record| {
+                record.owner.as_deref() == owner
+                    && params.matches(&record.execution)
+            })
+            .map(|record| record.execution.clone())
+            .collect();
+        super::store::sort_and_limit(&mut executions, params);
+        Ok(executions)
+    }
+}
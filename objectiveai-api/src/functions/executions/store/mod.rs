@@ -0,0 +1,15 @@
+//! Persistence for completed Function executions.
+//!
+//! Provides the [`Store`] trait and implementations which keep executions
+//! in memory or persist them to a directory so they survive restarts.
+//! Executions are persisted by [`super::usage_handler::StoreUsageHandler`].
+
+mod error;
+mod file_store;
+mod memory_store;
+mod store;
+
+pub use error::*;
+pub use file_store::*;
+pub use memory_store::*;
+pub use store::*;
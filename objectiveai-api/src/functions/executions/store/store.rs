@@ -0,0 +1,103 @@
+//! Function execution store trait definition.
+
+/// Trait for persisting completed Function executions.
+///
+/// Each execution belongs to the caller identified by the `owner` it was put
+/// with, see [`crate::ctx::ContextExt::owner`], and is only retrieved or
+/// listed for the same `owner`.
+#[async_trait::async_trait]
+pub trait Store {
+    /// Inserts or replaces an execution of `owner`.
+    async fn put(
+        &self,
+        owner: Option<&str>,
+        execution: &objectiveai::functions::executions::StoredFunctionExecution,
+    ) -> Result<(), super::Error>;
+
+    /// Retrieves an execution of `owner` by ID.
+    async fn get(
+        &self,
+        owner: Option<&str>,
+        id: &str,
+    ) -> Result<
+        Option<objectiveai::functions::executions::StoredFunctionExecution>,
+        super::Error,
+    >;
+
+    /// Lists the executions of `owner` matching `params`, newest first.
+    async fn list(
+        &self,
+        owner: Option<&str>,
+        params: &objectiveai::functions::executions::ListFunctionExecutionsParams,
+    ) -> Result<
+        Vec<objectiveai::functions::executions::StoredFunctionExecution>,
+        super::Error,
+    >;
+}
+
+#[async_trait::async_trait]
+impl<A, B> Store for either::Either<A, B>
+where
+    A: Store + Send + Sync,
+    B: Store + Send + Sync,
+{
+    async fn put(
+        &self,
+        owner: Option<&str>,
+        execution: &objectiveai::functions::executions::StoredFunctionExecution,
+    ) -> Result<(), super::Error> {
+        match self {
+            either::Either::Left(store) => store.put(owner, execution).await,
+            either::Either::Right(store) => store.put(owner, execution).await,
+        }
+    }
+
+    async fn get(
+        &self,
+        owner: Option<&str>,
+        id: &str,
+    ) -> Result<
+        Option<objectiveai::functions::executions::StoredFunctionExecution>,
+        super::Error,
+    > {
+        match self {
+            either::Either::Left(store) => store.get(owner, id).await,
+            either::Either::Right(store) => store.get(owner, id).await,
+        }
+    }
+
+    async fn list(
+        &self,
+        owner: Option<&str>,
+        params: &objectiveai::functions::executions::ListFunctionExecutionsParams,
+    ) -> Result<
+        Vec<objectiveai::functions::executions::StoredFunctionExecution>,
+        super::Error,
+    > {
+        match self {
+            either::Either::Left(store) => store.list(owner, params).await,
+            either::Either::Right(store) => store.list(owner, params).await,
+        }
+    }
+}
+
+/// A persisted execution, along with the caller it belongs to.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(super) struct Record {
+    pub owner: Option<String>,
+    pub execution: objectiveai::functions::executions::StoredFunctionExecution,
+}
+
+/// Sorts executions newest first and truncates them to `params.limit`.
+pub(super) fn sort_and_limit(
+    executions: &mut Vec<
+        objectiveai::functions::executions::StoredFunctionExecution,
+    >,
+    params: &objectiveai::functions::executions::ListFunctionExecutionsParams,
+) {
+    executions
+        .sort_by_key(|execution| std::cmp::Reverse(execution.inner.created));
+    if let Some(limit) = params.limit {
+        executions.truncate(limit as usize);
+    }
+}
@@ -16,6 +16,7 @@ where
         _ctx: ctx::Context<CTXEXT>,
         _request: Arc<objectiveai::functions::executions::request::Request>,
         response: objectiveai::functions::executions::response::unary::FunctionExecution,
+        _completed: bool,
     ) {
         println!(
             "[{}] cost: {}",
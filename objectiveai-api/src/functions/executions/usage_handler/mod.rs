@@ -4,7 +4,9 @@
 //! Function execution completes.
 
 mod log_usage_handler;
+mod store_usage_handler;
 mod usage_handler;
 
 pub use log_usage_handler::*;
+pub use store_usage_handler::*;
 pub use usage_handler::*;
@@ -0,0 +1,109 @@
+//! Usage handler which persists completed executions.
+
+use crate::{ctx, functions};
+use std::sync::Arc;
+
+/// Usage handler which persists each execution to a
+/// [`super::super::store::Store`] before passing it on to another handler.
+///
+/// Executions are persisted for the caller which started them, see
+/// [`ctx::ContextExt::owner`].
+///
+/// Along with the execution, the request and the tasks compiled from the
+/// Function and input are persisted. Only executions which reach the usage
+/// handler are persisted, so executions which used no upstream tokens, such
+/// as those fully replayed from a retry token, are not. Neither are
+/// cancelled executions, whose tasks are incomplete.
+pub struct StoreUsageHandler<FFN, STORE, FUSG> {
+    /// Fetcher for the Function definitions to compile tasks from.
+    pub function_fetcher: Arc<FFN>,
+    /// The store executions are persisted to.
+    pub store: Arc<STORE>,
+    /// The handler executions are passed on to.
+    pub inner: Arc<FUSG>,
+}
+
+impl<FFN, STORE, FUSG> StoreUsageHandler<FFN, STORE, FUSG> {
+    /// Creates a new store usage handler.
+    pub fn new(
+        function_fetcher: Arc<FFN>,
+        store: Arc<STORE>,
+        inner: Arc<FUSG>,
+    ) -> Self {
+        Self {
+            function_fetcher,
+            store,
+            inner,
+        }
+    }
+
+    /// Compiles the tasks of the executed Function, fetching it again at the
+    /// commit it was executed at if it is remote.
+    async fn compiled_tasks<CTXEXT>(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        request: &objectiveai::functions::executions::request::Request,
+        response: &objectiveai::functions::executions::response::unary::FunctionExecution,
+    ) -> Option<Vec<Option<objectiveai::functions::CompiledTask>>>
+    where
+        FFN: functions::function_fetcher::Fetcher<CTXEXT>,
+    {
+        let function = match request.inline_function() {
+            Some(function) => {
+                objectiveai::functions::Function::Inline(function.clone())
+            }
+            None => {
+                let mut id = response.function.as_deref()?.splitn(3, '/');
+                let (owner, repository, commit) =
+                    (id.next()?, id.next()?, id.next());
+                let function = self
+                    .function_fetcher
+                    .fetch(ctx, owner, repository, commit)
+                    .await
+                    .ok()??;
+                objectiveai::functions::Function::Remote(function.inner)
+            }
+        };
+        function.compile_tasks(&request.base().input).ok()
+    }
+}
+
+#[async_trait::async_trait]
+impl<CTXEXT, FFN, STORE, FUSG> super::UsageHandler<CTXEXT>
+    for StoreUsageHandler<FFN, STORE, FUSG>
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+    FFN: functions::function_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+    STORE: super::super::store::Store + Send + Sync + 'static,
+    FUSG: super::UsageHandler<CTXEXT> + Send + Sync + 'static,
+{
+    async fn handle_usage(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        request: Arc<objectiveai::functions::executions::request::Request>,
+        response: objectiveai::functions::executions::response::unary::FunctionExecution,
+        completed: bool,
+    ) {
+        if completed {
+            let execution =
+                objectiveai::functions::executions::StoredFunctionExecution {
+                    request: (*request).clone(),
+                    compiled_tasks: self
+                        .compiled_tasks(ctx.clone(), &request, &response)
+                        .await,
+                    inner: response.clone(),
+                };
+            if let Err(e) =
+                self.store.put(ctx.ext.owner().as_deref(), &execution).await
+            {
+                tracing::warn!(
+                    error = %e,
+                    "failed to persist function execution"
+                );
+            }
+        }
+        self.inner
+            .handle_usage(ctx, request, response, completed)
+            .await;
+    }
+}
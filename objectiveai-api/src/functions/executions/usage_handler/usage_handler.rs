@@ -6,11 +6,44 @@ use std::sync::Arc;
 /// Handler for recording usage after Function execution.
 #[async_trait::async_trait]
 pub trait UsageHandler<CTXEXT> {
-    /// Records usage from a completed Function execution.
+    /// Records usage from a Function execution.
+    ///
+    /// `completed` is false if the execution was cancelled before it finished,
+    /// in which case `response` only holds the tasks which finished.
     async fn handle_usage(
         &self,
         ctx: ctx::Context<CTXEXT>,
         request: Arc<objectiveai::functions::executions::request::Request>,
         response: objectiveai::functions::executions::response::unary::FunctionExecution,
+        completed: bool,
     );
 }
+
+#[async_trait::async_trait]
+impl<CTXEXT, A, B> UsageHandler<CTXEXT> for either::Either<A, B>
+where
+    CTXEXT: Send + Sync + 'static,
+    A: UsageHandler<CTXEXT> + Send + Sync,
+    B: UsageHandler<CTXEXT> + Send + Sync,
+{
+    async fn handle_usage(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        request: Arc<objectiveai::functions::executions::request::Request>,
+        response: objectiveai::functions::executions::response::unary::FunctionExecution,
+        completed: bool,
+    ) {
+        match self {
+            either::Either::Left(handler) => {
+                handler
+                    .handle_usage(ctx, request, response, completed)
+                    .await
+            }
+            either::Either::Right(handler) => {
+                handler
+                    .handle_usage(ctx, request, response, completed)
+                    .await
+            }
+        }
+    }
+}
@@ -472,8 +472,11 @@ where
 
     // validate input against input_schema
     if let Some(input_schema) = function.input_schema() {
-        if !input_schema.validate_input(&input) {
-            return Err(super::executions::Error::InputSchemaMismatch);
+        let violations = input_schema.violations(&input);
+        if !violations.is_empty() {
+            return Err(super::executions::Error::InputSchemaMismatch(
+                violations,
+            ));
         }
     }
 
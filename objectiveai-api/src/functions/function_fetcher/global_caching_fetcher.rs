@@ -0,0 +1,97 @@
+//! Process-wide caching wrapper for Function fetchers.
+
+use crate::{ctx, lru};
+use std::{sync::Arc, time::Duration};
+
+/// Cache key for a Function: owner, repository, and optional commit SHA.
+pub type FunctionCacheKey = (String, String, Option<String>);
+
+/// Wraps a Function fetcher with a process-wide LRU cache.
+///
+/// Functions fetched by commit SHA are immutable and cached without a TTL.
+/// Functions fetched without a commit resolve to the latest commit, so those
+/// entries expire after `unpinned_ttl`. The resolved commit is also cached
+/// as a pinned entry. Not-found results and errors are not cached.
+#[derive(Debug)]
+pub struct GlobalCachingFetcher<FFN> {
+    /// The underlying fetcher to delegate to on cache miss.
+    pub inner: Arc<FFN>,
+    /// Cached Functions keyed by owner, repository, and commit.
+    pub cache: lru::LruCache<
+        FunctionCacheKey,
+        objectiveai::functions::response::GetFunction,
+    >,
+    /// How long lookups without a commit are cached.
+    pub unpinned_ttl: Duration,
+}
+
+impl<FFN> GlobalCachingFetcher<FFN> {
+    /// Creates a new global caching fetcher wrapping the given inner fetcher.
+    pub fn new(
+        inner: Arc<FFN>,
+        max_entries: usize,
+        max_bytes: usize,
+        unpinned_ttl: Duration,
+    ) -> Self {
+        Self {
+            inner,
+            cache: lru::LruCache::new(max_entries, max_bytes),
+            unpinned_ttl,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<CTXEXT, FFN> super::Fetcher<CTXEXT> for GlobalCachingFetcher<FFN>
+where
+    CTXEXT: Send + Sync + 'static,
+    FFN: super::Fetcher<CTXEXT> + Send + Sync + 'static,
+{
+    async fn fetch(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> Result<
+        Option<objectiveai::functions::response::GetFunction>,
+        objectiveai::error::ResponseError,
+    > {
+        let key = (
+            owner.to_owned(),
+            repository.to_owned(),
+            commit.map(str::to_owned),
+        );
+        let cached = self.cache.get(&key);
+        crate::metrics::METRICS
+            .record_cache_lookup("function", cached.is_some());
+        if let Some(cached) = cached {
+            return Ok(Some(cached));
+        }
+        let result = self.inner.fetch(ctx, owner, repository, commit).await?;
+        if let Some(function) = &result {
+            let size = lru::json_size(function);
+            if commit.is_none() {
+                self.cache.insert(
+                    (
+                        function.owner.clone(),
+                        function.repository.clone(),
+                        Some(function.commit.clone()),
+                    ),
+                    function.clone(),
+                    size,
+                    None,
+                );
+                self.cache.insert(
+                    key,
+                    function.clone(),
+                    size,
+                    Some(self.unpinned_ttl),
+                );
+            } else {
+                self.cache.insert(key, function.clone(), size, None);
+            }
+        }
+        Ok(result)
+    }
+}
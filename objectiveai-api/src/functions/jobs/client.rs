@@ -0,0 +1,530 @@
+//! Job client implementation.
+
+use crate::{chat, ctx, functions, vector};
+use dashmap::DashMap;
+use futures::{Stream, StreamExt};
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tracing::Instrument;
+
+/// Number of chunks buffered for each reattached stream. A stream which
+/// falls further behind ends, and the client may reattach.
+const CHANNEL_CAPACITY: usize = 1024;
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// The Function executions client a job [`Client`] runs executions with.
+pub type FunctionExecutionsClient<
+    CTXEXT,
+    FENSLLM,
+    CUSG,
+    FENS,
+    FVVOTE,
+    FCVOTE,
+    VUSG,
+    FFN,
+    FPFL,
+    FUSG,
+> = Arc<
+    functions::executions::Client<
+        CTXEXT,
+        FENSLLM,
+        CUSG,
+        FENS,
+        FVVOTE,
+        FCVOTE,
+        VUSG,
+        FFN,
+        FPFL,
+        FUSG,
+    >,
+>;
+
+/// A job running on this server.
+struct RunningJob<CTXEXT> {
+    /// The context of the submitting request.
+    ctx: ctx::Context<CTXEXT>,
+    /// The job, updated as chunks arrive.
+    job: Mutex<super::Job>,
+    /// Broadcasts chunks to reattached streams.
+    tx: tokio::sync::broadcast::Sender<super::JobChunk>,
+    /// Aborts the task running the job.
+    abort: Mutex<Option<tokio::task::AbortHandle>>,
+}
+
+/// Client for running Function executions and Profile computations as
+/// jobs which outlive the HTTP request that submitted them.
+///
+/// Jobs are persisted to a [`super::Store`] as they progress. After a
+/// restart, [`Client::resume`] restarts the jobs which were still running,
+/// passing the retry token of the work already completed so that its votes
+/// are reused rather than recomputed.
+pub struct Client<
+    CTXEXT,
+    FENSLLM,
+    CUSG,
+    FENS,
+    FVVOTE,
+    FCVOTE,
+    VUSG,
+    FFN,
+    FPFL,
+    FUSG,
+    PCOMP,
+    STORE,
+> {
+    /// Client for running Function executions.
+    pub function_executions_client: FunctionExecutionsClient<
+        CTXEXT,
+        FENSLLM,
+        CUSG,
+        FENS,
+        FVVOTE,
+        FCVOTE,
+        VUSG,
+        FFN,
+        FPFL,
+        FUSG,
+    >,
+    /// Client for running Profile computations.
+    pub profile_computations_client: Arc<PCOMP>,
+    /// Store for persisting jobs.
+    pub store: Arc<STORE>,
+    /// Minimum time between persisting the progress of a running job.
+    pub persist_interval: Duration,
+    running: DashMap<String, Arc<RunningJob<CTXEXT>>>,
+}
+
+impl<
+    CTXEXT,
+    FENSLLM,
+    CUSG,
+    FENS,
+    FVVOTE,
+    FCVOTE,
+    VUSG,
+    FFN,
+    FPFL,
+    FUSG,
+    PCOMP,
+    STORE,
+>
+    Client<
+        CTXEXT,
+        FENSLLM,
+        CUSG,
+        FENS,
+        FVVOTE,
+        FCVOTE,
+        VUSG,
+        FFN,
+        FPFL,
+        FUSG,
+        PCOMP,
+        STORE,
+    >
+{
+    /// Creates a new job client.
+    pub fn new(
+        function_executions_client: FunctionExecutionsClient<
+            CTXEXT,
+            FENSLLM,
+            CUSG,
+            FENS,
+            FVVOTE,
+            FCVOTE,
+            VUSG,
+            FFN,
+            FPFL,
+            FUSG,
+        >,
+        profile_computations_client: Arc<PCOMP>,
+        store: Arc<STORE>,
+        persist_interval: Duration,
+    ) -> Self {
+        Self {
+            function_executions_client,
+            profile_computations_client,
+            store,
+            persist_interval,
+            running: DashMap::new(),
+        }
+    }
+}
+
+impl<
+    CTXEXT,
+    FENSLLM,
+    CUSG,
+    FENS,
+    FVVOTE,
+    FCVOTE,
+    VUSG,
+    FFN,
+    FPFL,
+    FUSG,
+    PCOMP,
+    STORE,
+>
+    Client<
+        CTXEXT,
+        FENSLLM,
+        CUSG,
+        FENS,
+        FVVOTE,
+        FCVOTE,
+        VUSG,
+        FFN,
+        FPFL,
+        FUSG,
+        PCOMP,
+        STORE,
+    >
+where
+    CTXEXT: ctx::ContextExt + Clone + Send + Sync + 'static,
+    FENSLLM:
+        crate::ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+    CUSG: chat::completions::usage_handler::UsageHandler<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    FENS: crate::ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+    FVVOTE: vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    FCVOTE: vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    VUSG: vector::completions::usage_handler::UsageHandler<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    FFN: functions::function_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+    FPFL: functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+    FUSG: functions::executions::usage_handler::UsageHandler<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    PCOMP: functions::profiles::computations::Client<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    STORE: super::Store<CTXEXT> + Send + Sync + 'static,
+{
+    /// Submits a job and starts running it in the background.
+    pub fn create(
+        self: Arc<Self>,
+        ctx: ctx::Context<CTXEXT>,
+        request: super::JobRequest,
+    ) -> super::Job {
+        let created = now();
+        self.start(
+            ctx,
+            super::Job {
+                id: super::job_id(created),
+                status: super::JobStatus::Running,
+                created,
+                updated: created,
+                attempts: 0,
+                request,
+                result: None,
+                retry_token: None,
+                error: None,
+            },
+        )
+    }
+
+    /// Retrieves a job of `owner` and its progress.
+    ///
+    /// Jobs submitted by other callers are not found, see
+    /// [`ctx::ContextExt::owner`].
+    pub async fn get(
+        &self,
+        owner: Option<&str>,
+        id: &str,
+    ) -> Result<super::Job, super::Error> {
+        if let Some(running) = self.running(owner, id) {
+            return Ok(running.job.lock().unwrap().clone());
+        }
+        Ok(self.stored(owner, id).await?.job)
+    }
+
+    /// Streams the output of a job of `owner`, reattaching to it if it is
+    /// still running.
+    ///
+    /// The first chunk is the aggregate of the output so far, after which
+    /// new chunks are streamed as they arrive until the job finishes.
+    pub async fn stream(
+        &self,
+        owner: Option<&str>,
+        id: &str,
+    ) -> Result<
+        impl Stream<Item = super::JobChunk> + Send + 'static,
+        super::Error,
+    > {
+        let (snapshot, rx) = match self.running(owner, id) {
+            Some(running) => {
+                // subscribe under the lock, so no chunk is missed or repeated
+                let job = running.job.lock().unwrap();
+                (job.result.clone(), Some(running.tx.subscribe()))
+            }
+            None => (self.stored(owner, id).await?.job.result, None),
+        };
+        Ok(async_stream::stream! {
+            if let Some(snapshot) = snapshot {
+                yield snapshot;
+            }
+            if let Some(mut rx) = rx {
+                // a lagging receiver can no longer reassemble the output
+                while let Ok(chunk) = rx.recv().await {
+                    yield chunk;
+                }
+            }
+        }
+        .boxed())
+    }
+
+    /// Cancels a running job of `owner`. Finished jobs are returned
+    /// unchanged.
+    pub async fn cancel(
+        &self,
+        owner: Option<&str>,
+        id: &str,
+    ) -> Result<super::Job, super::Error> {
+        let Some((_, running)) = self.running.remove_if(id, |_, running| {
+            running.ctx.ext.owner().as_deref() == owner
+        }) else {
+            return self.get(owner, id).await;
+        };
+        // dropping the task drops the upstream streams it was driving
+        if let Some(abort) = running.abort.lock().unwrap().take() {
+            abort.abort();
+        }
+        let job = {
+            let mut job = running.job.lock().unwrap();
+            if !job.status.is_finished() {
+                job.status = super::JobStatus::Cancelled;
+                job.updated = now();
+                job.retry_token = Self::retry_token(&job);
+            }
+            job.clone()
+        };
+        self.persist(&running.ctx, job.clone()).await?;
+        Ok(job)
+    }
+
+    /// Restarts the jobs which were still running when the server stopped.
+    ///
+    /// Returns the number of jobs resumed.
+    pub async fn resume(self: Arc<Self>) -> Result<usize, super::Error> {
+        let stored = self.store.list_running().await?;
+        let count = stored.len();
+        for super::StoredJob {
+            mut job,
+            ext,
+            cost_multiplier,
+        } in stored
+        {
+            let retry_token = job
+                .retry_token
+                .take()
+                .or_else(|| job.request.retry_token().map(str::to_owned));
+            job.request.set_retry_token(retry_token.clone());
+            job.retry_token = retry_token;
+            job.result = None;
+            self.clone()
+                .start(ctx::Context::new(Arc::new(ext), cost_multiplier), job);
+        }
+        Ok(count)
+    }
+
+    fn start(
+        self: Arc<Self>,
+        ctx: ctx::Context<CTXEXT>,
+        mut job: super::Job,
+    ) -> super::Job {
+        job.attempts += 1;
+        job.status = super::JobStatus::Running;
+        job.updated = now();
+        let (tx, _) = tokio::sync::broadcast::channel(CHANNEL_CAPACITY);
+        let running = Arc::new(RunningJob {
+            ctx,
+            job: Mutex::new(job.clone()),
+            tx,
+            abort: Mutex::new(None),
+        });
+        self.running.insert(job.id.clone(), running.clone());
+        let span = tracing::info_span!(
+            "job",
+            id = %job.id,
+            attempt = job.attempts,
+        );
+        let handle =
+            tokio::spawn(self.clone().run(running.clone()).instrument(span));
+        *running.abort.lock().unwrap() = Some(handle.abort_handle());
+        job
+    }
+
+    async fn run(self: Arc<Self>, running: Arc<RunningJob<CTXEXT>>) {
+        let ctx = running.ctx.clone();
+        let job = running.job.lock().unwrap().clone();
+        let id = job.id.clone();
+        let request = job.request.clone();
+        if let Err(e) = self.persist(&ctx, job).await {
+            tracing::warn!(error = %e, "failed to persist job");
+        }
+
+        let stream = match request {
+            super::JobRequest::FunctionExecution(request) => self
+                .function_executions_client
+                .clone()
+                .create_streaming_handle_usage(ctx.clone(), Arc::new(*request))
+                .await
+                .map(|stream| {
+                    stream
+                        .map(|chunk| {
+                            Ok(super::JobChunk::FunctionExecution(Box::new(
+                                chunk,
+                            )))
+                        })
+                        .boxed()
+                })
+                .map_err(|e| objectiveai::error::ResponseError::from(&e)),
+            super::JobRequest::ProfileComputation(request) => self
+                .profile_computations_client
+                .create_streaming(ctx.clone(), Arc::new(*request))
+                .await
+                .map(|stream| {
+                    stream
+                        .map(|result| {
+                            result.map(|chunk| {
+                                super::JobChunk::ProfileComputation(Box::new(
+                                    chunk,
+                                ))
+                            })
+                        })
+                        .boxed()
+                }),
+        };
+
+        let mut error = None;
+        match stream {
+            Ok(mut stream) => {
+                let mut persisted_at = Instant::now();
+                while let Some(result) = stream.next().await {
+                    let chunk = match result {
+                        Ok(chunk) => chunk,
+                        Err(e) => {
+                            error = Some(e);
+                            break;
+                        }
+                    };
+                    let job = {
+                        let mut job = running.job.lock().unwrap();
+                        match &mut job.result {
+                            Some(result) => result.push(&chunk),
+                            None => job.result = Some(chunk.clone()),
+                        }
+                        job.updated = now();
+                        let _ = running.tx.send(chunk);
+                        if persisted_at.elapsed() >= self.persist_interval {
+                            persisted_at = Instant::now();
+                            job.retry_token = Self::retry_token(&job);
+                            Some(job.clone())
+                        } else {
+                            None
+                        }
+                    };
+                    if let Some(job) = job
+                        && let Err(e) = self.persist(&ctx, job).await
+                    {
+                        tracing::warn!(error = %e, "failed to persist job");
+                    }
+                }
+            }
+            Err(e) => error = Some(e),
+        }
+
+        let job = {
+            let mut job = running.job.lock().unwrap();
+            if job.status.is_finished() {
+                return;
+            }
+            let error = error.or_else(|| {
+                job.result
+                    .as_ref()
+                    .and_then(super::JobChunk::error)
+                    .cloned()
+            });
+            job.status = match error {
+                Some(_) => super::JobStatus::Failed,
+                None => super::JobStatus::Succeeded,
+            };
+            job.error = error;
+            job.updated = now();
+            job.retry_token = Self::retry_token(&job);
+            job.clone()
+        };
+        if let Err(e) = self.persist(&ctx, job).await {
+            tracing::warn!(error = %e, "failed to persist job");
+        }
+        // ends the reattached streams once the task drops its reference
+        self.running.remove(&id);
+    }
+
+    /// Returns a running job of `owner`.
+    fn running(
+        &self,
+        owner: Option<&str>,
+        id: &str,
+    ) -> Option<Arc<RunningJob<CTXEXT>>> {
+        self.running
+            .get(id)
+            .map(|running| running.clone())
+            .filter(|running| running.ctx.ext.owner().as_deref() == owner)
+    }
+
+    /// Retrieves a persisted job of `owner`.
+    async fn stored(
+        &self,
+        owner: Option<&str>,
+        id: &str,
+    ) -> Result<super::StoredJob<CTXEXT>, super::Error> {
+        match self.store.get(id).await? {
+            Some(stored) if stored.ext.owner().as_deref() == owner => {
+                Ok(stored)
+            }
+            _ => Err(super::Error::JobNotFound),
+        }
+    }
+
+    fn retry_token(job: &super::Job) -> Option<String> {
+        match &job.result {
+            Some(result) => result.retry_token(job.request.retry_token()),
+            None => job.request.retry_token().map(str::to_owned),
+        }
+    }
+
+    async fn persist(
+        &self,
+        ctx: &ctx::Context<CTXEXT>,
+        job: super::Job,
+    ) -> Result<(), super::Error> {
+        self.store
+            .put(&super::StoredJob {
+                job,
+                ext: (*ctx.ext).clone(),
+                cost_multiplier: ctx.cost_multiplier,
+            })
+            .await
+    }
+}
@@ -0,0 +1,62 @@
+//! Error types for jobs.
+
+/// Errors that can occur when managing jobs.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The requested job was not found.
+    #[error("job not found")]
+    JobNotFound,
+    /// Failed to read or write persisted jobs.
+    #[error("job store error: {0}")]
+    Store(#[from] std::io::Error),
+    /// A persisted job could not be deserialized.
+    #[error("invalid stored job: {0}")]
+    InvalidStoredJob(#[from] serde_json::Error),
+    /// The encryption key of a job store is not 32 bytes.
+    #[error("job store encryption key must be 32 bytes")]
+    InvalidEncryptionKey,
+    /// A persisted job could not be encrypted or decrypted, such as one
+    /// encrypted with a different key.
+    #[error("job encryption failed")]
+    Encryption,
+}
+
+impl objectiveai::error::StatusError for Error {
+    fn status(&self) -> u16 {
+        match self {
+            Error::JobNotFound => 404,
+            Error::Store(_) => 500,
+            Error::InvalidStoredJob(_) => 500,
+            Error::InvalidEncryptionKey => 500,
+            Error::Encryption => 500,
+        }
+    }
+
+    fn message(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "kind": "jobs",
+            "error": match self {
+                Error::JobNotFound => serde_json::json!({
+                    "kind": "job_not_found",
+                    "error": "job not found",
+                }),
+                Error::Store(e) => serde_json::json!({
+                    "kind": "store",
+                    "error": e.to_string(),
+                }),
+                Error::InvalidStoredJob(e) => serde_json::json!({
+                    "kind": "invalid_stored_job",
+                    "error": e.to_string(),
+                }),
+                Error::InvalidEncryptionKey => serde_json::json!({
+                    "kind": "invalid_encryption_key",
+                    "error": "job store encryption key must be 32 bytes",
+                }),
+                Error::Encryption => serde_json::json!({
+                    "kind": "encryption",
+                    "error": "job encryption failed",
+                }),
+            },
+        }))
+    }
+}
@@ -0,0 +1,221 @@
+//! Job types.
+
+use serde::{Deserialize, Serialize};
+
+/// Generates a unique ID for a job.
+pub fn job_id(created: u64) -> String {
+    let uuid = uuid::Uuid::new_v4();
+    format!("job-{}-{}", uuid.simple(), created)
+}
+
+/// A long-running request submitted as a job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "request", rename_all = "snake_case")]
+pub enum JobRequest {
+    /// A Function execution.
+    FunctionExecution(
+        Box<objectiveai::functions::executions::request::Request>,
+    ),
+    /// A Profile computation.
+    ProfileComputation(
+        Box<objectiveai::functions::profiles::computations::request::Request>,
+    ),
+}
+
+impl JobRequest {
+    /// Returns the retry token the request was submitted with.
+    pub fn retry_token(&self) -> Option<&str> {
+        match self {
+            JobRequest::FunctionExecution(request) => {
+                request.base().retry_token.as_deref()
+            }
+            JobRequest::ProfileComputation(request) => {
+                request.base().retry_token.as_deref()
+            }
+        }
+    }
+
+    /// Replaces the retry token, so that a resumed job reuses the votes of
+    /// the work already completed.
+    pub fn set_retry_token(&mut self, retry_token: Option<String>) {
+        match self {
+            JobRequest::FunctionExecution(request) => {
+                request.base_mut().retry_token = retry_token;
+            }
+            JobRequest::ProfileComputation(request) => {
+                request.base_mut().retry_token = retry_token;
+            }
+        }
+    }
+}
+
+/// A streamed chunk of a job's output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "chunk", rename_all = "snake_case")]
+pub enum JobChunk {
+    /// A chunk of a Function execution.
+    FunctionExecution(
+        Box<
+            objectiveai::functions::executions::response::streaming::FunctionExecutionChunk,
+        >,
+    ),
+    /// A chunk of a Profile computation.
+    ProfileComputation(
+        Box<
+            objectiveai::functions::profiles::computations::response::streaming::FunctionProfileComputationChunk,
+        >,
+    ),
+}
+
+impl JobChunk {
+    /// Pushes another chunk of the same kind into this one.
+    pub fn push(&mut self, other: &JobChunk) {
+        match (self, other) {
+            (
+                JobChunk::FunctionExecution(a),
+                JobChunk::FunctionExecution(b),
+            ) => a.push(b),
+            (
+                JobChunk::ProfileComputation(a),
+                JobChunk::ProfileComputation(b),
+            ) => a.push(b),
+            _ => {}
+        }
+    }
+
+    /// Returns the error reported by the chunk, if any.
+    pub fn error(&self) -> Option<&objectiveai::error::ResponseError> {
+        match self {
+            JobChunk::FunctionExecution(chunk) => chunk.error.as_ref(),
+            JobChunk::ProfileComputation(_) => None,
+        }
+    }
+
+    /// Builds a retry token covering the work completed so far.
+    ///
+    /// Once the output finishes, this is the retry token it reports. Before
+    /// then, a Function execution's token is assembled from the Vector
+    /// Completion tasks which finished with at least one successful vote,
+    /// falling back to `previous` for the others. Swiss System executions
+    /// and Profile computations only report a token once finished, so
+    /// `previous` is returned for them until then.
+    pub fn retry_token(&self, previous: Option<&str>) -> Option<String> {
+        match self {
+            JobChunk::FunctionExecution(chunk) => {
+                if let Some(retry_token) = &chunk.retry_token {
+                    return Some(retry_token.clone());
+                }
+                let is_swiss_system = chunk.tasks.iter().any(|task| {
+                    matches!(
+                        task,
+                        objectiveai::functions::executions::response::streaming::TaskChunk::FunctionExecution(
+                            objectiveai::functions::executions::response::streaming::FunctionExecutionTaskChunk {
+                                swiss_round: Some(_),
+                                ..
+                            }
+                        )
+                    )
+                });
+                if is_swiss_system {
+                    return previous.map(str::to_owned);
+                }
+                let mut retry_token = previous
+                    .and_then(
+                        objectiveai::functions::executions::RetryToken::try_from_string,
+                    )
+                    .unwrap_or(
+                        objectiveai::functions::executions::RetryToken(
+                            Vec::new(),
+                        ),
+                    );
+                for task in chunk.vector_completion_tasks() {
+                    let finished = task.error.is_none()
+                        && task.inner.usage.is_some()
+                        && task
+                            .inner
+                            .completions
+                            .iter()
+                            .any(|completion| completion.error.is_none());
+                    if !finished {
+                        continue;
+                    }
+                    let index = task.task_index as usize;
+                    if retry_token.0.len() <= index {
+                        retry_token.0.resize(index + 1, None);
+                    }
+                    retry_token.0[index] = Some(task.inner.id.clone());
+                }
+                if retry_token.0.iter().all(Option::is_none) {
+                    previous.map(str::to_owned)
+                } else {
+                    Some(retry_token.to_string())
+                }
+            }
+            JobChunk::ProfileComputation(chunk) => chunk
+                .retry_token
+                .clone()
+                .or_else(|| previous.map(str::to_owned)),
+        }
+    }
+}
+
+/// The status of a job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// The job is running.
+    Running,
+    /// The job finished without errors.
+    Succeeded,
+    /// The job finished with an error.
+    Failed,
+    /// The job was cancelled.
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Returns whether the job has stopped running.
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, JobStatus::Running)
+    }
+}
+
+/// A job and its progress.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    /// The job ID.
+    pub id: String,
+    /// The current status.
+    pub status: JobStatus,
+    /// Unix timestamp (seconds) when the job was created.
+    pub created: u64,
+    /// Unix timestamp (seconds) when the job was last updated.
+    pub updated: u64,
+    /// How many times the job was started, including resumptions after
+    /// server restarts.
+    pub attempts: u64,
+    /// The submitted request.
+    #[serde(flatten)]
+    pub request: JobRequest,
+    /// The aggregate of the chunks streamed by the current attempt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<JobChunk>,
+    /// Retry token covering the work completed so far.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_token: Option<String>,
+    /// The error which failed the job, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<objectiveai::error::ResponseError>,
+}
+
+/// A job as persisted by a [`super::Store`], including the request context
+/// needed to resume it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredJob<CTXEXT> {
+    /// The job.
+    pub job: Job,
+    /// The context extension of the submitting request.
+    pub ext: CTXEXT,
+    /// The cost multiplier of the submitting request.
+    pub cost_multiplier: rust_decimal::Decimal,
+}
@@ -0,0 +1,16 @@
+//! Asynchronous jobs for long-running Function executions and Profile
+//! computations.
+//!
+//! A job runs in the background independently of the request which
+//! submitted it. Its progress can be polled, streamed by reattaching, or
+//! cancelled, and it is persisted so that it resumes after a restart.
+
+mod client;
+mod error;
+mod job;
+pub mod store;
+
+pub use client::*;
+pub use error::*;
+pub use job::*;
+pub use store::Store;
@@ -0,0 +1,196 @@
+//! Directory-backed job store.
+
+use base64::Engine;
+use ring::{aead, rand::SecureRandom};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Job store which persists each job as a JSON file in a directory.
+///
+/// Stored jobs include the context extension of the submitting request,
+/// which may hold BYOK API keys, so it is encrypted with ChaCha20-Poly1305
+/// under the store's key before being written.
+pub struct FileStore {
+    /// The directory holding the job files.
+    pub directory: PathBuf,
+    key: aead::LessSafeKey,
+    rng: ring::rand::SystemRandom,
+}
+
+/// A job as written to disk, with its context extension encrypted.
+#[derive(Serialize, Deserialize)]
+struct Record {
+    job: super::super::Job,
+    /// The nonce followed by the sealed JSON of the context extension,
+    /// base64 encoded.
+    ext: String,
+    cost_multiplier: rust_decimal::Decimal,
+}
+
+impl FileStore {
+    /// Creates a new file store, creating `directory` if needed.
+    ///
+    /// `encryption_key` must be 32 bytes, and the same across restarts for
+    /// persisted jobs to be resumed.
+    pub async fn new(
+        directory: impl Into<PathBuf>,
+        encryption_key: &[u8],
+    ) -> Result<Self, super::super::Error> {
+        let key =
+            aead::UnboundKey::new(&aead::CHACHA20_POLY1305, encryption_key)
+                .map_err(|_| super::super::Error::InvalidEncryptionKey)?;
+        let directory = directory.into();
+        tokio::fs::create_dir_all(&directory).await?;
+        Ok(Self {
+            directory,
+            key: aead::LessSafeKey::new(key),
+            rng: ring::rand::SystemRandom::new(),
+        })
+    }
+
+    /// Returns the path of a job's file, or `None` if the ID is not one
+    /// this server could have generated.
+    fn path(&self, id: &str) -> Option<PathBuf> {
+        if id.is_empty()
+            || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return None;
+        }
+        Some(self.directory.join(format!("{id}.json")))
+    }
+
+    /// Encrypts a context extension, bound to the ID of its job.
+    fn seal<CTXEXT: Serialize>(
+        &self,
+        id: &str,
+        ext: &CTXEXT,
+    ) -> Result<String, super::super::Error> {
+        let mut nonce = [0u8; aead::NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| super::super::Error::Encryption)?;
+        let mut data = serde_json::to_vec(ext)?;
+        self.key
+            .seal_in_place_append_tag(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::from(id.as_bytes()),
+                &mut data,
+            )
+            .map_err(|_| super::super::Error::Encryption)?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend(data);
+        Ok(base64::engine::general_purpose::STANDARD.encode(sealed))
+    }
+
+    /// Decrypts a context extension sealed by [`Self::seal`].
+    fn open<CTXEXT: serde::de::DeserializeOwned>(
+        &self,
+        id: &str,
+        sealed: &str,
+    ) -> Result<CTXEXT, super::super::Error> {
+        let mut sealed = base64::engine::general_purpose::STANDARD
+            .decode(sealed)
+            .map_err(|_| super::super::Error::Encryption)?;
+        if sealed.len() < aead::NONCE_LEN {
+            return Err(super::super::Error::Encryption);
+        }
+        let (nonce, data) = sealed.split_at_mut(aead::NONCE_LEN);
+        let nonce = aead::Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| super::super::Error::Encryption)?;
+        let json = self
+            .key
+            .open_in_place(nonce, aead::Aad::from(id.as_bytes()), data)
+            .map_err(|_| super::super::Error::Encryption)?;
+        Ok(serde_json::from_slice(json)?)
+    }
+
+    /// Reads a job's file and decrypts its context extension.
+    async fn read<CTXEXT: serde::de::DeserializeOwned>(
+        &self,
+        path: &std::path::Path,
+    ) -> Result<super::super::StoredJob<CTXEXT>, super::super::Error> {
+        let json = tokio::fs::read(path).await?;
+        let record: Record = serde_json::from_slice(&json)?;
+        Ok(super::super::StoredJob {
+            ext: self.open(&record.job.id, &record.ext)?,
+            job: record.job,
+            cost_multiplier: record.cost_multiplier,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl<CTXEXT> super::Store<CTXEXT> for FileStore
+where
+    CTXEXT:
+        serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+{
+    async fn put(
+        &self,
+        job: &super::super::StoredJob<CTXEXT>,
+    ) -> Result<(), super::super::Error> {
+        let Some(path) = self.path(&job.job.id) else {
+            return Err(super::super::Error::JobNotFound);
+        };
+        let json = serde_json::to_vec(&Record {
+            job: job.job.clone(),
+            ext: self.seal(&job.job.id, &job.ext)?,
+            cost_multiplier: job.cost_multiplier,
+        })?;
+        // write then rename, so a crash never leaves a partial file, to a
+        // file of its own, so concurrent writes of a job never interleave
+        let tmp = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+        tokio::fs::write(&tmp, json).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        id: &str,
+    ) -> Result<Option<super::super::StoredJob<CTXEXT>>, super::super::Error>
+    {
+        let Some(path) = self.path(id) else {
+            return Ok(None);
+        };
+        match self.read(&path).await {
+            Ok(job) => Ok(Some(job)),
+            Err(super::super::Error::Store(e))
+                if e.kind() == std::io::ErrorKind::NotFound =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list_running(
+        &self,
+    ) -> Result<Vec<super::super::StoredJob<CTXEXT>>, super::super::Error> {
+        let mut jobs = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            let job: super::super::StoredJob<CTXEXT> =
+                match self.read(&path).await {
+                    Ok(job) => job,
+                    // skip jobs which cannot be resumed, rather than all
+                    Err(e) => {
+                        tracing::warn!(
+                            path = %path.display(),
+                            error = %e,
+                            "skipping invalid stored job"
+                        );
+                        continue;
+                    }
+                };
+            if !job.job.status.is_finished() {
+                jobs.push(job);
+            }
+        }
+        Ok(jobs)
+    }
+}
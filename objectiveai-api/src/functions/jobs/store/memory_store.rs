@@ -0,0 +1,77 @@
+//! In-memory job store.
+
+use indexmap::IndexMap;
+use std::sync::Mutex;
+
+/// Job store which keeps jobs in memory.
+///
+/// Jobs are lost when the server restarts. Once more than `max_entries`
+/// jobs are stored, the finished jobs which were updated least recently are
+/// evicted to make room. Running jobs are never evicted.
+pub struct MemoryStore<CTXEXT> {
+    /// Maximum number of jobs kept, unless more are running.
+    pub max_entries: usize,
+    jobs: Mutex<IndexMap<String, super::super::StoredJob<CTXEXT>>>,
+}
+
+impl<CTXEXT> MemoryStore<CTXEXT> {
+    /// Creates a new, empty in-memory job store which keeps at most
+    /// `max_entries` jobs, unless more are running.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            jobs: Mutex::new(IndexMap::new()),
+        }
+    }
+}
+
+impl<CTXEXT> Default for MemoryStore<CTXEXT> {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+#[async_trait::async_trait]
+impl<CTXEXT> super::Store<CTXEXT> for MemoryStore<CTXEXT>
+where
+    CTXEXT: Clone + Send + Sync + 'static,
+{
+    async fn put(
+        &self,
+        job: &super::super::StoredJob<CTXEXT>,
+    ) -> Result<(), super::super::Error> {
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.shift_remove(&job.job.id);
+        jobs.insert(job.job.id.clone(), job.clone());
+        while jobs.len() > self.max_entries {
+            let Some(index) =
+                jobs.values().position(|job| job.job.status.is_finished())
+            else {
+                break;
+            };
+            jobs.shift_remove_index(index);
+        }
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        id: &str,
+    ) -> Result<Option<super::super::StoredJob<CTXEXT>>, super::super::Error>
+    {
+        Ok(self.jobs.lock().unwrap().get(id).cloned())
+    }
+
+    async fn list_running(
+        &self,
+    ) -> Result<Vec<super::super::StoredJob<CTXEXT>>, super::super::Error> {
+        Ok(self
+            .jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|job| !job.job.status.is_finished())
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,12 @@
+//! Persistence for jobs.
+//!
+//! Provides the [`Store`] trait and implementations which keep jobs in
+//! memory or persist them to a directory so they survive restarts.
+
+mod file_store;
+mod memory_store;
+mod store;
+
+pub use file_store::*;
+pub use memory_store::*;
+pub use store::*;
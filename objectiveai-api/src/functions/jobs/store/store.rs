@@ -0,0 +1,60 @@
+//! Job store trait definition.
+
+/// Trait for persisting jobs.
+#[async_trait::async_trait]
+pub trait Store<CTXEXT> {
+    /// Inserts or replaces a job.
+    async fn put(
+        &self,
+        job: &super::super::StoredJob<CTXEXT>,
+    ) -> Result<(), super::super::Error>;
+
+    /// Retrieves a job by ID.
+    async fn get(
+        &self,
+        id: &str,
+    ) -> Result<Option<super::super::StoredJob<CTXEXT>>, super::super::Error>;
+
+    /// Lists the jobs which were still running when last persisted.
+    async fn list_running(
+        &self,
+    ) -> Result<Vec<super::super::StoredJob<CTXEXT>>, super::super::Error>;
+}
+
+#[async_trait::async_trait]
+impl<CTXEXT, A, B> Store<CTXEXT> for either::Either<A, B>
+where
+    CTXEXT: Send + Sync + 'static,
+    A: Store<CTXEXT> + Send + Sync,
+    B: Store<CTXEXT> + Send + Sync,
+{
+    async fn put(
+        &self,
+        job: &super::super::StoredJob<CTXEXT>,
+    ) -> Result<(), super::super::Error> {
+        match self {
+            either::Either::Left(store) => store.put(job).await,
+            either::Either::Right(store) => store.put(job).await,
+        }
+    }
+
+    async fn get(
+        &self,
+        id: &str,
+    ) -> Result<Option<super::super::StoredJob<CTXEXT>>, super::super::Error>
+    {
+        match self {
+            either::Either::Left(store) => store.get(id).await,
+            either::Either::Right(store) => store.get(id).await,
+        }
+    }
+
+    async fn list_running(
+        &self,
+    ) -> Result<Vec<super::super::StoredJob<CTXEXT>>, super::super::Error> {
+        match self {
+            either::Either::Left(store) => store.list_running().await,
+            either::Either::Right(store) => store.list_running().await,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Process-wide caching wrapper for Profile fetchers.
+
+use crate::{ctx, lru};
+use std::{sync::Arc, time::Duration};
+
+/// Cache key for a Profile: owner, repository, and optional commit SHA.
+pub type ProfileCacheKey = (String, String, Option<String>);
+
+/// Wraps a Profile fetcher with a process-wide LRU cache.
+///
+/// Profiles fetched by commit SHA are immutable and cached without a TTL.
+/// Profiles fetched without a commit resolve to the latest commit, so those
+/// entries expire after `unpinned_ttl`. The resolved commit is also cached
+/// as a pinned entry. Not-found results and errors are not cached.
+#[derive(Debug)]
+pub struct GlobalCachingFetcher<FPFL> {
+    /// The underlying fetcher to delegate to on cache miss.
+    pub inner: Arc<FPFL>,
+    /// Cached Profiles keyed by owner, repository, and commit.
+    pub cache: lru::LruCache<
+        ProfileCacheKey,
+        objectiveai::functions::profiles::response::GetProfile,
+    >,
+    /// How long lookups without a commit are cached.
+    pub unpinned_ttl: Duration,
+}
+
+impl<FPFL> GlobalCachingFetcher<FPFL> {
+    /// Creates a new global caching fetcher wrapping the given inner fetcher.
+    pub fn new(
+        inner: Arc<FPFL>,
+        max_entries: usize,
+        max_bytes: usize,
+        unpinned_ttl: Duration,
+    ) -> Self {
+        Self {
+            inner,
+            cache: lru::LruCache::new(max_entries, max_bytes),
+            unpinned_ttl,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<CTXEXT, FPFL> super::Fetcher<CTXEXT> for GlobalCachingFetcher<FPFL>
+where
+    CTXEXT: Send + Sync + 'static,
+    FPFL: super::Fetcher<CTXEXT> + Send + Sync + 'static,
+{
+    async fn fetch(
+        &self,
+        ctx: ctx::Context<CTXEXT>,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> Result<
+        Option<objectiveai::functions::profiles::response::GetProfile>,
+        objectiveai::error::ResponseError,
+    > {
+        let key = (
+            owner.to_owned(),
+            repository.to_owned(),
+            commit.map(str::to_owned),
+        );
+        let cached = self.cache.get(&key);
+        crate::metrics::METRICS
+            .record_cache_lookup("profile", cached.is_some());
+        if let Some(cached) = cached {
+            return Ok(Some(cached));
+        }
+        let result = self.inner.fetch(ctx, owner, repository, commit).await?;
+        if let Some(profile) = &result {
+            let size = lru::json_size(profile);
+            if commit.is_none() {
+                self.cache.insert(
+                    (
+                        profile.owner.clone(),
+                        profile.repository.clone(),
+                        Some(profile.commit.clone()),
+                    ),
+                    profile.clone(),
+                    size,
+                    None,
+                );
+                self.cache.insert(
+                    key,
+                    profile.clone(),
+                    size,
+                    Some(self.unpinned_ttl),
+                );
+            } else {
+                self.cache.insert(key, profile.clone(), size, None);
+            }
+        }
+        Ok(result)
+    }
+}
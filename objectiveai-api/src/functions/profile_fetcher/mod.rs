@@ -1,7 +1,9 @@
 //! Fetcher for Profile definitions from GitHub.
 
 mod fetcher;
+mod global_caching_fetcher;
 mod objectiveai;
 
 pub use fetcher::*;
+pub use global_caching_fetcher::*;
 pub use objectiveai::*;
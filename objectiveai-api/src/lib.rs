@@ -12,6 +12,9 @@
 //! - [`ensemble_llm`] - Ensemble LLM management and retrieval
 //! - [`error`] - Error response handling
 //! - [`functions`] - Function execution and profile management
+//! - [`lru`] - Process-wide bounded LRU cache
+//! - [`metrics`] - Prometheus metrics
+//! - [`server`] - HTTP router builder
 //! - [`util`] - Utility types for streaming and indexing
 //! - [`vector`] - Vector completions for scoring and ranking
 
@@ -29,6 +32,12 @@ pub mod ensemble_llm;
 pub mod error;
 /// Function execution, profile management, and computations.
 pub mod functions;
+/// Process-wide bounded LRU cache shared across requests.
+pub mod lru;
+/// Process-wide Prometheus metrics.
+pub mod metrics;
+/// HTTP router builder serving the API with custom clients.
+pub mod server;
 /// Utility types for streaming and choice indexing.
 pub mod util;
 /// Vector completions for scoring and ranking responses.
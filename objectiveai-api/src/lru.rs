@@ -0,0 +1,159 @@
+//! Process-wide bounded LRU cache.
+//!
+//! Unlike the per-request caches in [`crate::ctx::Context`], an [`LruCache`]
+//! lives for the lifetime of the server and is shared across requests. It is
+//! bounded both by entry count and by an approximate size in bytes, evicting
+//! the least recently used entries first.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// A thread-safe LRU cache bounded by entry count and total size.
+#[derive(Debug)]
+pub struct LruCache<K, V> {
+    /// Maximum number of entries.
+    pub max_entries: usize,
+    /// Maximum total size of all entries, in bytes.
+    pub max_bytes: usize,
+    inner: Mutex<Inner<K, V>>,
+}
+
+#[derive(Debug)]
+struct Inner<K, V> {
+    /// Entries keyed by cache key.
+    entries: HashMap<K, Entry<V>>,
+    /// Cache keys ordered from least to most recently used.
+    order: BTreeMap<u64, K>,
+    /// Monotonic counter used to order entries by recency.
+    tick: u64,
+    /// Total size of all entries, in bytes.
+    bytes: usize,
+}
+
+#[derive(Debug)]
+struct Entry<V> {
+    value: V,
+    bytes: usize,
+    tick: u64,
+    expires_at: Option<Instant>,
+}
+
+impl<K, V> LruCache<K, V> {
+    /// Creates an empty cache with the given bounds.
+    pub fn new(max_entries: usize, max_bytes: usize) -> Self {
+        Self {
+            max_entries,
+            max_bytes,
+            inner: Mutex::new(Inner {
+                entries: HashMap::new(),
+                order: BTreeMap::new(),
+                tick: 0,
+                bytes: 0,
+            }),
+        }
+    }
+}
+
+impl<K, V> LruCache<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    /// Returns a clone of the cached value, marking it as recently used.
+    ///
+    /// Expired entries are removed and treated as missing.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let mut inner = self.inner.lock().unwrap();
+        let Inner {
+            entries,
+            order,
+            tick,
+            bytes,
+        } = &mut *inner;
+        let entry = entries.get_mut(key)?;
+        if entry
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= Instant::now())
+        {
+            order.remove(&entry.tick);
+            *bytes -= entry.bytes;
+            entries.remove(key);
+            return None;
+        }
+        order.remove(&entry.tick);
+        *tick += 1;
+        entry.tick = *tick;
+        order.insert(*tick, key.clone());
+        Some(entry.value.clone())
+    }
+
+    /// Inserts a value of approximately `size` bytes, evicting least recently
+    /// used entries until the cache is within its bounds.
+    ///
+    /// If `ttl` is provided, the entry expires after that duration. Values
+    /// larger than the whole cache are not inserted.
+    pub fn insert(&self, key: K, value: V, size: usize, ttl: Option<Duration>) {
+        if self.max_entries == 0 || size > self.max_bytes {
+            return;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        let Inner {
+            entries,
+            order,
+            tick,
+            bytes,
+        } = &mut *inner;
+
+        // replace any existing entry
+        if let Some(existing) = entries.remove(&key) {
+            order.remove(&existing.tick);
+            *bytes -= existing.bytes;
+        }
+
+        // evict until the new entry fits
+        while entries.len() + 1 > self.max_entries
+            || *bytes + size > self.max_bytes
+        {
+            let Some((_, oldest)) = order.pop_first() else {
+                break;
+            };
+            if let Some(evicted) = entries.remove(&oldest) {
+                *bytes -= evicted.bytes;
+            }
+        }
+
+        // insert as most recently used
+        *tick += 1;
+        order.insert(*tick, key.clone());
+        *bytes += size;
+        entries.insert(
+            key,
+            Entry {
+                value,
+                bytes: size,
+                tick: *tick,
+                expires_at: ttl.map(|ttl| Instant::now() + ttl),
+            },
+        );
+    }
+
+    /// Returns the number of entries, including any that have expired but
+    /// not yet been removed.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    /// Returns `true` if the cache holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Approximates the in-memory size of a value by its serialized JSON length.
+pub fn json_size<T: serde::Serialize>(value: &T) -> usize {
+    serde_json::to_vec(value).map(|v| v.len()).unwrap_or(0)
+}
@@ -3,23 +3,11 @@
 //! REST API server for chat completions, vector completions, Functions,
 //! Profiles, Ensembles, and authentication.
 
-use axum::{
-    Json,
-    extract::Path,
-    http::HeaderMap,
-    response::{IntoResponse, Sse, sse::Event},
-};
 use envconfig::Envconfig;
-use objectiveai::error::ResponseError;
 use objectiveai_api::{
-    auth, chat, ctx, ensemble, ensemble_llm,
-    error::ResponseErrorExt,
-    functions::{self, profiles::computations::Client},
-    util::StreamOnce,
-    vector,
+    auth, chat, ctx, ensemble, ensemble_llm, functions, server, vector,
 };
-use std::{convert::Infallible, sync::Arc};
-use tokio_stream::StreamExt;
+use std::sync::Arc;
 
 #[derive(Envconfig)]
 struct Config {
@@ -70,6 +58,58 @@ struct Config {
         default = "40000" // 40 seconds
     )]
     chat_completions_backoff_max_elapsed_time: u64,
+    #[envconfig(from = "FETCHER_CACHE_MAX_ENTRIES", default = "10000")]
+    fetcher_cache_max_entries: usize,
+    #[envconfig(
+        from = "FETCHER_CACHE_MAX_BYTES",
+        default = "104857600" // 100 MiB
+    )]
+    fetcher_cache_max_bytes: usize,
+    #[envconfig(
+        from = "FETCHER_CACHE_UNPINNED_TTL",
+        default = "60000" // 1 minute
+    )]
+    fetcher_cache_unpinned_ttl: u64,
+    #[envconfig(from = "UPSTREAM_MAX_IN_FLIGHT")]
+    upstream_max_in_flight: Option<usize>,
+    #[envconfig(from = "UPSTREAM_REQUESTS_PER_SECOND")]
+    upstream_requests_per_second: Option<f64>,
+    #[envconfig(from = "UPSTREAM_BURST")]
+    upstream_burst: Option<f64>,
+    #[envconfig(from = "UPSTREAM_MODEL_LIMITS")]
+    upstream_model_limits: Option<String>,
+    #[envconfig(from = "UPSTREAM_CIRCUIT_FAILURE_THRESHOLD", default = "5")]
+    upstream_circuit_failure_threshold: u64,
+    #[envconfig(
+        from = "UPSTREAM_CIRCUIT_OPEN_DURATION",
+        default = "30000" // 30 seconds
+    )]
+    upstream_circuit_open_duration: u64,
+    #[envconfig(from = "EXECUTIONS_DIRECTORY")]
+    executions_directory: Option<String>,
+    #[envconfig(from = "EXECUTIONS_IN_MEMORY", default = "false")]
+    executions_in_memory: bool,
+    #[envconfig(from = "EXECUTIONS_IN_MEMORY_MAX_ENTRIES", default = "10000")]
+    executions_in_memory_max_entries: usize,
+    #[envconfig(from = "EXECUTIONS_MAX_BATCH_CONCURRENCY", default = "32")]
+    executions_max_batch_concurrency: u64,
+    #[envconfig(from = "JOBS_DIRECTORY")]
+    jobs_directory: Option<String>,
+    #[envconfig(from = "JOBS_ENCRYPTION_KEY")]
+    jobs_encryption_key: Option<String>,
+    #[envconfig(from = "JOBS_IN_MEMORY_MAX_ENTRIES", default = "10000")]
+    jobs_in_memory_max_entries: usize,
+    #[envconfig(
+        from = "JOBS_PERSIST_INTERVAL",
+        default = "1000" // 1 second
+    )]
+    jobs_persist_interval: u64,
+    #[envconfig(from = "ADMIN_API_KEY")]
+    admin_api_key: Option<String>,
+    #[envconfig(from = "OTEL_EXPORTER_OTLP_ENDPOINT")]
+    otel_exporter_otlp_endpoint: Option<String>,
+    #[envconfig(from = "OTEL_SERVICE_NAME", default = "objectiveai-api")]
+    otel_service_name: String,
     #[envconfig(from = "ADDRESS", default = "0.0.0.0")]
     address: String,
     #[envconfig(from = "PORT", default = "5000")]
@@ -96,10 +136,34 @@ async fn main() {
         chat_completions_backoff_multiplier,
         chat_completions_backoff_max_interval,
         chat_completions_backoff_max_elapsed_time,
+        fetcher_cache_max_entries,
+        fetcher_cache_max_bytes,
+        fetcher_cache_unpinned_ttl,
+        upstream_max_in_flight,
+        upstream_requests_per_second,
+        upstream_burst,
+        upstream_model_limits,
+        upstream_circuit_failure_threshold,
+        upstream_circuit_open_duration,
+        executions_directory,
+        executions_in_memory,
+        executions_in_memory_max_entries,
+        executions_max_batch_concurrency,
+        jobs_directory,
+        jobs_encryption_key,
+        jobs_in_memory_max_entries,
+        jobs_persist_interval,
+        admin_api_key,
+        otel_exporter_otlp_endpoint,
+        otel_service_name,
         address,
         port,
     } = Config::init_from_env().unwrap();
 
+    // Tracing
+    let _tracer_provider = otel_exporter_otlp_endpoint
+        .map(|_| init_tracing(otel_service_name));
+
     // HTTP Client
     let http_client = reqwest::Client::new();
 
@@ -116,12 +180,57 @@ async fn main() {
     // Ensemble LLM Fetcher
     let ensemble_llm_fetcher = Arc::new(
         ensemble_llm::fetcher::CachingFetcher::new(Arc::new(
-            ensemble_llm::fetcher::ObjectiveAiFetcher::new(
-                objectiveai_http_client.clone(),
+            ensemble_llm::fetcher::GlobalCachingFetcher::new(
+                Arc::new(ensemble_llm::fetcher::ObjectiveAiFetcher::new(
+                    objectiveai_http_client.clone(),
+                )),
+                fetcher_cache_max_entries,
+                fetcher_cache_max_bytes,
             ),
         )),
     );
 
+    // Upstream Limiter
+    let upstream_model_limits = match upstream_model_limits
+        .map(|json| serde_json::from_str(&json))
+        .transpose()
+    {
+        Ok(upstream_model_limits) => upstream_model_limits.unwrap_or_default(),
+        Err(e) => config_error(format!("invalid UPSTREAM_MODEL_LIMITS: {e}")),
+    };
+    let upstream_limiter = match chat::completions::upstream::Limiter::new(
+        chat::completions::upstream::Limits {
+            max_in_flight: upstream_max_in_flight,
+            requests_per_second: upstream_requests_per_second,
+            burst: upstream_burst,
+        },
+        upstream_model_limits,
+        backoff::ExponentialBackoff {
+            current_interval: std::time::Duration::from_millis(
+                chat_completions_backoff_current_interval,
+            ),
+            initial_interval: std::time::Duration::from_millis(
+                chat_completions_backoff_initial_interval,
+            ),
+            randomization_factor: chat_completions_backoff_randomization_factor,
+            multiplier: chat_completions_backoff_multiplier,
+            max_interval: std::time::Duration::from_millis(
+                chat_completions_backoff_max_interval,
+            ),
+            ..Default::default()
+        },
+    ) {
+        Ok(upstream_limiter) => Arc::new(upstream_limiter),
+        Err(e) => config_error(e),
+    };
+
+    // Upstream Health Tracker
+    let upstream_health =
+        Arc::new(chat::completions::upstream::HealthTracker::new(
+            upstream_circuit_failure_threshold,
+            std::time::Duration::from_millis(upstream_circuit_open_duration),
+        ));
+
     // Chat Completions Client
     let chat_completions_client = Arc::new(chat::completions::Client::<
         ctx::DefaultContextExt,
@@ -139,6 +248,8 @@ async fn main() {
                 x_title,
                 http_referer,
             ),
+            upstream_limiter,
+            upstream_health.clone(),
         ),
         std::time::Duration::from_millis(
             chat_completions_backoff_current_interval,
@@ -157,8 +268,12 @@ async fn main() {
     // Ensemble Fetcher
     let ensemble_fetcher = Arc::new(
         ensemble::fetcher::CachingFetcher::new(Arc::new(
-            ensemble::fetcher::ObjectiveAiFetcher::new(
-                objectiveai_http_client.clone(),
+            ensemble::fetcher::GlobalCachingFetcher::new(
+                Arc::new(ensemble::fetcher::ObjectiveAiFetcher::new(
+                    objectiveai_http_client.clone(),
+                )),
+                fetcher_cache_max_entries,
+                fetcher_cache_max_bytes,
             ),
         )),
     );
@@ -195,16 +310,42 @@ async fn main() {
 
     // Function Fetcher
     let function_fetcher =
-        Arc::new(functions::function_fetcher::ObjectiveAiFetcher::new(
-            objectiveai_http_client.clone(),
+        Arc::new(functions::function_fetcher::GlobalCachingFetcher::new(
+            Arc::new(functions::function_fetcher::ObjectiveAiFetcher::new(
+                objectiveai_http_client.clone(),
+            )),
+            fetcher_cache_max_entries,
+            fetcher_cache_max_bytes,
+            std::time::Duration::from_millis(fetcher_cache_unpinned_ttl),
         ));
 
     // Function Profile Fetcher
     let profile_fetcher =
-        Arc::new(functions::profile_fetcher::ObjectiveAiFetcher::new(
-            objectiveai_http_client.clone(),
+        Arc::new(functions::profile_fetcher::GlobalCachingFetcher::new(
+            Arc::new(functions::profile_fetcher::ObjectiveAiFetcher::new(
+                objectiveai_http_client.clone(),
+            )),
+            fetcher_cache_max_entries,
+            fetcher_cache_max_bytes,
+            std::time::Duration::from_millis(fetcher_cache_unpinned_ttl),
         ));
 
+    // Function Executions Store
+    let persist_executions =
+        executions_directory.is_some() || executions_in_memory;
+    let executions_store = Arc::new(match executions_directory {
+        Some(directory) => either::Either::Left(
+            functions::executions::store::FileStore::new(directory)
+                .await
+                .unwrap(),
+        ),
+        None => either::Either::Right(
+            functions::executions::store::MemoryStore::new(
+                executions_in_memory_max_entries,
+            ),
+        ),
+    });
+
     // Function Executions Client
     let function_executions_client =
         Arc::new(functions::executions::Client::new(
@@ -213,7 +354,22 @@ async fn main() {
             vector_completions_client.clone(),
             function_fetcher.clone(),
             profile_fetcher.clone(),
-            Arc::new(functions::executions::usage_handler::LogUsageHandler),
+            Arc::new(if persist_executions {
+                either::Either::Left(
+                    functions::executions::usage_handler::StoreUsageHandler::new(
+                        function_fetcher.clone(),
+                        executions_store.clone(),
+                        Arc::new(
+                            functions::executions::usage_handler::LogUsageHandler,
+                        ),
+                    ),
+                )
+            } else {
+                either::Either::Right(
+                    functions::executions::usage_handler::LogUsageHandler,
+                )
+            }),
+            executions_max_batch_concurrency,
         ));
 
     // Functions Profiles Computations Client
@@ -222,6 +378,33 @@ async fn main() {
             objectiveai_http_client.clone(),
         ));
 
+    // Function Jobs Client
+    let jobs_client = Arc::new(functions::jobs::Client::new(
+        function_executions_client.clone(),
+        profile_computations_client.clone(),
+        Arc::new(match jobs_directory {
+            Some(directory) => either::Either::Left(
+                functions::jobs::store::FileStore::new(
+                    directory,
+                    &base64::Engine::decode(
+                        &base64::engine::general_purpose::STANDARD,
+                        jobs_encryption_key.expect(
+                            "JOBS_ENCRYPTION_KEY is required with JOBS_DIRECTORY",
+                        ),
+                    )
+                    .expect("JOBS_ENCRYPTION_KEY must be base64"),
+                )
+                .await
+                .unwrap(),
+            ),
+            None => either::Either::Right(functions::jobs::store::MemoryStore::<
+                ctx::DefaultContextExt,
+            >::new(jobs_in_memory_max_entries)),
+        }),
+        std::time::Duration::from_millis(jobs_persist_interval),
+    ));
+    jobs_client.clone().resume().await.unwrap();
+
     // Functions Client
     let functions_client = Arc::new(functions::Client::new(
         function_fetcher.clone(),
@@ -269,801 +452,28 @@ async fn main() {
     ));
 
     // Router
-    let app = axum::Router::new()
-        // Chat Completions - create
-        .route(
-            "/chat/completions",
-            axum::routing::post({
-                let chat_completions_client = chat_completions_client.clone();
-                move |headers: HeaderMap,
-                      Json(body): Json<
-                    objectiveai::chat::completions::request::ChatCompletionCreateParams,
-                >| {
-                    create_chat_completion(chat_completions_client, headers, body)
-                }
-            }),
-        )
-        // Vector Completions - create
-        .route(
-            "/vector/completions",
-            axum::routing::post({
-                let vector_completions_client = vector_completions_client.clone();
-                move |headers: HeaderMap,
-                      Json(body): Json<
-                    objectiveai::vector::completions::request::VectorCompletionCreateParams,
-                >| {
-                    create_vector_completion(vector_completions_client, headers, body)
-                }
-            }),
-        )
-        // Vector Completions - get completion votes
-        .route(
-            "/vector/completions/{id}",
-            axum::routing::post({
-                let vector_completions_cache_client =
-                    vector_completions_cache_client.clone();
-                move |headers: HeaderMap,
-                      Path(id): Path<String>| {
-                    get_vector_completion_votes(
-                        vector_completions_cache_client,
-                        headers,
-                        id,
-                    )
-                }
-            }),
-        )
-        // Vector Completions - get cache vote
-        .route(
-            "/vector/completions/cache",
-            axum::routing::post({
-                let vector_completions_cache_client =
-                    vector_completions_cache_client.clone();
-                move |headers: HeaderMap,
-                      Json(body): Json<
-                    objectiveai::vector::completions::cache::request::CacheVoteRequestOwned,
-                >| {
-                    get_vector_cache_vote(
-                        vector_completions_cache_client,
-                        headers,
-                        body,
-                    )
-                }
-            }),
-        )
-        // Functions - list
-        .route(
-            "/functions",
-            axum::routing::get({
-                let functions_client = functions_client.clone();
-                move |headers: HeaderMap| list_functions(functions_client, headers)
-            }),
-        )
-        // Functions - get (without commit)
-        .route(
-            "/functions/{fowner}/{frepository}",
-            axum::routing::get({
-                let functions_client = functions_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository)): Path<(String, String)>| {
-                    get_function(functions_client, headers, fowner, frepository, None)
-                }
-            }),
-        )
-        // Functions - get (with commit)
-        .route(
-            "/functions/{fowner}/{frepository}/{fcommit}",
-            axum::routing::get({
-                let functions_client = functions_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository, fcommit)): Path<(
-                    String,
-                    String,
-                    String,
-                )>| {
-                    get_function(
-                        functions_client,
-                        headers,
-                        fowner,
-                        frepository,
-                        Some(fcommit),
-                    )
-                }
-            }),
-        )
-        // Functions - get usage (without commit)
-        .route(
-            "/functions/{fowner}/{frepository}/usage",
-            axum::routing::get({
-                let functions_client = functions_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository)): Path<(String, String)>| {
-                    get_function_usage(functions_client, headers, fowner, frepository, None)
-                }
-            }),
-        )
-        // Functions - get usage (with commit)
-        .route(
-            "/functions/{fowner}/{frepository}/{fcommit}/usage",
-            axum::routing::get({
-                let functions_client = functions_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository, fcommit)): Path<(String, String, String)>| {
-                    get_function_usage(
-                        functions_client,
-                        headers,
-                        fowner,
-                        frepository,
-                        Some(fcommit),
-                    )
-                }
-            }),
-        )
-        // Function Executions - create
-        // inline function
-        // inline profile
-        .route(
-            "/functions",
-            axum::routing::post({
-                let function_executions_client = function_executions_client.clone();
-                move |headers: HeaderMap,
-                      Json(body): Json<
-                    objectiveai::functions::executions::request::FunctionInlineProfileInlineRequestBody,
-                >| {
-                    execute_function(
-                        function_executions_client,
-                        headers,
-                        objectiveai::functions::executions::request::Request::FunctionInlineProfileInline {
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Function Executions - create
-        // remote function (without commit)
-        // inline profile
-        .route(
-            "/functions/{fowner}/{frepository}",
-            axum::routing::post({
-                let function_executions_client = function_executions_client.clone();
-                move |headers: HeaderMap,
-                      Path(path): Path<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestPath,
-                >,
-                      Json(body): Json<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestBody,
-                >| {
-                    execute_function(
-                        function_executions_client,
-                        headers,
-                        objectiveai::functions::executions::request::Request::FunctionRemoteProfileInline {
-                            path,
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Function Executions - create
-        // remote function (with commit)
-        // inline profile
-        .route(
-            "/functions/{fowner}/{frepository}/{fcommit}",
-            axum::routing::post({
-                let function_executions_client = function_executions_client.clone();
-                move |headers: HeaderMap,
-                      Path(path): Path<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestPath,
-                >,
-                      Json(body): Json<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestBody,
-                >| {
-                    execute_function(
-                        function_executions_client,
-                        headers,
-                        objectiveai::functions::executions::request::Request::FunctionRemoteProfileInline {
-                            path,
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Function Executions - create
-        // inline function
-        // remote profile (without commit)
-        .route(
-            "/functions/profiles/{powner}/{prepository}",
-            axum::routing::post({
-                let function_executions_client = function_executions_client.clone();
-                move |headers: HeaderMap,
-                      Path(path): Path<
-                    objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestPath,
-                >,
-                      Json(body): Json<
-                    objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestBody,
-                >| {
-                    execute_function(
-                        function_executions_client,
-                        headers,
-                        objectiveai::functions::executions::request::Request::FunctionInlineProfileRemote {
-                            path,
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Function Executions - create
-        // inline function
-        // remote profile (with commit)
-        .route(
-            "/functions/profiles/{powner}/{prepository}/{pcommit}",
-            axum::routing::post({
-                let function_executions_client = function_executions_client.clone();
-                move |headers: HeaderMap,
-                      Path(path): Path<
-                    objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestPath,
-                >,
-                      Json(body): Json<
-                    objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestBody,
-                >| {
-                    execute_function(
-                        function_executions_client,
-                        headers,
-                        objectiveai::functions::executions::request::Request::FunctionInlineProfileRemote {
-                            path,
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Function Executions - create
-        // remote function (without commit)
-        // remote profile (without commit)
-        .route(
-            "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}",
-            axum::routing::post({
-                let function_executions_client = function_executions_client.clone();
-                move |headers: HeaderMap,
-                      Path(path): Path<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
-                >,
-                      Json(body): Json<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestBody,
-                >| {
-                    execute_function(
-                        function_executions_client,
-                        headers,
-                        objectiveai::functions::executions::request::Request::FunctionRemoteProfileRemote {
-                            path,
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Function Executions - create
-        // remote function (without commit)
-        // remote profile (with commit)
-        .route(
-            "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}/{pcommit}",
-            axum::routing::post({
-                let function_executions_client = function_executions_client.clone();
-                move |headers: HeaderMap,
-                      Path(path): Path<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
-                >,
-                      Json(body): Json<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestBody,
-                >| {
-                    execute_function(
-                        function_executions_client,
-                        headers,
-                        objectiveai::functions::executions::request::Request::FunctionRemoteProfileRemote {
-                            path,
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Function Executions - create
-        // remote function (with commit)
-        // remote profile (without commit)
-        .route(
-            "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}",
-            axum::routing::post({
-                let function_executions_client = function_executions_client.clone();
-                move |headers: HeaderMap,
-                      Path(path): Path<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
-                >,
-                      Json(body): Json<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestBody,
-                >| {
-                    execute_function(
-                        function_executions_client,
-                        headers,
-                        objectiveai::functions::executions::request::Request::FunctionRemoteProfileRemote {
-                            path,
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Function Executions - create
-        // remote function (with commit)
-        // remote profile (with commit)
-        .route(
-            "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}/{pcommit}",
-            axum::routing::post({
-                let function_executions_client = function_executions_client.clone();
-                move |headers: HeaderMap,
-                      Path(path): Path<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
-                >,
-                      Json(body): Json<
-                    objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestBody,
-                >| {
-                    execute_function(
-                        function_executions_client,
-                        headers,
-                        objectiveai::functions::executions::request::Request::FunctionRemoteProfileRemote {
-                            path,
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Function Profiles - list
-        .route(
-            "/functions/profiles",
-            axum::routing::get({
-                let profiles_client = profiles_client.clone();
-                move |headers: HeaderMap| list_profiles(profiles_client, headers)
-            }),
-        )
-        // Function Profiles - get (without commit)
-        .route(
-            "/functions/profiles/{powner}/{prepository}",
-            axum::routing::get({
-                let profiles_client = profiles_client.clone();
-                move |headers: HeaderMap,
-                      Path((powner, prepository)): Path<(String, String)>| {
-                    get_profile(profiles_client, headers, powner, prepository, None)
-                }
-            }),
-        )
-        // Function Profiles - get (with commit)
-        .route(
-            "/functions/profiles/{powner}/{prepository}/{pcommit}",
-            axum::routing::get({
-                let profiles_client = profiles_client.clone();
-                move |headers: HeaderMap,
-                      Path((powner, prepository, pcommit)): Path<(
-                    String,
-                    String,
-                    String,
-                )>| {
-                    get_profile(
-                        profiles_client,
-                        headers,
-                        powner,
-                        prepository,
-                        Some(pcommit),
-                    )
-                }
-            }),
-        )
-        // Function Profiles - get usage (without commit)
-        .route(
-            "/functions/profiles/{powner}/{prepository}/usage",
-            axum::routing::get({
-                let profiles_client = profiles_client.clone();
-                move |headers: HeaderMap,
-                      Path((powner, prepository)): Path<(String, String)>| {
-                    get_profile_usage(profiles_client, headers, powner, prepository, None)
-                }
-            }),
-        )
-        // Function Profiles - get usage (with commit)
-        .route(
-            "/functions/profiles/{powner}/{prepository}/{pcommit}/usage",
-            axum::routing::get({
-                let profiles_client = profiles_client.clone();
-                move |headers: HeaderMap,
-                      Path((powner, prepository, pcommit)): Path<(String, String, String)>| {
-                    get_profile_usage(
-                        profiles_client,
-                        headers,
-                        powner,
-                        prepository,
-                        Some(pcommit),
-                    )
-                }
-            }),
-        )
-        // Function-Profile Pairs - list
-        .route(
-            "/functions/profiles/pairs",
-            axum::routing::get({
-                let pairs_client = pairs_client.clone();
-                move |headers: HeaderMap| list_function_profile_pairs(pairs_client, headers)
-            }),
-        )
-        // Function-Profile Pairs - get (no commits)
-        .route(
-            "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}",
-            axum::routing::get({
-                let pairs_client = pairs_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository, powner, prepository)): Path<(String, String, String, String)>| {
-                    get_function_profile_pair(
-                        pairs_client,
-                        headers,
-                        fowner,
-                        frepository,
-                        None,
-                        powner,
-                        prepository,
-                        None,
-                    )
-                }
-            }),
-        )
-        // Function-Profile Pairs - get (fcommit only)
-        .route(
-            "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}",
-            axum::routing::get({
-                let pairs_client = pairs_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository, fcommit, powner, prepository)): Path<(String, String, String, String, String)>| {
-                    get_function_profile_pair(
-                        pairs_client,
-                        headers,
-                        fowner,
-                        frepository,
-                        Some(fcommit),
-                        powner,
-                        prepository,
-                        None,
-                    )
-                }
-            }),
-        )
-        // Function-Profile Pairs - get (pcommit only)
-        .route(
-            "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}/{pcommit}",
-            axum::routing::get({
-                let pairs_client = pairs_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository, powner, prepository, pcommit)): Path<(String, String, String, String, String)>| {
-                    get_function_profile_pair(
-                        pairs_client,
-                        headers,
-                        fowner,
-                        frepository,
-                        None,
-                        powner,
-                        prepository,
-                        Some(pcommit),
-                    )
-                }
-            }),
-        )
-        // Function-Profile Pairs - get (both commits)
-        .route(
-            "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}/{pcommit}",
-            axum::routing::get({
-                let pairs_client = pairs_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository, fcommit, powner, prepository, pcommit)): Path<(String, String, String, String, String, String)>| {
-                    get_function_profile_pair(
-                        pairs_client,
-                        headers,
-                        fowner,
-                        frepository,
-                        Some(fcommit),
-                        powner,
-                        prepository,
-                        Some(pcommit),
-                    )
-                }
-            }),
-        )
-        // Function-Profile Pairs - get usage (no commits)
-        .route(
-            "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}/usage",
-            axum::routing::get({
-                let pairs_client = pairs_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository, powner, prepository)): Path<(String, String, String, String)>| {
-                    get_function_profile_pair_usage(
-                        pairs_client,
-                        headers,
-                        fowner,
-                        frepository,
-                        None,
-                        powner,
-                        prepository,
-                        None,
-                    )
-                }
-            }),
-        )
-        // Function-Profile Pairs - get usage (fcommit only)
-        .route(
-            "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}/usage",
-            axum::routing::get({
-                let pairs_client = pairs_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository, fcommit, powner, prepository)): Path<(String, String, String, String, String)>| {
-                    get_function_profile_pair_usage(
-                        pairs_client,
-                        headers,
-                        fowner,
-                        frepository,
-                        Some(fcommit),
-                        powner,
-                        prepository,
-                        None,
-                    )
-                }
-            }),
-        )
-        // Function-Profile Pairs - get usage (pcommit only)
-        .route(
-            "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}/{pcommit}/usage",
-            axum::routing::get({
-                let pairs_client = pairs_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository, powner, prepository, pcommit)): Path<(String, String, String, String, String)>| {
-                    get_function_profile_pair_usage(
-                        pairs_client,
-                        headers,
-                        fowner,
-                        frepository,
-                        None,
-                        powner,
-                        prepository,
-                        Some(pcommit),
-                    )
-                }
-            }),
-        )
-        // Function-Profile Pairs - get usage (both commits)
-        .route(
-            "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}/{pcommit}/usage",
-            axum::routing::get({
-                let pairs_client = pairs_client.clone();
-                move |headers: HeaderMap,
-                      Path((fowner, frepository, fcommit, powner, prepository, pcommit)): Path<(String, String, String, String, String, String)>| {
-                    get_function_profile_pair_usage(
-                        pairs_client,
-                        headers,
-                        fowner,
-                        frepository,
-                        Some(fcommit),
-                        powner,
-                        prepository,
-                        Some(pcommit),
-                    )
-                }
-            }),
-        )
-        // Function Profile Computations - create
-        // inline function
-        .route(
-            "/functions/profiles/compute",
-            axum::routing::post({
-                let profile_computations_client =
-                    profile_computations_client.clone();
-                move |headers: HeaderMap,
-                      Json(body): Json<
-                    objectiveai::functions::profiles::computations::request::FunctionInlineRequestBody,
-                >| {
-                    create_profile_computation(
-                        profile_computations_client,
-                        headers,
-                        objectiveai::functions::profiles::computations::request::Request::FunctionInline {
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Function Profile Computations - create
-        // remote function (without commit)
-        .route(
-            "/functions/{fowner}/{frepository}/profiles/compute",
-            axum::routing::post({
-                let profile_computations_client =
-                    profile_computations_client.clone();
-                move |headers: HeaderMap,
-                      Path(path): Path<
-                    objectiveai::functions::profiles::computations::request::FunctionRemoteRequestPath,
-                >,
-                      Json(body): Json<
-                    objectiveai::functions::profiles::computations::request::FunctionRemoteRequestBody,
-                >| {
-                    create_profile_computation(
-                        profile_computations_client,
-                        headers,
-                        objectiveai::functions::profiles::computations::request::Request::FunctionRemote {
-                            path,
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Function Profile Computations - create
-        // remote function (with commit)
-        .route(
-            "/functions/{fowner}/{frepository}/{fcommit}/profiles/compute",
-            axum::routing::post({
-                let profile_computations_client =
-                    profile_computations_client.clone();
-                move |headers: HeaderMap,
-                      Path(path): Path<
-                    objectiveai::functions::profiles::computations::request::FunctionRemoteRequestPath,
-                >,
-                      Json(body): Json<
-                    objectiveai::functions::profiles::computations::request::FunctionRemoteRequestBody,
-                >| {
-                    create_profile_computation(
-                        profile_computations_client,
-                        headers,
-                        objectiveai::functions::profiles::computations::request::Request::FunctionRemote {
-                            path,
-                            body,
-                        },
-                    )
-                }
-            }),
-        )
-        // Auth - create API key
-        .route(
-            "/auth/keys",
-            axum::routing::post({
-                let auth_client = auth_client.clone();
-                move |headers: HeaderMap,
-                      Json(body): Json<
-                    objectiveai::auth::request::CreateApiKeyRequest,
-                >| {
-                    create_api_key(auth_client, headers, body)
-                }
-            }),
-        )
-        // Auth - create OpenRouter BYOK API key
-        .route(
-            "/auth/keys/openrouter",
-            axum::routing::post({
-                let auth_client = auth_client.clone();
-                move |headers: HeaderMap,
-                      Json(body): Json<
-                    objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest,
-                >| {
-                    create_openrouter_byok_api_key(auth_client, headers, body)
-                }
-            }),
-        )
-        // Auth - disable API key
-        .route(
-            "/auth/keys",
-            axum::routing::delete({
-                let auth_client = auth_client.clone();
-                move |headers: HeaderMap,
-                      Json(body): Json<
-                    objectiveai::auth::request::DisableApiKeyRequest,
-                >| {
-                    disable_api_key(auth_client, headers, body)
-                }
-            }),
-        )
-        // Auth - delete OpenRouter BYOK API key
-        .route(
-            "/auth/keys/openrouter",
-            axum::routing::delete({
-                let auth_client = auth_client.clone();
-                move |headers: HeaderMap| {
-                    delete_openrouter_byok_api_key(auth_client, headers)
-                }
-            }),
-        )
-        // Auth - list API keys
-        .route(
-            "/auth/keys",
-            axum::routing::get({
-                let auth_client = auth_client.clone();
-                move |headers: HeaderMap| {
-                    list_api_keys(auth_client, headers)
-                }
-            }),
-        )
-        // Auth - get OpenRouter BYOK API key
-        .route(
-            "/auth/keys/openrouter",
-            axum::routing::get({
-                let auth_client = auth_client.clone();
-                move |headers: HeaderMap| {
-                    get_openrouter_byok_api_key(auth_client, headers)
-                }
-            }),
-        )
-        // Auth - get credits
-        .route(
-            "/auth/credits",
-            axum::routing::get({
-                let auth_client = auth_client.clone();
-                move |headers: HeaderMap| {
-                    get_credits(auth_client, headers)
-                }
-            }),
-        )
-        // Ensemble - list
-        .route(
-            "/ensembles",
-            axum::routing::get({
-                let ensemble_client = ensemble_client.clone();
-                move |headers: HeaderMap| {
-                    list_ensembles(ensemble_client, headers)
-                }
-            }),
-        )
-        // Ensemble - get
-        .route(
-            "/ensembles/{id}",
-            axum::routing::get({
-                let ensemble_client = ensemble_client.clone();
-                move |headers: HeaderMap, Path(id): Path<String>| {
-                    get_ensemble(ensemble_client, headers, id)
-                }
-            }),
-        )
-        // Ensemble - get usage
-        .route(
-            "/ensembles/{id}/usage",
-            axum::routing::get({
-                let ensemble_client = ensemble_client.clone();
-                move |headers: HeaderMap, Path(id): Path<String>| {
-                    get_ensemble_usage(ensemble_client, headers, id)
-                }
-            }),
-        )
-        // Ensemble LLM - list
-        .route(
-            "/ensemble_llms",
-            axum::routing::get({
-                let ensemble_llm_client = ensemble_llm_client.clone();
-                move |headers: HeaderMap| {
-                    list_ensemble_llms(ensemble_llm_client, headers)
-                }
-            }),
-        )
-        // Ensemble LLM - get
-        .route(
-            "/ensemble_llms/{id}",
-            axum::routing::get({
-                let ensemble_llm_client = ensemble_llm_client.clone();
-                move |headers: HeaderMap, Path(id): Path<String>| {
-                    get_ensemble_llm(ensemble_llm_client, headers, id)
-                }
-            }),
-        )
-        // Ensemble LLM - get usage
-        .route(
-            "/ensemble_llms/{id}/usage",
-            axum::routing::get({
-                let ensemble_llm_client = ensemble_llm_client.clone();
-                move |headers: HeaderMap, Path(id): Path<String>| {
-                    get_ensemble_llm_usage(ensemble_llm_client, headers, id)
-                }
-            }),
+
+    let app = server::Builder::new(server::default_context)
+        .chat_completions(chat_completions_client)
+        .vector_completions(vector_completions_client)
+        .vector_completions_cache(vector_completions_cache_client)
+        .functions(functions_client)
+        .function_executions(function_executions_client, executions_store)
+        .profiles(profiles_client)
+        .function_profile_pairs(pairs_client)
+        .profile_computations(profile_computations_client)
+        .jobs(jobs_client)
+        .auth(auth_client)
+        .ensembles(ensemble_client)
+        .ensemble_llms(ensemble_llm_client)
+        .admin(upstream_health, admin_api_key)
+        .build()
+        // Tracing
+        .layer(
+            tower_http::trace::TraceLayer::new_for_http().make_span_with(
+                tower_http::trace::DefaultMakeSpan::new()
+                    .level(tracing::Level::INFO),
+            ),
         )
         // CORS
         .layer(
@@ -1082,820 +492,41 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
-// Create Context
-
-fn context(headers: &HeaderMap) -> ctx::Context<ctx::DefaultContextExt> {
-    ctx::Context::new(
-        Arc::new(ctx::DefaultContextExt::from_headers(headers)),
-        rust_decimal::Decimal::ONE,
-    )
-}
-
-// Chat Completions
-
-async fn create_chat_completion(
-    client: Arc<
-        chat::completions::Client<
-            ctx::DefaultContextExt,
-            impl ensemble_llm::fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl chat::completions::usage_handler::UsageHandler<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    body: objectiveai::chat::completions::request::ChatCompletionCreateParams,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    if body.stream.unwrap_or(false) {
-        match client
-            .create_streaming_for_chat_handle_usage(ctx, Arc::new(body))
-            .await
-        {
-            Ok(stream) => Sse::new(
-                stream
-                    .map(|result| {
-                        Ok::<Event, Infallible>(
-                            Event::default().data(
-                                match result {
-                                    Ok(chunk) => serde_json::to_string(&chunk),
-                                    Err(e) => serde_json::to_string(
-                                        &ResponseError::from(&e),
-                                    ),
-                                }
-                                .unwrap(),
-                            ),
-                        )
-                    })
-                    .chain(StreamOnce::new(
-                        Ok(Event::default().data("[DONE]")),
-                    )),
-            )
-            .into_response(),
-            Err(e) => ResponseError::from(&e).into_response(),
-        }
-    } else {
-        match client
-            .create_unary_for_chat_handle_usage(ctx, Arc::new(body))
-            .await
-        {
-            Ok(r) => Json(r).into_response(),
-            Err(e) => ResponseError::from(&e).into_response(),
-        }
-    }
-}
-
-// Vector Completions
-
-async fn create_vector_completion(
-    client: Arc<
-        vector::completions::Client<
-            ctx::DefaultContextExt,
-            impl ensemble_llm::fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl chat::completions::usage_handler::UsageHandler<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl ensemble::fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl vector::completions::completion_votes_fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl vector::completions::cache_vote_fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl vector::completions::usage_handler::UsageHandler<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    body: objectiveai::vector::completions::request::VectorCompletionCreateParams,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    if body.stream.unwrap_or(false) {
-        match client
-            .create_streaming_handle_usage(ctx, Arc::new(body))
-            .await
-        {
-            Ok(stream) => Sse::new(
-                stream
-                    .map(|chunk| {
-                        Ok::<Event, Infallible>(
-                            Event::default()
-                                .data(serde_json::to_string(&chunk).unwrap()),
-                        )
-                    })
-                    .chain(StreamOnce::new(
-                        Ok(Event::default().data("[DONE]")),
-                    )),
-            )
-            .into_response(),
-            Err(e) => ResponseError::from(&e).into_response(),
-        }
-    } else {
-        match client.create_unary_handle_usage(ctx, Arc::new(body)).await {
-            Ok(r) => Json(r).into_response(),
-            Err(e) => ResponseError::from(&e).into_response(),
-        }
-    }
-}
-
-// Functions
-
-async fn list_functions(
-    client: Arc<
-        functions::Client<
-            ctx::DefaultContextExt,
-            impl functions::function_fetcher::Fetcher<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-            impl functions::retrieval_client::Client<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.list_functions(ctx).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => ResponseError::from(&e).into_response(),
-    }
-}
-
-async fn get_function_usage(
-    client: Arc<
-        functions::Client<
-            ctx::DefaultContextExt,
-            impl functions::function_fetcher::Fetcher<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-            impl functions::retrieval_client::Client<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    owner: String,
-    repository: String,
-    commit: Option<String>,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client
-        .get_function_usage(ctx, &owner, &repository, commit.as_deref())
-        .await
-    {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => ResponseError::from(&e).into_response(),
-    }
-}
-
-async fn execute_function(
-    client: Arc<
-        functions::executions::Client<
-            ctx::DefaultContextExt,
-            impl ensemble_llm::fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl chat::completions::usage_handler::UsageHandler<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl ensemble::fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl vector::completions::completion_votes_fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl vector::completions::cache_vote_fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl vector::completions::usage_handler::UsageHandler<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl functions::function_fetcher::Fetcher<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-            impl functions::profile_fetcher::Fetcher<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-            impl functions::executions::usage_handler::UsageHandler<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    request: objectiveai::functions::executions::request::Request,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    if request.base().stream.unwrap_or(false) {
-        match client
-            .create_streaming_handle_usage(ctx, Arc::new(request))
-            .await
-        {
-            Ok(stream) => Sse::new(
-                stream
-                    .map(|chunk| {
-                        Ok::<Event, Infallible>(
-                            Event::default()
-                                .data(serde_json::to_string(&chunk).unwrap()),
-                        )
-                    })
-                    .chain(StreamOnce::new(
-                        Ok(Event::default().data("[DONE]")),
-                    )),
-            )
-            .into_response(),
-            Err(e) => ResponseError::from(&e).into_response(),
-        }
-    } else {
-        match client
-            .create_unary_handle_usage(ctx, Arc::new(request))
-            .await
-        {
-            Ok(r) => Json(r).into_response(),
-            Err(e) => ResponseError::from(&e).into_response(),
-        }
-    }
-}
-
-// Profiles
-
-async fn list_profiles(
-    client: Arc<
-        functions::profiles::Client<
-            ctx::DefaultContextExt,
-            impl functions::profile_fetcher::Fetcher<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-            impl functions::profiles::retrieval_client::Client<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.list_profiles(ctx).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => ResponseError::from(&e).into_response(),
-    }
-}
-
-async fn get_profile_usage(
-    client: Arc<
-        functions::profiles::Client<
-            ctx::DefaultContextExt,
-            impl functions::profile_fetcher::Fetcher<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-            impl functions::profiles::retrieval_client::Client<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    owner: String,
-    repository: String,
-    commit: Option<String>,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client
-        .get_profile_usage(ctx, &owner, &repository, commit.as_deref())
-        .await
-    {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => ResponseError::from(&e).into_response(),
-    }
-}
-
-// Function-Profile Pairs
-
-async fn list_function_profile_pairs(
-    client: Arc<
-        impl functions::pair_retrieval_client::Client<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-    >,
-    headers: HeaderMap,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.list_function_profile_pairs(ctx).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn get_function_profile_pair(
-    client: Arc<
-        impl functions::pair_retrieval_client::Client<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-    >,
-    headers: HeaderMap,
-    fowner: String,
-    frepository: String,
-    fcommit: Option<String>,
-    powner: String,
-    prepository: String,
-    pcommit: Option<String>,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client
-        .get_function_profile_pair(
-            ctx,
-            &fowner,
-            &frepository,
-            fcommit.as_deref(),
-            &powner,
-            &prepository,
-            pcommit.as_deref(),
-        )
-        .await
-    {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn get_function_profile_pair_usage(
-    client: Arc<
-        impl functions::pair_retrieval_client::Client<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-    >,
-    headers: HeaderMap,
-    fowner: String,
-    frepository: String,
-    fcommit: Option<String>,
-    powner: String,
-    prepository: String,
-    pcommit: Option<String>,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client
-        .get_function_profile_pair_usage(
-            ctx,
-            &fowner,
-            &frepository,
-            fcommit.as_deref(),
-            &powner,
-            &prepository,
-            pcommit.as_deref(),
-        )
-        .await
-    {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-// Vector Completions Cache
-
-async fn get_vector_completion_votes(
-    client: Arc<
-        vector::completions::cache::Client<
-            ctx::DefaultContextExt,
-            impl vector::completions::completion_votes_fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl vector::completions::cache_vote_fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    id: String,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.fetch_completion_votes(ctx, &id).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn get_vector_cache_vote(
-    client: Arc<
-        vector::completions::cache::Client<
-            ctx::DefaultContextExt,
-            impl vector::completions::completion_votes_fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-            impl vector::completions::cache_vote_fetcher::Fetcher<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    body: objectiveai::vector::completions::cache::request::CacheVoteRequestOwned,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client
-        .fetch_cache_vote(
-            ctx,
-            &body.model,
-            body.models.as_deref(),
-            &body.messages,
-            body.tools.as_deref(),
-            &body.responses,
-        )
-        .await
-    {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-// Functions - get
-
-async fn get_function(
-    client: Arc<
-        functions::Client<
-            ctx::DefaultContextExt,
-            impl functions::function_fetcher::Fetcher<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-            impl functions::retrieval_client::Client<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    owner: String,
-    repository: String,
-    commit: Option<String>,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client
-        .get_function(ctx, &owner, &repository, commit.as_deref())
-        .await
-    {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-// Profiles - get
-
-async fn get_profile(
-    client: Arc<
-        functions::profiles::Client<
-            ctx::DefaultContextExt,
-            impl functions::profile_fetcher::Fetcher<ctx::DefaultContextExt>
-            + Send
-            + Sync
-            + 'static,
-            impl functions::profiles::retrieval_client::Client<
-                ctx::DefaultContextExt,
-            > + Send
-            + Sync
-            + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    owner: String,
-    repository: String,
-    commit: Option<String>,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client
-        .get_profile(ctx, &owner, &repository, commit.as_deref())
-        .await
-    {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-// Profile Computations
-
-async fn create_profile_computation(
-    // client: Arc<
-    //     impl functions::profiles::computations::Client<ctx::DefaultContextExt>
-    //     + Send
-    //     + Sync
-    //     + 'static,
-    // >,
-    // https://github.com/rust-lang/rust/issues/100013
-    // using a concrete type for client instead
-    client: Arc<functions::profiles::computations::ObjectiveAiClient>,
-    headers: HeaderMap,
-    request: objectiveai::functions::profiles::computations::request::Request,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    if request.base().stream.unwrap_or(false) {
-        match client.create_streaming(ctx, Arc::new(request)).await {
-            Ok(stream) => Sse::new(
-                stream
-                    .map(|result| {
-                        Ok::<Event, Infallible>(
-                            Event::default().data(
-                                match result {
-                                    Ok(chunk) => serde_json::to_string(&chunk),
-                                    Err(e) => serde_json::to_string(&e),
-                                }
-                                .unwrap(),
-                            ),
-                        )
-                    })
-                    .chain(StreamOnce::new(
-                        Ok(Event::default().data("[DONE]")),
-                    )),
-            )
-            .into_response(),
-            Err(e) => e.into_response(),
-        }
-    } else {
-        match client.create_unary(ctx, Arc::new(request)).await {
-            Ok(r) => Json(r).into_response(),
-            Err(e) => e.into_response(),
-        }
-    }
-}
-
-// Auth
-
-async fn create_api_key(
-    client: Arc<impl auth::Client<ctx::DefaultContextExt> + Send + Sync + 'static>,
-    headers: HeaderMap,
-    body: objectiveai::auth::request::CreateApiKeyRequest,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.create_api_key(ctx, body).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn create_openrouter_byok_api_key(
-    client: Arc<impl auth::Client<ctx::DefaultContextExt> + Send + Sync + 'static>,
-    headers: HeaderMap,
-    body: objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.create_openrouter_byok_api_key(ctx, body).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn disable_api_key(
-    client: Arc<impl auth::Client<ctx::DefaultContextExt> + Send + Sync + 'static>,
-    headers: HeaderMap,
-    body: objectiveai::auth::request::DisableApiKeyRequest,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.disable_api_key(ctx, body).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn delete_openrouter_byok_api_key(
-    client: Arc<impl auth::Client<ctx::DefaultContextExt> + Send + Sync + 'static>,
-    headers: HeaderMap,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.delete_openrouter_byok_api_key(ctx).await {
-        Ok(()) => axum::http::StatusCode::OK.into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn list_api_keys(
-    client: Arc<impl auth::Client<ctx::DefaultContextExt> + Send + Sync + 'static>,
-    headers: HeaderMap,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.list_api_keys(ctx).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn get_openrouter_byok_api_key(
-    client: Arc<impl auth::Client<ctx::DefaultContextExt> + Send + Sync + 'static>,
-    headers: HeaderMap,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.get_openrouter_byok_api_key(ctx).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn get_credits(
-    client: Arc<impl auth::Client<ctx::DefaultContextExt> + Send + Sync + 'static>,
-    headers: HeaderMap,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.get_credits(ctx).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-// Ensemble
-
-async fn list_ensembles(
-    client: Arc<
-        ensemble::Client<
-            ctx::DefaultContextExt,
-            impl ensemble::fetcher::Fetcher<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-            impl ensemble::retrieval_client::Client<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.list(ctx).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn get_ensemble(
-    client: Arc<
-        ensemble::Client<
-            ctx::DefaultContextExt,
-            impl ensemble::fetcher::Fetcher<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-            impl ensemble::retrieval_client::Client<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    id: String,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.get(ctx, &id).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn get_ensemble_usage(
-    client: Arc<
-        ensemble::Client<
-            ctx::DefaultContextExt,
-            impl ensemble::fetcher::Fetcher<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-            impl ensemble::retrieval_client::Client<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    id: String,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.get_usage(ctx, &id).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-// Ensemble LLM
-
-async fn list_ensemble_llms(
-    client: Arc<
-        ensemble_llm::Client<
-            ctx::DefaultContextExt,
-            impl ensemble_llm::fetcher::Fetcher<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-            impl ensemble_llm::retrieval_client::Client<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.list(ctx).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
-}
-
-async fn get_ensemble_llm(
-    client: Arc<
-        ensemble_llm::Client<
-            ctx::DefaultContextExt,
-            impl ensemble_llm::fetcher::Fetcher<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-            impl ensemble_llm::retrieval_client::Client<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    id: String,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.get(ctx, &id).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
+// Tracing
+
+// Logs events to stdout and exports tracing spans via OTLP, configured by the
+// standard `OTEL_EXPORTER_OTLP_*` environment variables.
+fn init_tracing(
+    service_name: String,
+) -> opentelemetry_sdk::trace::SdkTracerProvider {
+    use opentelemetry::trace::TracerProvider;
+    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .build()
+        .unwrap();
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            opentelemetry_sdk::Resource::builder()
+                .with_service_name(service_name.clone())
+                .build(),
+        )
+        .build();
+    let tracer = provider.tracer(service_name);
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .init();
+    provider
 }
 
-async fn get_ensemble_llm_usage(
-    client: Arc<
-        ensemble_llm::Client<
-            ctx::DefaultContextExt,
-            impl ensemble_llm::fetcher::Fetcher<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-            impl ensemble_llm::retrieval_client::Client<ctx::DefaultContextExt>
-                + Send
-                + Sync
-                + 'static,
-        >,
-    >,
-    headers: HeaderMap,
-    id: String,
-) -> axum::response::Response {
-    let ctx = context(&headers);
-    match client.get_usage(ctx, &id).await {
-        Ok(r) => Json(r).into_response(),
-        Err(e) => e.into_response(),
-    }
+/// Reports invalid configuration and exits.
+fn config_error(error: impl std::fmt::Display) -> ! {
+    eprintln!("configuration error: {error}");
+    std::process::exit(1)
 }
@@ -0,0 +1,251 @@
+//! Process-wide Prometheus metrics.
+//!
+//! Metrics are recorded into [`METRICS`] from anywhere in the crate and
+//! rendered in the Prometheus text format by [`Metrics::render`].
+
+use prometheus::{
+    CounterVec, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts,
+    Registry,
+};
+use rust_decimal::prelude::ToPrimitive;
+use std::{
+    collections::HashSet,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+/// The metrics shared by every request on the server.
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+/// Maximum number of distinct values of the `model` label. Models admitted
+/// after the limit is reached are recorded as `other`.
+pub const MAX_MODEL_LABELS: usize = 512;
+
+/// The `model` label value of models which have not been admitted.
+const OTHER_MODEL: &str = "other";
+
+/// Operational metrics for the API server.
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    /// The registry all metrics are registered with.
+    pub registry: Registry,
+    /// HTTP requests by route, method, and status.
+    pub http_requests: IntCounterVec,
+    /// HTTP request latency until response headers, by route and method.
+    pub http_request_duration: HistogramVec,
+    /// Upstream requests by upstream, model, and outcome.
+    pub upstream_requests: IntCounterVec,
+    /// Time from sending an upstream request to its first chunk.
+    pub upstream_time_to_first_chunk: HistogramVec,
+    /// Tokens used by upstream requests, by model and kind.
+    pub upstream_tokens: IntCounterVec,
+    /// Cost charged for upstream requests, by model.
+    pub upstream_cost: CounterVec,
+    /// Ensemble LLM responses from which no vote could be parsed.
+    pub vote_parse_failures: IntCounterVec,
+    /// Cache lookups by cache and result.
+    pub cache_lookups: IntCounterVec,
+    /// Streams currently being served, by kind.
+    pub in_flight_streams: IntGaugeVec,
+    /// Models which are recorded under their own `model` label.
+    models: Arc<RwLock<HashSet<String>>>,
+}
+
+impl Metrics {
+    /// Creates and registers all metrics.
+    pub fn new() -> Self {
+        let registry =
+            Registry::new_custom(Some("objectiveai".to_string()), None)
+                .unwrap();
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests handled."),
+            &["route", "method", "status"],
+        )
+        .unwrap();
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency until response headers.",
+            ),
+            &["route", "method"],
+        )
+        .unwrap();
+        let upstream_requests = IntCounterVec::new(
+            Opts::new("upstream_requests_total", "Upstream requests made."),
+            &["upstream", "model", "outcome"],
+        )
+        .unwrap();
+        let upstream_time_to_first_chunk = HistogramVec::new(
+            HistogramOpts::new(
+                "upstream_time_to_first_chunk_seconds",
+                "Time from sending an upstream request to its first chunk.",
+            )
+            .buckets(vec![
+                0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 40.0, 80.0, 120.0,
+            ]),
+            &["upstream", "model"],
+        )
+        .unwrap();
+        let upstream_tokens = IntCounterVec::new(
+            Opts::new("upstream_tokens_total", "Tokens used upstream."),
+            &["model", "kind"],
+        )
+        .unwrap();
+        let upstream_cost = CounterVec::new(
+            Opts::new(
+                "upstream_cost_total",
+                "Cost charged for upstream requests.",
+            ),
+            &["model"],
+        )
+        .unwrap();
+        let vote_parse_failures = IntCounterVec::new(
+            Opts::new(
+                "vote_parse_failures_total",
+                "Ensemble LLM responses from which no vote could be parsed.",
+            ),
+            &["model"],
+        )
+        .unwrap();
+        let cache_lookups = IntCounterVec::new(
+            Opts::new("cache_lookups_total", "Cache lookups."),
+            &["cache", "result"],
+        )
+        .unwrap();
+        let in_flight_streams = IntGaugeVec::new(
+            Opts::new("in_flight_streams", "Streams currently being served."),
+            &["kind"],
+        )
+        .unwrap();
+        registry.register(Box::new(http_requests.clone())).unwrap();
+        registry
+            .register(Box::new(http_request_duration.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(upstream_requests.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(upstream_time_to_first_chunk.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(upstream_tokens.clone()))
+            .unwrap();
+        registry.register(Box::new(upstream_cost.clone())).unwrap();
+        registry
+            .register(Box::new(vote_parse_failures.clone()))
+            .unwrap();
+        registry.register(Box::new(cache_lookups.clone())).unwrap();
+        registry
+            .register(Box::new(in_flight_streams.clone()))
+            .unwrap();
+        Self {
+            registry,
+            http_requests,
+            http_request_duration,
+            upstream_requests,
+            upstream_time_to_first_chunk,
+            upstream_tokens,
+            upstream_cost,
+            vote_parse_failures,
+            cache_lookups,
+            in_flight_streams,
+            models: Arc::new(RwLock::new(HashSet::new())),
+        }
+    }
+
+    /// Admits a model to be recorded under its own `model` label, unless
+    /// [`MAX_MODEL_LABELS`] models already are.
+    ///
+    /// Only models an upstream has served are admitted, so that arbitrary
+    /// model names in requests cannot grow the number of series.
+    pub fn admit_model(&self, model: &str) {
+        if self.models.read().unwrap().contains(model) {
+            return;
+        }
+        let mut models = self.models.write().unwrap();
+        if models.len() < MAX_MODEL_LABELS {
+            models.insert(model.to_owned());
+        }
+    }
+
+    /// Returns the `model` label value for a model, which is `other` unless
+    /// the model was admitted.
+    pub fn model_label<'a>(&self, model: &'a str) -> &'a str {
+        if self.models.read().unwrap().contains(model) {
+            model
+        } else {
+            OTHER_MODEL
+        }
+    }
+
+    /// Records the outcome of an upstream request, either `"ok"` or the kind
+    /// of error which failed it.
+    pub fn record_upstream_outcome(
+        &self,
+        upstream: &str,
+        model: &str,
+        outcome: &str,
+    ) {
+        self.upstream_requests
+            .with_label_values(&[upstream, self.model_label(model), outcome])
+            .inc();
+    }
+
+    /// Records the tokens and cost of a finished upstream request.
+    pub fn record_upstream_usage(
+        &self,
+        model: &str,
+        usage: &objectiveai::chat::completions::response::Usage,
+    ) {
+        let model = self.model_label(model);
+        self.upstream_tokens
+            .with_label_values(&[model, "prompt"])
+            .inc_by(usage.prompt_tokens);
+        self.upstream_tokens
+            .with_label_values(&[model, "completion"])
+            .inc_by(usage.completion_tokens);
+        self.upstream_cost
+            .with_label_values(&[model])
+            .inc_by(usage.cost.to_f64().unwrap_or(0.0));
+    }
+
+    /// Records a cache lookup.
+    pub fn record_cache_lookup(&self, cache: &str, hit: bool) {
+        self.cache_lookups
+            .with_label_values(&[cache, if hit { "hit" } else { "miss" }])
+            .inc();
+    }
+
+    /// Marks a stream of `kind` as in flight until the guard is dropped.
+    pub fn in_flight_stream(&self, kind: &'static str) -> InFlightStream {
+        self.in_flight_streams.with_label_values(&[kind]).inc();
+        InFlightStream { kind }
+    }
+
+    /// Renders all metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        prometheus::TextEncoder::new()
+            .encode_to_string(&self.registry.gather())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decrements the in-flight stream gauge when dropped.
+#[derive(Debug)]
+pub struct InFlightStream {
+    kind: &'static str,
+}
+
+impl Drop for InFlightStream {
+    fn drop(&mut self) {
+        METRICS
+            .in_flight_streams
+            .with_label_values(&[self.kind])
+            .dec();
+    }
+}
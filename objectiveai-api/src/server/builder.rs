@@ -0,0 +1,1411 @@
+//! Router builder for the ObjectiveAI API.
+
+use super::{ContextFactory, handlers::*};
+use crate::{auth, chat, ctx, ensemble, ensemble_llm, functions, vector};
+use axum::{
+    Json,
+    extract::{Path, Query},
+    http::HeaderMap,
+};
+use std::{marker::PhantomData, sync::Arc};
+
+/// Builds an [`axum::Router`] serving the ObjectiveAI API.
+///
+/// Each method adds the routes for one group of endpoints, backed by the
+/// clients passed to it. Groups which are never added are not served. The
+/// router returned by [`Builder::build`] can be served directly, or mounted
+/// under a prefix alongside other routes with [`axum::Router::nest`].
+pub struct Builder<CTXEXT, FCTX> {
+    /// Creates the request context from the request headers.
+    context: Arc<FCTX>,
+    /// The routes added so far.
+    router: axum::Router,
+    _ctxext: PhantomData<fn() -> CTXEXT>,
+}
+
+impl<CTXEXT, FCTX> Builder<CTXEXT, FCTX>
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+    FCTX: ContextFactory<CTXEXT> + Send + Sync + 'static,
+{
+    /// Creates a new builder with no routes.
+    pub fn new(context: FCTX) -> Self {
+        Self {
+            context: Arc::new(context),
+            router: axum::Router::new(),
+            _ctxext: PhantomData,
+        }
+    }
+
+    /// Returns the router, recording HTTP metrics for every route.
+    pub fn build(self) -> axum::Router {
+        self.router
+            .route_layer(axum::middleware::from_fn(record_http_metrics))
+    }
+
+    /// Adds the routes for Chat Completions.
+    pub fn chat_completions(
+        self,
+        chat_completions_client: Arc<
+            chat::completions::Client<
+                CTXEXT,
+                impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+                impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+            >,
+        >,
+    ) -> Self {
+        let router = self
+            .router
+            // Chat Completions - create
+            .route(
+                "/chat/completions",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let chat_completions_client = chat_completions_client.clone();
+                    move |headers: HeaderMap,
+                          Json(body): Json<
+                        objectiveai::chat::completions::request::ChatCompletionCreateParams,
+                    >| {
+                        create_chat_completion(chat_completions_client, context.context(&headers), body)
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for Vector Completions.
+    pub fn vector_completions(
+        self,
+        vector_completions_client: Arc<
+            vector::completions::Client<
+                CTXEXT,
+                impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+                impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+                impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl vector::completions::usage_handler::UsageHandler<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+            >,
+        >,
+    ) -> Self {
+        let router = self
+            .router
+            // Vector Completions - create
+            .route(
+                "/vector/completions",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let vector_completions_client = vector_completions_client.clone();
+                    move |headers: HeaderMap,
+                          Json(body): Json<
+                        objectiveai::vector::completions::request::VectorCompletionCreateParams,
+                    >| {
+                        create_vector_completion(vector_completions_client, context.context(&headers), body)
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for the Vector Completion cache.
+    pub fn vector_completions_cache(
+        self,
+        vector_completions_cache_client: Arc<
+            vector::completions::cache::Client<
+                CTXEXT,
+                impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+            >,
+        >,
+    ) -> Self {
+        let router = self
+            .router
+            // Vector Completions - get completion votes
+            .route(
+                "/vector/completions/{id}",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let vector_completions_cache_client =
+                        vector_completions_cache_client.clone();
+                    move |headers: HeaderMap,
+                          Path(id): Path<String>| {
+                        get_vector_completion_votes(
+                            vector_completions_cache_client,
+                            context.context(&headers),
+                            id,
+                        )
+                    }
+                }),
+            )
+            // Vector Completions - get cache vote
+            .route(
+                "/vector/completions/cache",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let vector_completions_cache_client =
+                        vector_completions_cache_client.clone();
+                    move |headers: HeaderMap,
+                          Json(body): Json<
+                        objectiveai::vector::completions::cache::request::CacheVoteRequestOwned,
+                    >| {
+                        get_vector_cache_vote(
+                            vector_completions_cache_client,
+                            context.context(&headers),
+                            body,
+                        )
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for Function retrieval.
+    pub fn functions(
+        self,
+        functions_client: Arc<
+            functions::Client<
+                CTXEXT,
+                impl functions::function_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl functions::retrieval_client::Client<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+            >,
+        >,
+    ) -> Self {
+        let router = self
+            .router
+            // Functions - list
+            .route(
+                "/functions",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let functions_client = functions_client.clone();
+                    move |headers: HeaderMap| {
+                        list_functions(
+                            functions_client,
+                            context.context(&headers),
+                        )
+                    }
+                }),
+            )
+            // Functions - get (without commit)
+            .route(
+                "/functions/{fowner}/{frepository}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let functions_client = functions_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository)): Path<(
+                        String,
+                        String,
+                    )>| {
+                        get_function(
+                            functions_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            None,
+                        )
+                    }
+                }),
+            )
+            // Functions - get (with commit)
+            .route(
+                "/functions/{fowner}/{frepository}/{fcommit}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let functions_client = functions_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository, fcommit)): Path<(
+                        String,
+                        String,
+                        String,
+                    )>| {
+                        get_function(
+                            functions_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            Some(fcommit),
+                        )
+                    }
+                }),
+            )
+            // Functions - get usage (without commit)
+            .route(
+                "/functions/{fowner}/{frepository}/usage",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let functions_client = functions_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository)): Path<(
+                        String,
+                        String,
+                    )>| {
+                        get_function_usage(
+                            functions_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            None,
+                        )
+                    }
+                }),
+            )
+            // Functions - get usage (with commit)
+            .route(
+                "/functions/{fowner}/{frepository}/{fcommit}/usage",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let functions_client = functions_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository, fcommit)): Path<(
+                        String,
+                        String,
+                        String,
+                    )>| {
+                        get_function_usage(
+                            functions_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            Some(fcommit),
+                        )
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for Function Executions, including batch execution, replay, and
+    /// retrieval of stored executions.
+    pub fn function_executions(
+        self,
+        function_executions_client: Arc<
+            functions::executions::Client<
+                CTXEXT,
+                impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+                impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+                impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl vector::completions::usage_handler::UsageHandler<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl functions::function_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl functions::profile_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl functions::executions::usage_handler::UsageHandler<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+            >,
+        >,
+        executions_store: Arc<
+            impl functions::executions::store::Store + Send + Sync + 'static,
+        >,
+    ) -> Self {
+        let router = self
+            .router
+            // Function Executions - create
+            // inline function
+            // inline profile
+            .route(
+                "/functions",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let function_executions_client = function_executions_client.clone();
+                    move |headers: HeaderMap,
+                          Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionInlineProfileInlineRequestBody,
+                    >| {
+                        execute_function(
+                            function_executions_client,
+                            context.context(&headers),
+                            objectiveai::functions::executions::request::Request::FunctionInlineProfileInline {
+                                body,
+                            },
+                        )
+                    }
+                }),
+            )
+            // Function Executions - create
+            // remote function (without commit)
+            // inline profile
+            .route(
+                "/functions/{fowner}/{frepository}",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let function_executions_client = function_executions_client.clone();
+                    move |headers: HeaderMap,
+                          Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestPath,
+                    >,
+                          Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestBody,
+                    >| {
+                        execute_function(
+                            function_executions_client,
+                            context.context(&headers),
+                            objectiveai::functions::executions::request::Request::FunctionRemoteProfileInline {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                }),
+            )
+            // Function Executions - create
+            // remote function (with commit)
+            // inline profile
+            .route(
+                "/functions/{fowner}/{frepository}/{fcommit}",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let function_executions_client = function_executions_client.clone();
+                    move |headers: HeaderMap,
+                          Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestPath,
+                    >,
+                          Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileInlineRequestBody,
+                    >| {
+                        execute_function(
+                            function_executions_client,
+                            context.context(&headers),
+                            objectiveai::functions::executions::request::Request::FunctionRemoteProfileInline {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                }),
+            )
+            // Function Executions - create
+            // inline function
+            // remote profile (without commit)
+            .route(
+                "/functions/profiles/{powner}/{prepository}",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let function_executions_client = function_executions_client.clone();
+                    move |headers: HeaderMap,
+                          Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestPath,
+                    >,
+                          Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestBody,
+                    >| {
+                        execute_function(
+                            function_executions_client,
+                            context.context(&headers),
+                            objectiveai::functions::executions::request::Request::FunctionInlineProfileRemote {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                }),
+            )
+            // Function Executions - create
+            // inline function
+            // remote profile (with commit)
+            .route(
+                "/functions/profiles/{powner}/{prepository}/{pcommit}",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let function_executions_client = function_executions_client.clone();
+                    move |headers: HeaderMap,
+                          Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestPath,
+                    >,
+                          Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionInlineProfileRemoteRequestBody,
+                    >| {
+                        execute_function(
+                            function_executions_client,
+                            context.context(&headers),
+                            objectiveai::functions::executions::request::Request::FunctionInlineProfileRemote {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                }),
+            )
+            // Function Executions - create
+            // remote function (without commit)
+            // remote profile (without commit)
+            .route(
+                "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let function_executions_client = function_executions_client.clone();
+                    move |headers: HeaderMap,
+                          Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
+                    >,
+                          Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestBody,
+                    >| {
+                        execute_function(
+                            function_executions_client,
+                            context.context(&headers),
+                            objectiveai::functions::executions::request::Request::FunctionRemoteProfileRemote {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                }),
+            )
+            // Function Executions - create
+            // remote function (without commit)
+            // remote profile (with commit)
+            .route(
+                "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}/{pcommit}",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let function_executions_client = function_executions_client.clone();
+                    move |headers: HeaderMap,
+                          Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
+                    >,
+                          Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestBody,
+                    >| {
+                        execute_function(
+                            function_executions_client,
+                            context.context(&headers),
+                            objectiveai::functions::executions::request::Request::FunctionRemoteProfileRemote {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                }),
+            )
+            // Function Executions - create
+            // remote function (with commit)
+            // remote profile (without commit)
+            .route(
+                "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let function_executions_client = function_executions_client.clone();
+                    move |headers: HeaderMap,
+                          Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
+                    >,
+                          Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestBody,
+                    >| {
+                        execute_function(
+                            function_executions_client,
+                            context.context(&headers),
+                            objectiveai::functions::executions::request::Request::FunctionRemoteProfileRemote {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                }),
+            )
+            // Function Executions - create
+            // remote function (with commit)
+            // remote profile (with commit)
+            .route(
+                "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}/{pcommit}",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let function_executions_client = function_executions_client.clone();
+                    move |headers: HeaderMap,
+                          Path(path): Path<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestPath,
+                    >,
+                          Json(body): Json<
+                        objectiveai::functions::executions::request::FunctionRemoteProfileRemoteRequestBody,
+                    >| {
+                        execute_function(
+                            function_executions_client,
+                            context.context(&headers),
+                            objectiveai::functions::executions::request::Request::FunctionRemoteProfileRemote {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                }),
+            )
+            // Function Executions - create batch
+            .route(
+                "/functions/batch",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let function_executions_client = function_executions_client.clone();
+                    move |headers: HeaderMap,
+                          Json(body): Json<
+                        objectiveai::functions::executions::batch::FunctionExecutionBatchCreateParams,
+                    >| {
+                        execute_function_batch(function_executions_client, context.context(&headers), body)
+                    }
+                }),
+            )
+            // Function Executions - replay
+            .route(
+                "/functions/replay",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let function_executions_client = function_executions_client.clone();
+                    let executions_store = executions_store.clone();
+                    move |headers: HeaderMap,
+                          Json(body): Json<
+                        objectiveai::functions::executions::FunctionExecutionReplayCreateParams,
+                    >| {
+                        replay_function_execution(
+                            function_executions_client,
+                            executions_store,
+                            context.context(&headers),
+                            body,
+                        )
+                    }
+                }),
+            )
+            // Function Executions - list
+            .route(
+                "/functions/executions",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let executions_store = executions_store.clone();
+                    move |headers: HeaderMap,
+                          Query(params): Query<
+                        objectiveai::functions::executions::ListFunctionExecutionsParams,
+                    >| {
+                        list_function_executions(
+                            executions_store,
+                            context.context(&headers),
+                            params,
+                        )
+                    }
+                }),
+            )
+            // Function Executions - get
+            .route(
+                "/functions/executions/{id}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let executions_store = executions_store.clone();
+                    move |headers: HeaderMap, Path(id): Path<String>| {
+                        get_function_execution(
+                            executions_store,
+                            context.context(&headers),
+                            id,
+                        )
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for Profile retrieval.
+    pub fn profiles(
+        self,
+        profiles_client: Arc<
+            functions::profiles::Client<
+                CTXEXT,
+                impl functions::profile_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl functions::profiles::retrieval_client::Client<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+            >,
+        >,
+    ) -> Self {
+        let router = self
+            .router
+            // Function Profiles - list
+            .route(
+                "/functions/profiles",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let profiles_client = profiles_client.clone();
+                    move |headers: HeaderMap| {
+                        list_profiles(
+                            profiles_client,
+                            context.context(&headers),
+                        )
+                    }
+                }),
+            )
+            // Function Profiles - get (without commit)
+            .route(
+                "/functions/profiles/{powner}/{prepository}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let profiles_client = profiles_client.clone();
+                    move |headers: HeaderMap,
+                          Path((powner, prepository)): Path<(
+                        String,
+                        String,
+                    )>| {
+                        get_profile(
+                            profiles_client,
+                            context.context(&headers),
+                            powner,
+                            prepository,
+                            None,
+                        )
+                    }
+                }),
+            )
+            // Function Profiles - get (with commit)
+            .route(
+                "/functions/profiles/{powner}/{prepository}/{pcommit}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let profiles_client = profiles_client.clone();
+                    move |headers: HeaderMap,
+                          Path((powner, prepository, pcommit)): Path<(
+                        String,
+                        String,
+                        String,
+                    )>| {
+                        get_profile(
+                            profiles_client,
+                            context.context(&headers),
+                            powner,
+                            prepository,
+                            Some(pcommit),
+                        )
+                    }
+                }),
+            )
+            // Function Profiles - get usage (without commit)
+            .route(
+                "/functions/profiles/{powner}/{prepository}/usage",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let profiles_client = profiles_client.clone();
+                    move |headers: HeaderMap,
+                          Path((powner, prepository)): Path<(
+                        String,
+                        String,
+                    )>| {
+                        get_profile_usage(
+                            profiles_client,
+                            context.context(&headers),
+                            powner,
+                            prepository,
+                            None,
+                        )
+                    }
+                }),
+            )
+            // Function Profiles - get usage (with commit)
+            .route(
+                "/functions/profiles/{powner}/{prepository}/{pcommit}/usage",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let profiles_client = profiles_client.clone();
+                    move |headers: HeaderMap,
+                          Path((powner, prepository, pcommit)): Path<(
+                        String,
+                        String,
+                        String,
+                    )>| {
+                        get_profile_usage(
+                            profiles_client,
+                            context.context(&headers),
+                            powner,
+                            prepository,
+                            Some(pcommit),
+                        )
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for Function-Profile pair retrieval.
+    pub fn function_profile_pairs(
+        self,
+        pairs_client: Arc<
+            impl functions::pair_retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    ) -> Self {
+        let router = self
+            .router
+            // Function-Profile Pairs - list
+            .route(
+                "/functions/profiles/pairs",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let pairs_client = pairs_client.clone();
+                    move |headers: HeaderMap| list_function_profile_pairs(pairs_client, context.context(&headers))
+                }),
+            )
+            // Function-Profile Pairs - get (no commits)
+            .route(
+                "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let pairs_client = pairs_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository, powner, prepository)): Path<(String, String, String, String)>| {
+                        get_function_profile_pair(
+                            pairs_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            None,
+                            powner,
+                            prepository,
+                            None,
+                        )
+                    }
+                }),
+            )
+            // Function-Profile Pairs - get (fcommit only)
+            .route(
+                "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let pairs_client = pairs_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository, fcommit, powner, prepository)): Path<(String, String, String, String, String)>| {
+                        get_function_profile_pair(
+                            pairs_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            Some(fcommit),
+                            powner,
+                            prepository,
+                            None,
+                        )
+                    }
+                }),
+            )
+            // Function-Profile Pairs - get (pcommit only)
+            .route(
+                "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}/{pcommit}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let pairs_client = pairs_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository, powner, prepository, pcommit)): Path<(String, String, String, String, String)>| {
+                        get_function_profile_pair(
+                            pairs_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            None,
+                            powner,
+                            prepository,
+                            Some(pcommit),
+                        )
+                    }
+                }),
+            )
+            // Function-Profile Pairs - get (both commits)
+            .route(
+                "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}/{pcommit}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let pairs_client = pairs_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository, fcommit, powner, prepository, pcommit)): Path<(String, String, String, String, String, String)>| {
+                        get_function_profile_pair(
+                            pairs_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            Some(fcommit),
+                            powner,
+                            prepository,
+                            Some(pcommit),
+                        )
+                    }
+                }),
+            )
+            // Function-Profile Pairs - get usage (no commits)
+            .route(
+                "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}/usage",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let pairs_client = pairs_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository, powner, prepository)): Path<(String, String, String, String)>| {
+                        get_function_profile_pair_usage(
+                            pairs_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            None,
+                            powner,
+                            prepository,
+                            None,
+                        )
+                    }
+                }),
+            )
+            // Function-Profile Pairs - get usage (fcommit only)
+            .route(
+                "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}/usage",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let pairs_client = pairs_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository, fcommit, powner, prepository)): Path<(String, String, String, String, String)>| {
+                        get_function_profile_pair_usage(
+                            pairs_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            Some(fcommit),
+                            powner,
+                            prepository,
+                            None,
+                        )
+                    }
+                }),
+            )
+            // Function-Profile Pairs - get usage (pcommit only)
+            .route(
+                "/functions/{fowner}/{frepository}/profiles/{powner}/{prepository}/{pcommit}/usage",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let pairs_client = pairs_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository, powner, prepository, pcommit)): Path<(String, String, String, String, String)>| {
+                        get_function_profile_pair_usage(
+                            pairs_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            None,
+                            powner,
+                            prepository,
+                            Some(pcommit),
+                        )
+                    }
+                }),
+            )
+            // Function-Profile Pairs - get usage (both commits)
+            .route(
+                "/functions/{fowner}/{frepository}/{fcommit}/profiles/{powner}/{prepository}/{pcommit}/usage",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let pairs_client = pairs_client.clone();
+                    move |headers: HeaderMap,
+                          Path((fowner, frepository, fcommit, powner, prepository, pcommit)): Path<(String, String, String, String, String, String)>| {
+                        get_function_profile_pair_usage(
+                            pairs_client,
+                            context.context(&headers),
+                            fowner,
+                            frepository,
+                            Some(fcommit),
+                            powner,
+                            prepository,
+                            Some(pcommit),
+                        )
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for Profile Computations.
+    pub fn profile_computations(
+        self,
+        profile_computations_client: Arc<
+            impl functions::profiles::computations::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    ) -> Self {
+        let router = self
+            .router
+            // Function Profile Computations - create
+            // inline function
+            .route(
+                "/functions/profiles/compute",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let profile_computations_client =
+                        profile_computations_client.clone();
+                    move |headers: HeaderMap,
+                          Json(body): Json<
+                        objectiveai::functions::profiles::computations::request::FunctionInlineRequestBody,
+                    >| {
+                        create_profile_computation(
+                            profile_computations_client,
+                            context.context(&headers),
+                            objectiveai::functions::profiles::computations::request::Request::FunctionInline {
+                                body,
+                            },
+                        )
+                    }
+                }),
+            )
+            // Function Profile Computations - create
+            // remote function (without commit)
+            .route(
+                "/functions/{fowner}/{frepository}/profiles/compute",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let profile_computations_client =
+                        profile_computations_client.clone();
+                    move |headers: HeaderMap,
+                          Path(path): Path<
+                        objectiveai::functions::profiles::computations::request::FunctionRemoteRequestPath,
+                    >,
+                          Json(body): Json<
+                        objectiveai::functions::profiles::computations::request::FunctionRemoteRequestBody,
+                    >| {
+                        create_profile_computation(
+                            profile_computations_client,
+                            context.context(&headers),
+                            objectiveai::functions::profiles::computations::request::Request::FunctionRemote {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                }),
+            )
+            // Function Profile Computations - create
+            // remote function (with commit)
+            .route(
+                "/functions/{fowner}/{frepository}/{fcommit}/profiles/compute",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let profile_computations_client =
+                        profile_computations_client.clone();
+                    move |headers: HeaderMap,
+                          Path(path): Path<
+                        objectiveai::functions::profiles::computations::request::FunctionRemoteRequestPath,
+                    >,
+                          Json(body): Json<
+                        objectiveai::functions::profiles::computations::request::FunctionRemoteRequestBody,
+                    >| {
+                        create_profile_computation(
+                            profile_computations_client,
+                            context.context(&headers),
+                            objectiveai::functions::profiles::computations::request::Request::FunctionRemote {
+                                path,
+                                body,
+                            },
+                        )
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for background Jobs.
+    pub fn jobs(
+        self,
+        jobs_client: Arc<
+            functions::jobs::Client<
+                CTXEXT,
+                impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+                impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+                impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl vector::completions::usage_handler::UsageHandler<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl functions::function_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl functions::profile_fetcher::Fetcher<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl functions::executions::usage_handler::UsageHandler<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl functions::profiles::computations::Client<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+                impl functions::jobs::Store<CTXEXT> + Send + Sync + 'static,
+            >,
+        >,
+    ) -> Self
+    where
+        CTXEXT: Clone,
+    {
+        let router = self
+            .router
+            // Jobs - create
+            .route(
+                "/jobs",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let jobs_client = jobs_client.clone();
+                    move |headers: HeaderMap,
+                          Json(body): Json<functions::jobs::JobRequest>| {
+                        create_job(jobs_client, context.context(&headers), body)
+                    }
+                }),
+            )
+            // Jobs - get
+            .route(
+                "/jobs/{id}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let jobs_client = jobs_client.clone();
+                    move |headers: HeaderMap, Path(id): Path<String>| {
+                        get_job(jobs_client, context.context(&headers), id)
+                    }
+                }),
+            )
+            // Jobs - stream
+            .route(
+                "/jobs/{id}/stream",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let jobs_client = jobs_client.clone();
+                    move |headers: HeaderMap, Path(id): Path<String>| {
+                        stream_job(jobs_client, context.context(&headers), id)
+                    }
+                }),
+            )
+            // Jobs - cancel
+            .route(
+                "/jobs/{id}/cancel",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let jobs_client = jobs_client.clone();
+                    move |headers: HeaderMap, Path(id): Path<String>| {
+                        cancel_job(jobs_client, context.context(&headers), id)
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for API key and credit management.
+    pub fn auth(
+        self,
+        auth_client: Arc<impl auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ) -> Self {
+        let router = self
+            .router
+            // Auth - create API key
+            .route(
+                "/auth/keys",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let auth_client = auth_client.clone();
+                    move |headers: HeaderMap,
+                          Json(body): Json<
+                        objectiveai::auth::request::CreateApiKeyRequest,
+                    >| {
+                        create_api_key(auth_client, context.context(&headers), body)
+                    }
+                }),
+            )
+            // Auth - create OpenRouter BYOK API key
+            .route(
+                "/auth/keys/openrouter",
+                axum::routing::post({
+                    let context = self.context.clone();
+                    let auth_client = auth_client.clone();
+                    move |headers: HeaderMap,
+                          Json(body): Json<
+                        objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest,
+                    >| {
+                        create_openrouter_byok_api_key(auth_client, context.context(&headers), body)
+                    }
+                }),
+            )
+            // Auth - disable API key
+            .route(
+                "/auth/keys",
+                axum::routing::delete({
+                    let context = self.context.clone();
+                    let auth_client = auth_client.clone();
+                    move |headers: HeaderMap,
+                          Json(body): Json<
+                        objectiveai::auth::request::DisableApiKeyRequest,
+                    >| {
+                        disable_api_key(auth_client, context.context(&headers), body)
+                    }
+                }),
+            )
+            // Auth - delete OpenRouter BYOK API key
+            .route(
+                "/auth/keys/openrouter",
+                axum::routing::delete({
+                    let context = self.context.clone();
+                    let auth_client = auth_client.clone();
+                    move |headers: HeaderMap| {
+                        delete_openrouter_byok_api_key(auth_client, context.context(&headers))
+                    }
+                }),
+            )
+            // Auth - list API keys
+            .route(
+                "/auth/keys",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let auth_client = auth_client.clone();
+                    move |headers: HeaderMap| {
+                        list_api_keys(auth_client, context.context(&headers))
+                    }
+                }),
+            )
+            // Auth - get OpenRouter BYOK API key
+            .route(
+                "/auth/keys/openrouter",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let auth_client = auth_client.clone();
+                    move |headers: HeaderMap| {
+                        get_openrouter_byok_api_key(auth_client, context.context(&headers))
+                    }
+                }),
+            )
+            // Auth - get credits
+            .route(
+                "/auth/credits",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let auth_client = auth_client.clone();
+                    move |headers: HeaderMap| {
+                        get_credits(auth_client, context.context(&headers))
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for Ensemble retrieval.
+    pub fn ensembles(
+        self,
+        ensemble_client: Arc<
+            ensemble::Client<
+                CTXEXT,
+                impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+                impl ensemble::retrieval_client::Client<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+            >,
+        >,
+    ) -> Self {
+        let router = self
+            .router
+            // Ensemble - list
+            .route(
+                "/ensembles",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let ensemble_client = ensemble_client.clone();
+                    move |headers: HeaderMap| {
+                        list_ensembles(
+                            ensemble_client,
+                            context.context(&headers),
+                        )
+                    }
+                }),
+            )
+            // Ensemble - get
+            .route(
+                "/ensembles/{id}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let ensemble_client = ensemble_client.clone();
+                    move |headers: HeaderMap, Path(id): Path<String>| {
+                        get_ensemble(
+                            ensemble_client,
+                            context.context(&headers),
+                            id,
+                        )
+                    }
+                }),
+            )
+            // Ensemble - get usage
+            .route(
+                "/ensembles/{id}/usage",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let ensemble_client = ensemble_client.clone();
+                    move |headers: HeaderMap, Path(id): Path<String>| {
+                        get_ensemble_usage(
+                            ensemble_client,
+                            context.context(&headers),
+                            id,
+                        )
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for Ensemble LLM retrieval.
+    pub fn ensemble_llms(
+        self,
+        ensemble_llm_client: Arc<
+            ensemble_llm::Client<
+                CTXEXT,
+                impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+                impl ensemble_llm::retrieval_client::Client<CTXEXT>
+                + Send
+                + Sync
+                + 'static,
+            >,
+        >,
+    ) -> Self {
+        let router = self
+            .router
+            // Ensemble LLM - list
+            .route(
+                "/ensemble_llms",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let ensemble_llm_client = ensemble_llm_client.clone();
+                    move |headers: HeaderMap| {
+                        list_ensemble_llms(
+                            ensemble_llm_client,
+                            context.context(&headers),
+                        )
+                    }
+                }),
+            )
+            // Ensemble LLM - get
+            .route(
+                "/ensemble_llms/{id}",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let ensemble_llm_client = ensemble_llm_client.clone();
+                    move |headers: HeaderMap, Path(id): Path<String>| {
+                        get_ensemble_llm(
+                            ensemble_llm_client,
+                            context.context(&headers),
+                            id,
+                        )
+                    }
+                }),
+            )
+            // Ensemble LLM - get usage
+            .route(
+                "/ensemble_llms/{id}/usage",
+                axum::routing::get({
+                    let context = self.context.clone();
+                    let ensemble_llm_client = ensemble_llm_client.clone();
+                    move |headers: HeaderMap, Path(id): Path<String>| {
+                        get_ensemble_llm_usage(
+                            ensemble_llm_client,
+                            context.context(&headers),
+                            id,
+                        )
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+
+    /// Adds the routes for upstream health and Prometheus metrics, guarded by
+    /// `admin_api_key`. The routes are not added if no key is set.
+    pub fn admin(
+        self,
+        upstream_health: Arc<chat::completions::upstream::HealthTracker>,
+        admin_api_key: Option<String>,
+    ) -> Self {
+        let Some(admin_api_key) = admin_api_key.map(Arc::<str>::from) else {
+            return self;
+        };
+        let router = self
+            .router
+            // Admin - get upstream health
+            .route(
+                "/admin/upstreams/health",
+                axum::routing::get({
+                    let upstream_health = upstream_health.clone();
+                    let admin_api_key = admin_api_key.clone();
+                    move |headers: HeaderMap| {
+                        get_upstream_health(
+                            upstream_health,
+                            admin_api_key,
+                            headers,
+                        )
+                    }
+                }),
+            )
+            // Metrics
+            .route(
+                "/metrics",
+                axum::routing::get({
+                    let admin_api_key = admin_api_key.clone();
+                    move |headers: HeaderMap| {
+                        get_metrics(admin_api_key, headers)
+                    }
+                }),
+            );
+        Self { router, ..self }
+    }
+}
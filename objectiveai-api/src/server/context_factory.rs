@@ -0,0 +1,33 @@
+//! Creation of the request context from request headers.
+
+use crate::ctx;
+use axum::http::HeaderMap;
+use std::sync::Arc;
+
+/// Creates the [`ctx::Context`] for each request from its headers.
+///
+/// Implemented for any `Fn(&HeaderMap) -> ctx::Context<CTXEXT>`.
+pub trait ContextFactory<CTXEXT> {
+    /// Creates the context for a request with the given headers.
+    fn context(&self, headers: &HeaderMap) -> ctx::Context<CTXEXT>;
+}
+
+impl<CTXEXT, F> ContextFactory<CTXEXT> for F
+where
+    F: Fn(&HeaderMap) -> ctx::Context<CTXEXT>,
+{
+    fn context(&self, headers: &HeaderMap) -> ctx::Context<CTXEXT> {
+        self(headers)
+    }
+}
+
+/// Creates a context with a [`ctx::DefaultContextExt`] read from the
+/// request headers and a cost multiplier of one.
+pub fn default_context(
+    headers: &HeaderMap,
+) -> ctx::Context<ctx::DefaultContextExt> {
+    ctx::Context::new(
+        Arc::new(ctx::DefaultContextExt::from_headers(headers)),
+        rust_decimal::Decimal::ONE,
+    )
+}
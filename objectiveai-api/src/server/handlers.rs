@@ -0,0 +1,1287 @@
+//! Request handlers for the routes added by the server builder.
+
+use crate::{
+    auth, chat, ctx, ensemble, ensemble_llm,
+    error::ResponseErrorExt,
+    functions,
+    util::StreamOnce,
+    vector,
+};
+use axum::{
+    Json,
+    http::HeaderMap,
+    response::{IntoResponse, Sse, sse::Event},
+};
+use objectiveai::error::ResponseError;
+use std::{collections::HashMap, convert::Infallible, sync::Arc};
+use subtle::ConstantTimeEq;
+use tokio_stream::StreamExt;
+
+// Chat Completions
+
+pub(super) async fn create_chat_completion<CTXEXT>(
+    client: Arc<
+        chat::completions::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::chat::completions::request::ChatCompletionCreateParams,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    if body.stream.unwrap_or(false) {
+        match client
+            .create_streaming_for_chat_handle_usage(ctx, Arc::new(body))
+            .await
+        {
+            Ok(stream) => Sse::new(
+                stream
+                    .map(|result| {
+                        Ok::<Event, Infallible>(
+                            Event::default().data(
+                                match result {
+                                    Ok(chunk) => serde_json::to_string(&chunk),
+                                    Err(e) => serde_json::to_string(
+                                        &ResponseError::from(&e),
+                                    ),
+                                }
+                                .unwrap(),
+                            ),
+                        )
+                    })
+                    .chain(StreamOnce::new(
+                        Ok(Event::default().data("[DONE]")),
+                    )),
+            )
+            .into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    } else {
+        match client
+            .create_unary_for_chat_handle_usage(ctx, Arc::new(body))
+            .await
+        {
+            Ok(r) => Json(r).into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    }
+}
+
+// Vector Completions
+
+pub(super) async fn create_vector_completion<CTXEXT>(
+    client: Arc<
+        vector::completions::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::vector::completions::request::VectorCompletionCreateParams,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    if body.stream.unwrap_or(false) {
+        match client
+            .create_streaming_handle_usage(ctx, Arc::new(body))
+            .await
+        {
+            Ok(stream) => Sse::new(
+                stream
+                    .map(|chunk| {
+                        Ok::<Event, Infallible>(
+                            Event::default()
+                                .data(serde_json::to_string(&chunk).unwrap()),
+                        )
+                    })
+                    .chain(StreamOnce::new(
+                        Ok(Event::default().data("[DONE]")),
+                    )),
+            )
+            .into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    } else {
+        match client.create_unary_handle_usage(ctx, Arc::new(body)).await {
+            Ok(r) => Json(r).into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    }
+}
+
+// Functions
+
+pub(super) async fn list_functions<CTXEXT>(
+    client: Arc<
+        functions::Client<
+            CTXEXT,
+            impl functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::retrieval_client::Client<CTXEXT> + Send + Sync + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.list_functions(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+pub(super) async fn get_function_usage<CTXEXT>(
+    client: Arc<
+        functions::Client<
+            CTXEXT,
+            impl functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::retrieval_client::Client<CTXEXT> + Send + Sync + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    owner: String,
+    repository: String,
+    commit: Option<String>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client
+        .get_function_usage(ctx, &owner, &repository, commit.as_deref())
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+pub(super) async fn execute_function<CTXEXT>(
+    client: Arc<
+        functions::executions::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl functions::executions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    request: objectiveai::functions::executions::request::Request,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    if request.base().stream.unwrap_or(false) {
+        match client
+            .create_streaming_handle_usage(ctx, Arc::new(request))
+            .await
+        {
+            Ok(stream) => Sse::new(
+                stream
+                    .map(|chunk| {
+                        Ok::<Event, Infallible>(
+                            Event::default()
+                                .data(serde_json::to_string(&chunk).unwrap()),
+                        )
+                    })
+                    .chain(StreamOnce::new(
+                        Ok(Event::default().data("[DONE]")),
+                    )),
+            )
+            .into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    } else {
+        match client
+            .create_unary_handle_usage(ctx, Arc::new(request))
+            .await
+        {
+            Ok(r) => Json(r).into_response(),
+            Err(e) => ResponseError::from(&e).into_response(),
+        }
+    }
+}
+
+pub(super) async fn execute_function_batch<CTXEXT>(
+    client: Arc<
+        functions::executions::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl functions::executions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    params: objectiveai::functions::executions::batch::FunctionExecutionBatchCreateParams,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.create_batch_streaming_handle_usage(ctx, params) {
+        Ok(stream) => Sse::new(
+            stream
+                .map(|chunk| {
+                    Ok::<Event, Infallible>(
+                        Event::default()
+                            .data(serde_json::to_string(&chunk).unwrap()),
+                    )
+                })
+                .chain(StreamOnce::new(Ok(Event::default().data("[DONE]")))),
+        )
+        .into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+// Function Executions
+
+pub(super) async fn list_function_executions<CTXEXT>(
+    store: Arc<
+        impl functions::executions::store::Store + Send + Sync + 'static,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    params: objectiveai::functions::executions::ListFunctionExecutionsParams,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match store.list(ctx.ext.owner().as_deref(), &params).await {
+        Ok(executions) => {
+            Json(objectiveai::functions::executions::ListFunctionExecution {
+                data: executions.iter().map(Into::into).collect(),
+            })
+            .into_response()
+        }
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+pub(super) async fn get_function_execution<CTXEXT>(
+    store: Arc<
+        impl functions::executions::store::Store + Send + Sync + 'static,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match store.get(ctx.ext.owner().as_deref(), &id).await {
+        Ok(Some(execution)) => Json(execution).into_response(),
+        Ok(None) => ResponseError::from(
+            &functions::executions::store::Error::ExecutionNotFound,
+        )
+        .into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+pub(super) async fn replay_function_execution<CTXEXT>(
+    client: Arc<
+        functions::executions::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl functions::executions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    store: Arc<
+        impl functions::executions::store::Store + Send + Sync + 'static,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    params: objectiveai::functions::executions::FunctionExecutionReplayCreateParams,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    let (request, votes) = match params.execution {
+        objectiveai::functions::executions::ReplayExecution::Stored { id } => {
+            match store.get(ctx.ext.owner().as_deref(), &id).await {
+                Ok(Some(execution)) => {
+                    // reuse the persisted votes rather than fetching them
+                    let mut votes = HashMap::new();
+                    stored_votes(&execution.inner, &mut votes);
+                    let mut request = execution.request;
+                    // compile the tasks the stored votes were cast for
+                    if let Some(function) = &execution.inner.function {
+                        request.pin_function_commit(function);
+                    }
+                    request.base_mut().retry_token =
+                        execution.inner.retry_token;
+                    (request, votes)
+                }
+                Ok(None) => {
+                    return ResponseError::from(
+                        &functions::executions::store::Error::ExecutionNotFound,
+                    )
+                    .into_response();
+                }
+                Err(e) => return ResponseError::from(&e).into_response(),
+            }
+        }
+        objectiveai::functions::executions::ReplayExecution::Request(
+            request,
+        ) => (*request, HashMap::new()),
+    };
+    // reuse the retried votes, and cached votes of LLMs added by the Profile
+    let mut request = request.with_profile(params.profile);
+    let base = request.base_mut();
+    base.from_cache = Some(true);
+    base.stream = params.stream;
+    execute_function(client, ctx.with_replay_votes(votes), request).await
+}
+
+/// Collects the votes of every vector completion of a stored execution,
+/// keyed by vector completion ID. Randomly generated votes are skipped.
+fn stored_votes(
+    execution: &objectiveai::functions::executions::response::unary::FunctionExecution,
+    votes: &mut HashMap<
+        String,
+        Vec<objectiveai::vector::completions::response::Vote>,
+    >,
+) {
+    use objectiveai::functions::executions::response::unary::Task;
+    for task in &execution.tasks {
+        match task {
+            Task::FunctionExecution(task) => stored_votes(&task.inner, votes),
+            Task::VectorCompletion(task) => {
+                votes.insert(
+                    task.inner.id.clone(),
+                    task.inner
+                        .votes
+                        .iter()
+                        .filter(|vote| vote.from_rng != Some(true))
+                        .cloned()
+                        .collect(),
+                );
+            }
+        }
+    }
+}
+
+// Profiles
+
+pub(super) async fn list_profiles<CTXEXT>(
+    client: Arc<
+        functions::profiles::Client<
+            CTXEXT,
+            impl functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl functions::profiles::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.list_profiles(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+pub(super) async fn get_profile_usage<CTXEXT>(
+    client: Arc<
+        functions::profiles::Client<
+            CTXEXT,
+            impl functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl functions::profiles::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    owner: String,
+    repository: String,
+    commit: Option<String>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client
+        .get_profile_usage(ctx, &owner, &repository, commit.as_deref())
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+// Function-Profile Pairs
+
+pub(super) async fn list_function_profile_pairs<CTXEXT>(
+    client: Arc<
+        impl functions::pair_retrieval_client::Client<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.list_function_profile_pairs(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn get_function_profile_pair<CTXEXT>(
+    client: Arc<
+        impl functions::pair_retrieval_client::Client<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    fowner: String,
+    frepository: String,
+    fcommit: Option<String>,
+    powner: String,
+    prepository: String,
+    pcommit: Option<String>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client
+        .get_function_profile_pair(
+            ctx,
+            &fowner,
+            &frepository,
+            fcommit.as_deref(),
+            &powner,
+            &prepository,
+            pcommit.as_deref(),
+        )
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn get_function_profile_pair_usage<CTXEXT>(
+    client: Arc<
+        impl functions::pair_retrieval_client::Client<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    fowner: String,
+    frepository: String,
+    fcommit: Option<String>,
+    powner: String,
+    prepository: String,
+    pcommit: Option<String>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client
+        .get_function_profile_pair_usage(
+            ctx,
+            &fowner,
+            &frepository,
+            fcommit.as_deref(),
+            &powner,
+            &prepository,
+            pcommit.as_deref(),
+        )
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Vector Completions Cache
+
+pub(super) async fn get_vector_completion_votes<CTXEXT>(
+    client: Arc<
+        vector::completions::cache::Client<
+            CTXEXT,
+            impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.fetch_completion_votes(ctx, &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn get_vector_cache_vote<CTXEXT>(
+    client: Arc<
+        vector::completions::cache::Client<
+            CTXEXT,
+            impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::vector::completions::cache::request::CacheVoteRequestOwned,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client
+        .fetch_cache_vote(
+            ctx,
+            &body.model,
+            body.models.as_deref(),
+            &body.messages,
+            body.tools.as_deref(),
+            &body.responses,
+        )
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Functions - get
+
+pub(super) async fn get_function<CTXEXT>(
+    client: Arc<
+        functions::Client<
+            CTXEXT,
+            impl functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::retrieval_client::Client<CTXEXT> + Send + Sync + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    owner: String,
+    repository: String,
+    commit: Option<String>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client
+        .get_function(ctx, &owner, &repository, commit.as_deref())
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Profiles - get
+
+pub(super) async fn get_profile<CTXEXT>(
+    client: Arc<
+        functions::profiles::Client<
+            CTXEXT,
+            impl functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl functions::profiles::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    owner: String,
+    repository: String,
+    commit: Option<String>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client
+        .get_profile(ctx, &owner, &repository, commit.as_deref())
+        .await
+    {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Profile Computations
+
+pub(super) async fn create_profile_computation<CTXEXT>(
+    client: Arc<
+        impl functions::profiles::computations::Client<CTXEXT>
+        + Send
+        + Sync
+        + 'static,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    request: objectiveai::functions::profiles::computations::request::Request,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    if request.base().stream.unwrap_or(false) {
+        match client.create_streaming(ctx, Arc::new(request)).await {
+            Ok(stream) => Sse::new(
+                stream
+                    .map(|result| {
+                        Ok::<Event, Infallible>(
+                            Event::default().data(
+                                match result {
+                                    Ok(chunk) => serde_json::to_string(&chunk),
+                                    Err(e) => serde_json::to_string(&e),
+                                }
+                                .unwrap(),
+                            ),
+                        )
+                    })
+                    .chain(StreamOnce::new(
+                        Ok(Event::default().data("[DONE]")),
+                    )),
+            )
+            .into_response(),
+            Err(e) => e.into_response(),
+        }
+    } else {
+        match client.create_unary(ctx, Arc::new(request)).await {
+            Ok(r) => Json(r).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+// Jobs
+
+pub(super) async fn create_job<CTXEXT>(
+    client: Arc<
+        functions::jobs::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl functions::executions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::profiles::computations::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::jobs::Store<CTXEXT> + Send + Sync + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    request: functions::jobs::JobRequest,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Clone + Send + Sync + 'static,
+{
+    Json(client.create(ctx, request)).into_response()
+}
+
+pub(super) async fn get_job<CTXEXT>(
+    client: Arc<
+        functions::jobs::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl functions::executions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::profiles::computations::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::jobs::Store<CTXEXT> + Send + Sync + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Clone + Send + Sync + 'static,
+{
+    match client.get(ctx.ext.owner().as_deref(), &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+pub(super) async fn stream_job<CTXEXT>(
+    client: Arc<
+        functions::jobs::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl functions::executions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::profiles::computations::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::jobs::Store<CTXEXT> + Send + Sync + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Clone + Send + Sync + 'static,
+{
+    match client.stream(ctx.ext.owner().as_deref(), &id).await {
+        Ok(stream) => Sse::new(
+            stream
+                .map(|chunk| {
+                    Ok::<Event, Infallible>(
+                        Event::default()
+                            .data(serde_json::to_string(&chunk).unwrap()),
+                    )
+                })
+                .chain(StreamOnce::new(Ok(Event::default().data("[DONE]")))),
+        )
+        .into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+pub(super) async fn cancel_job<CTXEXT>(
+    client: Arc<
+        functions::jobs::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl chat::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl vector::completions::completion_votes_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::cache_vote_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl vector::completions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::function_fetcher::Fetcher<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::profile_fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl functions::executions::usage_handler::UsageHandler<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::profiles::computations::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+            impl functions::jobs::Store<CTXEXT> + Send + Sync + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Clone + Send + Sync + 'static,
+{
+    match client.cancel(ctx.ext.owner().as_deref(), &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => ResponseError::from(&e).into_response(),
+    }
+}
+
+// Auth
+
+pub(super) async fn create_api_key<CTXEXT>(
+    client: Arc<impl auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::auth::request::CreateApiKeyRequest,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.create_api_key(ctx, body).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn create_openrouter_byok_api_key<CTXEXT>(
+    client: Arc<impl auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::auth::request::CreateOpenRouterByokApiKeyRequest,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.create_openrouter_byok_api_key(ctx, body).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn disable_api_key<CTXEXT>(
+    client: Arc<impl auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+    body: objectiveai::auth::request::DisableApiKeyRequest,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.disable_api_key(ctx, body).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn delete_openrouter_byok_api_key<CTXEXT>(
+    client: Arc<impl auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.delete_openrouter_byok_api_key(ctx).await {
+        Ok(()) => axum::http::StatusCode::OK.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn list_api_keys<CTXEXT>(
+    client: Arc<impl auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.list_api_keys(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn get_openrouter_byok_api_key<CTXEXT>(
+    client: Arc<impl auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.get_openrouter_byok_api_key(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn get_credits<CTXEXT>(
+    client: Arc<impl auth::Client<CTXEXT> + Send + Sync + 'static>,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.get_credits(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Ensemble
+
+pub(super) async fn list_ensembles<CTXEXT>(
+    client: Arc<
+        ensemble::Client<
+            CTXEXT,
+            impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ensemble::retrieval_client::Client<CTXEXT> + Send + Sync + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.list(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn get_ensemble<CTXEXT>(
+    client: Arc<
+        ensemble::Client<
+            CTXEXT,
+            impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ensemble::retrieval_client::Client<CTXEXT> + Send + Sync + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.get(ctx, &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn get_ensemble_usage<CTXEXT>(
+    client: Arc<
+        ensemble::Client<
+            CTXEXT,
+            impl ensemble::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ensemble::retrieval_client::Client<CTXEXT> + Send + Sync + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.get_usage(ctx, &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Ensemble LLM
+
+pub(super) async fn list_ensemble_llms<CTXEXT>(
+    client: Arc<
+        ensemble_llm::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ensemble_llm::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.list(ctx).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn get_ensemble_llm<CTXEXT>(
+    client: Arc<
+        ensemble_llm::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ensemble_llm::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.get(ctx, &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub(super) async fn get_ensemble_llm_usage<CTXEXT>(
+    client: Arc<
+        ensemble_llm::Client<
+            CTXEXT,
+            impl ensemble_llm::fetcher::Fetcher<CTXEXT> + Send + Sync + 'static,
+            impl ensemble_llm::retrieval_client::Client<CTXEXT>
+            + Send
+            + Sync
+            + 'static,
+        >,
+    >,
+    ctx: ctx::Context<CTXEXT>,
+    id: String,
+) -> axum::response::Response
+where
+    CTXEXT: ctx::ContextExt + Send + Sync + 'static,
+{
+    match client.get_usage(ctx, &id).await {
+        Ok(r) => Json(r).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Admin
+
+pub(super) fn authorize_admin(
+    admin_api_key: &str,
+    headers: &HeaderMap,
+) -> Result<(), ResponseError> {
+    // compare in constant time, so that timing does not reveal the key
+    let authorized = headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|value| {
+            value.as_bytes().ct_eq(admin_api_key.as_bytes()).into()
+        });
+    if authorized {
+        Ok(())
+    } else {
+        Err(ResponseError {
+            code: 401,
+            message: serde_json::json!({
+                "kind": "unauthorized",
+                "error": "invalid admin API key",
+            }),
+        })
+    }
+}
+
+pub(super) async fn get_upstream_health(
+    health: Arc<chat::completions::upstream::HealthTracker>,
+    admin_api_key: Arc<str>,
+    headers: HeaderMap,
+) -> axum::response::Response {
+    match authorize_admin(&admin_api_key, &headers) {
+        Ok(()) => Json(health.snapshot()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Metrics
+
+pub(super) async fn record_http_metrics(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let route = request
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_default();
+    let method = request.method().to_string();
+    let started = std::time::Instant::now();
+    let response = next.run(request).await;
+    let metrics = &crate::metrics::METRICS;
+    metrics
+        .http_request_duration
+        .with_label_values(&[&route, &method])
+        .observe(started.elapsed().as_secs_f64());
+    metrics
+        .http_requests
+        .with_label_values(&[&route, &method, response.status().as_str()])
+        .inc();
+    response
+}
+
+pub(super) async fn get_metrics(
+    admin_api_key: Arc<str>,
+    headers: HeaderMap,
+) -> axum::response::Response {
+    match authorize_admin(&admin_api_key, &headers) {
+        Ok(()) => (
+            [(axum::http::header::CONTENT_TYPE, prometheus::TEXT_FORMAT)],
+            crate::metrics::METRICS.render(),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
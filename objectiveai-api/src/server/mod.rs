@@ -0,0 +1,13 @@
+//! Library-level HTTP server for the ObjectiveAI API.
+//!
+//! [`Builder`] assembles an [`axum::Router`] from the clients of each
+//! module, so that the API can be served with custom fetchers, usage
+//! handlers, auth clients and context extensions, or mounted alongside
+//! other routes.
+
+mod builder;
+mod context_factory;
+mod handlers;
+
+pub use builder::*;
+pub use context_factory::*;
@@ -65,3 +65,55 @@ where
         std::task::Poll::Ready(self.as_mut().get_mut().0.take())
     }
 }
+
+/// Yields items from `stream` until the receiver of `tx` is dropped.
+///
+/// Tasks which forward a stream over a channel use this so that, once the
+/// client disconnects, the stream is dropped along with any upstream
+/// requests it was driving.
+pub fn until_closed<S, T>(
+    stream: S,
+    tx: &tokio::sync::mpsc::UnboundedSender<T>,
+) -> impl Stream<Item = S::Item> + use<S, T>
+where
+    S: Stream,
+    T: Send + 'static,
+{
+    let tx = tx.clone();
+    futures::StreamExt::take_until(stream, async move { tx.closed().await })
+}
+
+/// A stream that enters a tracing span each time it is polled.
+///
+/// Spans created while the inner stream is polled, including those of
+/// lazily started child streams, become children of `span`.
+pub struct InstrumentedStream<S> {
+    inner: std::pin::Pin<Box<S>>,
+    span: tracing::Span,
+}
+
+impl<S> InstrumentedStream<S> {
+    /// Creates a new stream which polls `inner` within `span`.
+    pub fn new(inner: S, span: tracing::Span) -> Self {
+        Self {
+            inner: Box::pin(inner),
+            span,
+        }
+    }
+}
+
+impl<S> Stream for InstrumentedStream<S>
+where
+    S: Stream,
+{
+    type Item = S::Item;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = self.as_mut().get_mut();
+        let _enter = this.span.enter();
+        this.inner.as_mut().poll_next(cx)
+    }
+}
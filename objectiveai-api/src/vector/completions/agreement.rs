@@ -0,0 +1,173 @@
+//! Inter-LLM agreement statistics for vector completions.
+//!
+//! Measures whether the ensemble's LLMs actually agree, so profile authors can
+//! tell which ensemble members contribute signal and which contribute noise.
+
+use rust_decimal::{
+    Decimal,
+    prelude::{FromPrimitive, ToPrimitive},
+};
+
+/// Computes agreement statistics from the votes and final scores of a vector
+/// completion.
+///
+/// Votes drawn from the RNG are not opinions of any LLM, so they are left
+/// out. Every other vote counts with its profile weight, so pairwise
+/// statistics weight each pair of votes by the product of their weights.
+pub fn agreement(
+    votes: &[objectiveai::vector::completions::response::Vote],
+    scores: &[Decimal],
+) -> objectiveai::vector::completions::response::Agreement {
+    let scores = normalize(scores);
+    let votes = votes
+        .iter()
+        .filter(|vote| vote.from_rng != Some(true))
+        .collect::<Vec<_>>();
+    let distributions = votes
+        .iter()
+        .map(|vote| normalize(&vote.vote))
+        .collect::<Vec<_>>();
+    let weights = votes
+        .iter()
+        .map(|vote| vote.weight.to_f64().unwrap_or(0.0).max(0.0))
+        .collect::<Vec<_>>();
+
+    // pairwise statistics, absent unless two votes carry weight
+    let (fleiss_kappa, mean_pairwise_jensen_shannon_divergence) =
+        if weights.iter().filter(|w| **w > 0.0).count() >= 2 {
+            (
+                Some(to_decimal(fleiss_kappa(
+                    &distributions,
+                    &weights,
+                    scores.len(),
+                ))),
+                Some(to_decimal(mean_pairwise_jensen_shannon_divergence(
+                    &distributions,
+                    &weights,
+                ))),
+            )
+        } else {
+            (None, None)
+        };
+
+    // entropy of the consensus
+    let normalized_score_entropy = if scores.len() > 1 {
+        to_decimal(entropy(&scores) / (scores.len() as f64).log2())
+    } else {
+        Decimal::ZERO
+    };
+
+    // agreement of each LLM with the consensus
+    let llms = votes
+        .iter()
+        .zip(distributions.iter())
+        .map(|(vote, distribution)| {
+            objectiveai::vector::completions::response::LlmAgreement {
+                model: vote.model.clone(),
+                ensemble_index: vote.ensemble_index,
+                flat_ensemble_index: vote.flat_ensemble_index,
+                agreement: to_decimal(
+                    1.0 - jensen_shannon_divergence(distribution, &scores),
+                ),
+            }
+        })
+        .collect();
+
+    objectiveai::vector::completions::response::Agreement {
+        fleiss_kappa,
+        mean_pairwise_jensen_shannon_divergence,
+        normalized_score_entropy,
+        llms,
+    }
+}
+
+/// Free-marginal Fleiss' kappa for a single subject rated by every vote.
+///
+/// Soft votes contribute the probability that two raters pick the same
+/// response, which reduces to the usual count-based formula for discrete votes.
+/// Each pair of raters counts with the product of their weights, so equal
+/// weights give the unweighted statistic.
+fn fleiss_kappa(
+    distributions: &[Vec<f64>],
+    weights: &[f64],
+    responses_len: usize,
+) -> f64 {
+    let mut totals = vec![0.0; responses_len];
+    let mut total_weight = 0.0;
+    let mut self_weight = 0.0;
+    let mut self_agreement = 0.0;
+    for (distribution, w) in distributions.iter().zip(weights.iter()) {
+        total_weight += w;
+        self_weight += w * w;
+        for (i, p) in distribution.iter().enumerate() {
+            totals[i] += w * p;
+            self_agreement += w * w * p * p;
+        }
+    }
+    let observed = (totals.iter().map(|t| t * t).sum::<f64>() - self_agreement)
+        / (total_weight * total_weight - self_weight);
+    let chance = 1.0 / responses_len as f64;
+    (observed - chance) / (1.0 - chance)
+}
+
+/// Mean Jensen-Shannon divergence over every pair of distributions, each
+/// pair weighted by the product of their weights.
+fn mean_pairwise_jensen_shannon_divergence(
+    distributions: &[Vec<f64>],
+    weights: &[f64],
+) -> f64 {
+    let mut sum = 0.0;
+    let mut total_weight = 0.0;
+    for (i, (a, wa)) in distributions.iter().zip(weights.iter()).enumerate() {
+        for (b, wb) in distributions[i + 1..].iter().zip(&weights[i + 1..]) {
+            sum += wa * wb * jensen_shannon_divergence(a, b);
+            total_weight += wa * wb;
+        }
+    }
+    sum / total_weight
+}
+
+/// Jensen-Shannon divergence (base 2) between two distributions, in [0, 1].
+fn jensen_shannon_divergence(a: &[f64], b: &[f64]) -> f64 {
+    let mut divergence = 0.0;
+    for (p, q) in a.iter().zip(b.iter()) {
+        let m = (p + q) / 2.0;
+        if *p > 0.0 {
+            divergence += p * (p / m).log2() / 2.0;
+        }
+        if *q > 0.0 {
+            divergence += q * (q / m).log2() / 2.0;
+        }
+    }
+    divergence
+}
+
+/// Shannon entropy (base 2) of a distribution.
+fn entropy(distribution: &[f64]) -> f64 {
+    distribution
+        .iter()
+        .filter(|p| **p > 0.0)
+        .map(|p| -p * p.log2())
+        .sum()
+}
+
+/// Converts decimals into a distribution summing to 1, or uniform if empty.
+fn normalize(values: &[Decimal]) -> Vec<f64> {
+    let values = values
+        .iter()
+        .map(|v| v.to_f64().unwrap_or(0.0).max(0.0))
+        .collect::<Vec<_>>();
+    let sum: f64 = values.iter().sum();
+    if sum > 0.0 {
+        values.into_iter().map(|v| v / sum).collect()
+    } else {
+        vec![1.0 / values.len() as f64; values.len()]
+    }
+}
+
+/// Converts a statistic into a decimal.
+fn to_decimal(value: f64) -> Decimal {
+    Decimal::from_f64(value)
+        .unwrap_or(Decimal::ZERO)
+        .round_dp(6)
+}
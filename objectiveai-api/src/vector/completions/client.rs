@@ -2,12 +2,13 @@
 
 use crate::{
     chat, ctx,
-    util::{ChoiceIndexer, StreamOnce},
+    util::{ChoiceIndexer, StreamOnce, until_closed},
 };
 use futures::{FutureExt, Stream, StreamExt, TryStreamExt};
 use rand::Rng;
 use rust_decimal::Decimal;
 use std::{collections::HashMap, sync::Arc, time};
+use tracing::Instrument;
 
 /// Generates a unique response ID for a vector completion.
 pub fn response_id(created: u64) -> String {
@@ -31,6 +32,8 @@ pub struct Client<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG> {
     pub cache_vote_fetcher: Arc<FCVOTE>,
     /// Handler for usage tracking.
     pub usage_handler: Arc<VUSG>,
+    /// Upstream vote requests currently in flight, shared across requests.
+    pub vote_flights: Arc<super::VoteFlights>,
 }
 
 impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG>
@@ -52,6 +55,7 @@ impl<CTXEXT, FENSLLM, CUSG, FENS, FVVOTE, FCVOTE, VUSG>
             completion_votes_fetcher,
             cache_vote_fetcher,
             usage_handler,
+            vote_flights: Arc::new(super::VoteFlights::new()),
         }
     }
 }
@@ -106,6 +110,8 @@ where
     /// Creates a streaming vector completion with usage tracking.
     ///
     /// Spawns a background task to track usage after the stream completes.
+    /// Dropping the returned stream cancels the remaining upstream requests,
+    /// and usage of the completions which already finished is still tracked.
     pub async fn create_streaming_handle_usage(
         self: Arc<Self>,
         ctx: ctx::Context<CTXEXT>,
@@ -117,11 +123,27 @@ where
         + 'static,
         super::Error,
     >{
+        let span = tracing::info_span!(
+            "vector_completion",
+            responses = request.responses.len(),
+            id = tracing::field::Empty,
+            ensemble = tracing::field::Empty,
+            prompt_tokens = tracing::field::Empty,
+            completion_tokens = tracing::field::Empty,
+            cost = tracing::field::Empty,
+            error = tracing::field::Empty,
+            cancelled = tracing::field::Empty,
+        );
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
         tokio::spawn(async move {
+            let in_flight = crate::metrics::METRICS.in_flight_stream("vector");
             let mut aggregate: Option<
                 objectiveai::vector::completions::response::streaming::VectorCompletionChunk,
             > = None;
+            // usage of the completions which finished, for when the client
+            // disconnects before the final chunk carrying the total
+            let mut usage =
+                objectiveai::vector::completions::response::Usage::default();
             let stream = match self
                 .clone()
                 .create_streaming(ctx.clone(), request.clone())
@@ -129,12 +151,20 @@ where
             {
                 Ok(stream) => stream,
                 Err(e) => {
+                    tracing::Span::current()
+                        .record("error", tracing::field::display(&e));
                     let _ = tx.send(Err(e));
                     return;
                 }
             };
+            let stream = until_closed(stream, &tx);
             futures::pin_mut!(stream);
             while let Some(chunk) = stream.next().await {
+                for completion in &chunk.completions {
+                    if let Some(completion_usage) = &completion.inner.usage {
+                        usage.push_chat_completion_usage(completion_usage);
+                    }
+                }
                 match &mut aggregate {
                     Some(aggregate) => aggregate.push(&chunk),
                     None => aggregate = Some(chunk.clone()),
@@ -142,9 +172,26 @@ where
                 let _ = tx.send(Ok(chunk));
             }
             drop(stream);
+            if tx.is_closed() {
+                tracing::Span::current().record("cancelled", true);
+            }
             drop(tx);
+            drop(in_flight);
+            // a disconnected client may leave no chunks
+            let Some(mut aggregate) = aggregate else {
+                return;
+            };
+            if aggregate.usage.is_none() {
+                aggregate.usage = Some(usage);
+            }
             let response: objectiveai::vector::completions::response::unary::VectorCompletion =
-                aggregate.unwrap().into();
+                aggregate.into();
+            let span = tracing::Span::current();
+            span.record("id", response.id.as_str());
+            span.record("ensemble", response.ensemble.as_str());
+            span.record("prompt_tokens", response.usage.prompt_tokens);
+            span.record("completion_tokens", response.usage.completion_tokens);
+            span.record("cost", tracing::field::display(response.usage.cost));
             let all_retry_or_cached_or_rng = request
                 .retry
                 .as_deref()
@@ -157,7 +204,7 @@ where
                     .handle_usage(ctx, request, response)
                     .await;
             }
-        });
+        }.instrument(span));
         let mut stream =
             tokio_stream::wrappers::UnboundedReceiverStream::new(rx);
         match stream.next().await {
@@ -219,6 +266,13 @@ where
             ));
         }
 
+        // validate uncertainty options
+        if let Some(uncertainty) = &request.uncertainty {
+            uncertainty
+                .validate()
+                .map_err(super::Error::InvalidUncertainty)?;
+        }
+
         // validate credits + fetch ensemble if needed + fetch retry votes if needed
         let (ensemble, mut static_votes) = match (
             &request.ensemble,
@@ -240,15 +294,7 @@ where
                             }
                         }
                     ),
-                    self.completion_votes_fetcher
-                        .fetch(ctx.clone(), retry)
-                        .map(|result| {
-                            match result {
-                                Ok(Some(votes)) => Ok(votes),
-                                Ok(None) => Err(super::Error::RetryNotFound),
-                                Err(e) => Err(super::Error::FetchRetry(e)),
-                            }
-                        }),
+                    self.fetch_retry_votes(&ctx, retry),
                 )?;
                 votes.iter_mut().for_each(|vote| {
                     vote.retry = Some(true);
@@ -268,15 +314,7 @@ where
                     .clone()
                     .try_into()
                     .map_err(super::Error::InvalidEnsemble)?;
-                let mut votes = self
-                    .completion_votes_fetcher
-                    .fetch(ctx.clone(), retry)
-                    .map(|result| match result {
-                        Ok(Some(votes)) => Ok(votes),
-                        Ok(None) => Err(super::Error::RetryNotFound),
-                        Err(e) => Err(super::Error::FetchRetry(e)),
-                    })
-                    .await?;
+                let mut votes = self.fetch_retry_votes(&ctx, retry).await?;
                 votes.iter_mut().for_each(|vote| {
                     vote.retry = Some(true);
                     vote.from_cache = Some(true);
@@ -343,6 +381,14 @@ where
             ));
         }
 
+        // when replaying, re-assign retried votes to the LLMs of this ensemble
+        // which produced them, weighted by this profile, so that a replay
+        // under a different Profile reuses every vote whose LLM is still
+        // present
+        if ctx.replay_votes.is_some() && !static_votes.is_empty() {
+            reassign_votes(&mut static_votes, &ensemble, &request.profile);
+        }
+
         // compute hash IDs
         let prompt_id = {
             let mut prompt = request.messages.clone();
@@ -367,6 +413,17 @@ where
             responses_ids
         };
 
+        // first flat ensemble index of each LLM, to number repeated copies
+        let flat_offsets = ensemble
+            .llms
+            .iter()
+            .scan(0, |offset, llm| {
+                let flat_offset = *offset;
+                *offset += llm.count as usize;
+                Some(flat_offset)
+            })
+            .collect::<Vec<_>>();
+
         // create a vector of LLMs with useful info
         // only ones that may stream
         let mut llms = ensemble
@@ -438,6 +495,9 @@ where
                         &request.responses,
                     ).await {
                         Ok(Some(mut vote)) => {
+                            crate::metrics::METRICS
+                                .record_cache_lookup("vote", true);
+
                             // update fields
                             vote.ensemble_index = *ensemble_index as u64;
                             vote.flat_ensemble_index = *flat_ensemble_index as u64;
@@ -469,7 +529,11 @@ where
                             // return vote
                             Ok(Some(vote))
                         }
-                        Ok(None) => Ok(None),
+                        Ok(None) => {
+                            crate::metrics::METRICS
+                                .record_cache_lookup("vote", false);
+                            Ok(None)
+                        }
                         Err(e) => Err(super::Error::FetchCacheVote(e))
                     }
                 });
@@ -564,6 +628,7 @@ where
                         llm,
                         ensemble_index,
                         flat_ensemble_index,
+                        flat_ensemble_index - flat_offsets[ensemble_index],
                         weight,
                         request.clone(),
                         prompt_id.clone(),
@@ -591,6 +656,18 @@ where
                         *score = weights[i] / weight_sum;
                     }
                 }
+                // bootstrap uncertainty if requested
+                let uncertainty = request.uncertainty.as_ref().map(|params| {
+                    super::uncertainty::bootstrap(
+                        params,
+                        request.seed,
+                        &static_votes,
+                        &scores,
+                    )
+                });
+                // compute agreement statistics
+                let agreement =
+                    super::agreement::agreement(&static_votes, &scores);
                 // return stream of existing votes
                 return Ok(futures::future::Either::Left(StreamOnce::new(
                     objectiveai::vector::completions::response::streaming::VectorCompletionChunk {
@@ -603,6 +680,8 @@ where
                         ensemble: ensemble.id,
                         object: objectiveai::vector::completions::response::streaming::Object::VectorCompletionChunk,
                         usage: None,
+                        uncertainty,
+                        agreement: Some(agreement),
                     }
                 )));
             } else {
@@ -619,6 +698,9 @@ where
             }
         };
 
+        // all votes so far
+        let mut all_votes = Vec::new();
+
         Ok(futures::future::Either::Right(async_stream::stream! {
             // stream all chunks
             while let Some(mut chunk) = next_chunk.take() {
@@ -664,9 +746,23 @@ where
                 chunk.weights = weights.clone();
                 chunk.scores = scores.clone();
 
-                // if on last chunk, add usage
+                // keep votes for uncertainty and agreement
+                all_votes.extend_from_slice(&chunk.votes);
+
+                // if on last chunk, add usage, uncertainty, and agreement
                 if next_chunk.is_none() {
                     chunk.usage = Some(usage.clone());
+                    chunk.uncertainty =
+                        request.uncertainty.as_ref().map(|params| {
+                            super::uncertainty::bootstrap(
+                                params,
+                                request.seed,
+                                &all_votes,
+                                &scores,
+                            )
+                        });
+                    chunk.agreement =
+                        Some(super::agreement::agreement(&all_votes, &scores));
                 }
 
                 yield chunk;
@@ -676,9 +772,155 @@ where
 
     /// Creates a streaming completion for a single LLM in the ensemble.
     ///
+    /// If an identical upstream vote request is already in flight anywhere
+    /// on the server, waits for it and yields a copy of its result instead
+    /// of calling the upstream again.
+    async fn llm_create_streaming(
+        self: Arc<Self>,
+        ctx: ctx::Context<CTXEXT>,
+        id: String,
+        created: u64,
+        ensemble: String,
+        indexer: Arc<ChoiceIndexer>,
+        llm: objectiveai::ensemble_llm::EnsembleLlmWithFallbacksAndCount,
+        ensemble_index: usize,
+        flat_ensemble_index: usize,
+        ordinal: usize,
+        weight: Decimal,
+        request: Arc<objectiveai::vector::completions::request::VectorCompletionCreateParams>,
+        prompt_id: String,
+        tools_id: Option<String>,
+        responses_ids: Vec<String>,
+    ) -> impl Stream<Item = objectiveai::vector::completions::response::streaming::VectorCompletionChunk> + Send + 'static
+    {
+        // identify the credentials the upstream call is billed to
+        let mut byok_ids = Some(Vec::with_capacity(
+            chat::completions::upstream::Upstream::ALL.len(),
+        ));
+        for upstream in chat::completions::upstream::Upstream::ALL {
+            match ctx.ext.get_byok(upstream).await {
+                Ok(byok) => {
+                    if let Some(byok_ids) = &mut byok_ids {
+                        byok_ids.push(byok.map(|byok| {
+                            ring::digest::digest(
+                                &ring::digest::SHA256,
+                                byok.as_bytes(),
+                            )
+                            .as_ref()
+                            .iter()
+                            .map(|byte| format!("{byte:02x}"))
+                            .collect()
+                        }));
+                    }
+                }
+                // the upstream call reports the error, do not share it
+                Err(_) => byok_ids = None,
+            }
+        }
+        let key = byok_ids.map(|byok_ids| super::VoteFlightKey {
+            ensemble_llm_id: llm.inner.id.clone(),
+            ordinal,
+            prompt_id: prompt_id.clone(),
+            tools_id: tools_id.clone(),
+            responses_ids: responses_ids.clone(),
+            seed: request.seed,
+            provider: request
+                .provider
+                .as_ref()
+                .map(|provider| serde_json::to_string(provider).unwrap()),
+            byok_ids,
+        });
+
+        // join an in-flight request, or become the one making it
+        let guard = match key {
+            Some(key) => loop {
+                // Clone the shared future while holding the lock, then release the lock before awaiting.
+                let shared = match self.vote_flights.entry(key.clone()) {
+                    dashmap::mapref::entry::Entry::Occupied(entry) => {
+                        entry.get().clone()
+                    }
+                    dashmap::mapref::entry::Entry::Vacant(entry) => {
+                        let (tx, rx) = tokio::sync::oneshot::channel();
+                        entry.insert(rx.shared());
+                        break Some(super::VoteFlightGuard::new(
+                            self.vote_flights.clone(),
+                            key,
+                            tx,
+                        ));
+                    }
+                };
+                if let Ok(flight) = shared.await {
+                    return futures::future::Either::Left(StreamOnce::new(
+                        flight.into_chunk(super::VoteFlightFollower {
+                            id,
+                            created,
+                            ensemble,
+                            completion_index: indexer.get(flat_ensemble_index),
+                            ensemble_index,
+                            flat_ensemble_index,
+                            weight,
+                        }),
+                    ));
+                }
+                // the request making it went away before finishing, try again
+            },
+            None => None,
+        };
+
+        let stream = self
+            .llm_create_streaming_upstream(
+                ctx,
+                id,
+                created,
+                ensemble,
+                indexer,
+                llm,
+                ensemble_index,
+                flat_ensemble_index,
+                weight,
+                request,
+                prompt_id,
+                tools_id,
+                responses_ids,
+            )
+            .await;
+
+        // share the aggregate with waiting requests once the stream ends
+        futures::future::Either::Right(async_stream::stream! {
+            futures::pin_mut!(stream);
+            let mut aggregate: Option<
+                objectiveai::vector::completions::response::streaming::VectorCompletionChunk,
+            > = None;
+            while let Some(chunk) = stream.next().await {
+                match aggregate {
+                    Some(ref mut aggregate) => {
+                        aggregate.push(&chunk);
+                    }
+                    None => {
+                        aggregate = Some(chunk.clone());
+                    }
+                }
+                yield chunk;
+            }
+            if let (Some(guard), Some(aggregate)) = (guard, aggregate) {
+                guard.finish(super::VoteFlight {
+                    completion: aggregate
+                        .completions
+                        .into_iter()
+                        .next()
+                        .unwrap_or_default(),
+                    votes: aggregate.votes,
+                });
+            }
+        })
+    }
+
+    /// Creates a streaming completion for a single LLM in the ensemble
+    /// by calling the upstream.
+    ///
     /// Generates prefix data for vote extraction, streams the chat completion,
     /// and extracts votes from the LLM's response.
-    async fn llm_create_streaming(
+    async fn llm_create_streaming_upstream(
         self: Arc<Self>,
         ctx: ctx::Context<CTXEXT>,
         id: String,
@@ -834,6 +1076,8 @@ where
                     ensemble: ensemble.clone(),
                     object: objectiveai::vector::completions::response::streaming::Object::VectorCompletionChunk,
                     usage: None,
+                    uncertainty: None,
+                    agreement: None,
                 };
 
                 // push the chunk into the aggregate
@@ -880,6 +1124,14 @@ where
                                     from_rng: None,
                                     completion_index: Some(completion.index),
                                 });
+                            } else {
+                                let metrics = &crate::metrics::METRICS;
+                                metrics
+                                    .vote_parse_failures
+                                    .with_label_values(&[metrics.model_label(
+                                        &completion.inner.model,
+                                    )])
+                                    .inc();
                             }
                         }
                     }
@@ -920,7 +1172,173 @@ where
                 ensemble,
                 object: objectiveai::vector::completions::response::streaming::Object::VectorCompletionChunk,
                 usage: None,
+                uncertainty: None,
+                agreement: None,
             }
         )
     }
+
+    /// Fetches the votes of a vector completion being retried.
+    ///
+    /// When replaying, votes persisted with the replayed execution are used
+    /// instead of being fetched.
+    async fn fetch_retry_votes(
+        &self,
+        ctx: &ctx::Context<CTXEXT>,
+        retry: &str,
+    ) -> Result<
+        Vec<objectiveai::vector::completions::response::Vote>,
+        super::Error,
+    > {
+        if let Some(votes) = ctx
+            .replay_votes
+            .as_ref()
+            .and_then(|replay_votes| replay_votes.get(retry))
+        {
+            return Ok(votes.clone());
+        }
+        match self
+            .completion_votes_fetcher
+            .fetch(ctx.clone(), retry)
+            .await
+        {
+            Ok(Some(votes)) => Ok(votes),
+            Ok(None) => Err(super::Error::RetryNotFound),
+            Err(e) => Err(super::Error::FetchRetry(e)),
+        }
+    }
+}
+
+/// Re-assigns replayed votes to the LLMs of `ensemble` which produced them,
+/// weighted by `profile`.
+///
+/// Each LLM copy is assigned the votes of at most one original copy. Votes
+/// whose LLM is absent from the ensemble or has no weight are dropped.
+fn reassign_votes(
+    votes: &mut Vec<objectiveai::vector::completions::response::Vote>,
+    ensemble: &objectiveai::ensemble::Ensemble,
+    profile: &[Decimal],
+) {
+    let flat_llms = ensemble
+        .llms
+        .iter()
+        .enumerate()
+        .flat_map(|(ensemble_index, llm)| {
+            std::iter::repeat_n((ensemble_index, llm), llm.count as usize)
+        })
+        .collect::<Vec<_>>();
+    let mut assigned = vec![false; flat_llms.len()];
+    let mut reassigned: HashMap<u64, Option<usize>> = HashMap::new();
+    votes.retain_mut(|vote| {
+        let flat_ensemble_index = *reassigned
+            .entry(vote.flat_ensemble_index)
+            .or_insert_with(|| {
+                let flat_ensemble_index = (0..flat_llms.len()).find(|&i| {
+                    let (ensemble_index, llm) = flat_llms[i];
+                    !assigned[i]
+                        && profile[ensemble_index] > Decimal::ZERO
+                        && (llm.inner.id == vote.model
+                            || llm
+                                .fallbacks
+                                .iter()
+                                .flatten()
+                                .any(|fallback| fallback.id == vote.model))
+                });
+                if let Some(i) = flat_ensemble_index {
+                    assigned[i] = true;
+                }
+                flat_ensemble_index
+            });
+        match flat_ensemble_index {
+            Some(flat_ensemble_index) => {
+                let ensemble_index = flat_llms[flat_ensemble_index].0;
+                vote.ensemble_index = ensemble_index as u64;
+                vote.flat_ensemble_index = flat_ensemble_index as u64;
+                vote.weight = profile[ensemble_index];
+                true
+            }
+            // the LLM is not in this ensemble, or has no weight
+            None => false,
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use objectiveai::ensemble::{Ensemble, EnsembleBuilder};
+    use objectiveai::ensemble_llm::EnsembleLlmBuilder;
+    use objectiveai::vector::completions::response::Vote;
+    use rust_decimal::dec;
+
+    fn ensemble(models: &[&str]) -> Ensemble {
+        let mut builder = EnsembleBuilder::new();
+        for model in models {
+            builder =
+                builder.llm(EnsembleLlmBuilder::new(*model).build().unwrap());
+        }
+        Ensemble::try_from(builder.build().unwrap()).unwrap()
+    }
+
+    fn vote(model: &str, flat_ensemble_index: u64) -> Vote {
+        Vote {
+            model: model.to_string(),
+            ensemble_index: flat_ensemble_index,
+            flat_ensemble_index,
+            prompt_id: String::new(),
+            tools_id: None,
+            responses_ids: Vec::new(),
+            vote: vec![Decimal::ONE, Decimal::ZERO],
+            weight: Decimal::ONE,
+            retry: Some(true),
+            from_cache: Some(true),
+            from_rng: None,
+            completion_index: None,
+        }
+    }
+
+    #[test]
+    fn reassign_votes_follows_reordered_ensemble() {
+        let ensemble = ensemble(&[
+            "openai/gpt-4o",
+            "google/gemini-2.5-pro",
+            "x-ai/grok-4",
+        ]);
+        let ids = ensemble
+            .llms
+            .iter()
+            .map(|llm| llm.inner.id.clone())
+            .collect::<Vec<_>>();
+        // votes of an ensemble which listed the LLMs in reverse order
+        let mut votes =
+            vec![vote(&ids[2], 0), vote(&ids[1], 1), vote(&ids[0], 2)];
+        let profile = [dec!(0.5), dec!(0.25), dec!(1)];
+
+        reassign_votes(&mut votes, &ensemble, &profile);
+
+        assert_eq!(votes.len(), 3);
+        for vote in &votes {
+            let index = ids.iter().position(|id| *id == vote.model).unwrap();
+            assert_eq!(vote.ensemble_index, index as u64);
+            assert_eq!(vote.flat_ensemble_index, index as u64);
+            assert_eq!(vote.weight, profile[index]);
+        }
+    }
+
+    #[test]
+    fn reassign_votes_drops_absent_and_unweighted_llms() {
+        let ensemble = ensemble(&["openai/gpt-4o", "google/gemini-2.5-pro"]);
+        let ids = ensemble
+            .llms
+            .iter()
+            .map(|llm| llm.inner.id.clone())
+            .collect::<Vec<_>>();
+        let mut votes =
+            vec![vote(&ids[0], 0), vote(&ids[1], 1), vote("removed", 2)];
+
+        reassign_votes(&mut votes, &ensemble, &[Decimal::ZERO, Decimal::ONE]);
+
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].model, ids[1]);
+    }
 }
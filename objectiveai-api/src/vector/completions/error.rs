@@ -27,6 +27,9 @@ pub enum Error {
     /// Vector completions require at least two response options.
     #[error("expected two or more request vector responses, got {0}")]
     ExpectedTwoOrMoreRequestVectorResponses(usize),
+    /// The uncertainty options are invalid.
+    #[error("invalid uncertainty: {0}")]
+    InvalidUncertainty(String),
 }
 
 impl objectiveai::error::StatusError for Error {
@@ -40,6 +43,7 @@ impl objectiveai::error::StatusError for Error {
             Error::EnsembleNotFound => 404,
             Error::InvalidEnsemble(_) => 400,
             Error::ExpectedTwoOrMoreRequestVectorResponses(_) => 400,
+            Error::InvalidUncertainty(_) => 400,
         }
     }
 
@@ -79,6 +83,10 @@ impl objectiveai::error::StatusError for Error {
                     "kind": "expected_two_or_more_request_vector_responses",
                     "error": format!("expected two or more request vector responses, got {}", n),
                 }),
+                Error::InvalidUncertainty(msg) => serde_json::json!({
+                    "kind": "invalid_uncertainty",
+                    "error": msg,
+                }),
             }
         }))
     }
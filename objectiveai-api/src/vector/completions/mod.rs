@@ -3,6 +3,8 @@
 //! This module provides the client for creating vector completions, which
 //! orchestrate multiple LLM chat completions for voting on response options.
 
+/// Inter-LLM agreement statistics for vector completions.
+pub mod agreement;
 /// Vote caching client for the global ObjectiveAI cache.
 pub mod cache;
 /// Fetcher for retrieving votes from the global cache.
@@ -14,13 +16,17 @@ mod error;
 mod get_vote;
 mod pfx;
 mod response_key;
+/// Bootstrapped uncertainty estimation for vector completion scores.
+pub mod uncertainty;
 /// Usage tracking for vector completions.
 pub mod usage_handler;
 /// Vector response transformation utilities.
 pub mod vector_responses;
+mod vote_flight;
 
 pub use client::*;
 pub use error::*;
 pub use get_vote::*;
 pub use pfx::*;
 pub use response_key::*;
+pub use vote_flight::*;
@@ -1,7 +1,7 @@
 //! Response key schema generation for structured LLM voting.
 //!
-//! Provides JSON schema and tool definitions that constrain LLM output to
-//! select one of the available response keys.
+//! Provides JSON schema, tool, and grammar definitions that constrain LLM
+//! output to select one of the available response keys.
 
 /// Parsed response key from LLM structured output.
 #[derive(Debug, serde::Deserialize)]
@@ -13,6 +13,23 @@ pub struct ResponseKey {
     response_key: String,
 }
 
+/// Decoding constraints for grammar output mode.
+///
+/// Self-hosted servers accept different constraint formats, so every
+/// representation is generated from the same set of response keys.
+#[derive(Debug, Clone)]
+pub struct ResponseKeyGrammar {
+    /// GBNF grammar, as accepted by llama.cpp.
+    pub gbnf: String,
+    /// Regular expression, as accepted by vLLM.
+    pub regex: String,
+    /// The exact set of valid outputs, as accepted by vLLM.
+    ///
+    /// None when synthetic reasoning is enabled, since the output then
+    /// contains free-form text.
+    pub choice: Option<Vec<String>>,
+}
+
 impl ResponseKey {
     /// Creates a JSON schema for response key selection.
     fn schema(
@@ -112,4 +129,65 @@ impl ResponseKey {
             },
         )
     }
+
+    /// GBNF rule matching a JSON string body, including the quotes.
+    const GBNF_JSON_STRING: &'static str = r#""\"" ( [^"\\\x7F\x00-\x1F] | "\\" ( ["\\/bfnrt] | "u" [0-9a-fA-F] [0-9a-fA-F] [0-9a-fA-F] [0-9a-fA-F] ) )* "\"""#;
+
+    /// Regex matching a JSON string body, including the quotes.
+    const REGEX_JSON_STRING: &'static str =
+        r#""([^"\\\x00-\x1F]|\\["\\/bfnrt]|\\u[0-9a-fA-F]{4})*""#;
+
+    /// Creates decoding constraints for grammar output mode.
+    ///
+    /// Without `think`, the LLM outputs exactly one response key as-is (e.g.
+    /// `` `A` `` or `` `A``B` ``). With `think`, the LLM outputs the same
+    /// compact JSON object as JSON schema mode, so `_think` can be parsed
+    /// into [`ResponseKey`].
+    pub fn grammar(
+        vector_response_keys: Vec<String>,
+        think: bool,
+    ) -> ResponseKeyGrammar {
+        // alternation of every response key
+        let mut gbnf_keys = String::new();
+        let mut regex_keys = String::new();
+        for key in &vector_response_keys {
+            if !gbnf_keys.is_empty() {
+                gbnf_keys.push_str(" | ");
+                regex_keys.push('|');
+            }
+            gbnf_keys.push('"');
+            for c in key.chars() {
+                if c == '"' || c == '\\' {
+                    gbnf_keys.push('\\');
+                }
+                gbnf_keys.push(c);
+            }
+            gbnf_keys.push('"');
+            regex_keys.push('(');
+            regex_keys.push_str(&regex::escape(key));
+            regex_keys.push(')');
+        }
+
+        if think {
+            ResponseKeyGrammar {
+                gbnf: format!(
+                    "root ::= \"{{\\\"_think\\\":\" think \",\\\"response_key\\\":\\\"\" key \"\\\"}}\"\nthink ::= {}\nkey ::= {}\n",
+                    Self::GBNF_JSON_STRING,
+                    gbnf_keys,
+                ),
+                regex: format!(
+                    r#"\{{"_think":{},"response_key":"({})"\}}"#,
+                    Self::REGEX_JSON_STRING,
+                    regex_keys,
+                ),
+                choice: None,
+            }
+        } else {
+            ResponseKeyGrammar {
+                gbnf: format!("root ::= {}\n", gbnf_keys),
+                regex: regex_keys,
+                choice: Some(vector_response_keys),
+            }
+        }
+    }
 }
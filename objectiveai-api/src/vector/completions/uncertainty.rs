@@ -0,0 +1,147 @@
+//! Bootstrapped uncertainty estimation for vector completion scores.
+//!
+//! Resamples the ensemble's votes with replacement to estimate how much the
+//! final scores depend on which LLMs happened to vote. Probabilistic votes
+//! (from logprobs) additionally contribute their own sampling noise: each
+//! resampled vote selects a single response drawn from its distribution.
+
+use rand::{Rng, SeedableRng};
+use rust_decimal::{
+    Decimal,
+    prelude::{FromPrimitive, ToPrimitive},
+};
+
+/// Computes bootstrapped confidence intervals and decisiveness for scores.
+///
+/// Uses `seed` for deterministic results when provided.
+pub fn bootstrap(
+    params: &objectiveai::vector::completions::request::Uncertainty,
+    seed: Option<i64>,
+    votes: &[objectiveai::vector::completions::response::Vote],
+    scores: &[Decimal],
+) -> objectiveai::vector::completions::response::Uncertainty {
+    let mut rng = match seed {
+        Some(seed) => rand::rngs::StdRng::seed_from_u64(seed as u64),
+        None => rand::rngs::StdRng::from_rng(&mut rand::rng()),
+    };
+    let samples = params.samples() as usize;
+    let confidence_level = params.confidence_level();
+    let responses_len = scores.len();
+
+    // convert votes to floats once, dropping votes that carry no weight
+    let votes = votes
+        .iter()
+        .filter_map(|vote| {
+            let weight = vote.weight.to_f64().unwrap_or(0.0);
+            let vote = vote
+                .vote
+                .iter()
+                .map(|v| v.to_f64().unwrap_or(0.0))
+                .collect::<Vec<_>>();
+            let vote_sum: f64 = vote.iter().sum();
+            if weight > 0.0 && vote_sum > 0.0 {
+                Some((weight, vote, vote_sum))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // the response which won the actual completion
+    let winner = argmax(scores.iter().map(|s| s.to_f64().unwrap_or(0.0)));
+
+    // resample
+    let mut sampled_scores = vec![Vec::with_capacity(samples); responses_len];
+    let mut winner_wins = 0.0;
+    let mut weights = vec![0.0; responses_len];
+    for _ in 0..samples {
+        weights.iter_mut().for_each(|w| *w = 0.0);
+        for _ in 0..votes.len() {
+            let (weight, vote, vote_sum) =
+                &votes[rng.random_range(0..votes.len())];
+            weights[select(&mut rng, vote, *vote_sum)] += *weight;
+        }
+        let weight_sum: f64 = weights.iter().sum();
+        let mut max = f64::MIN;
+        let mut max_count = 0;
+        for (i, weight) in weights.iter().enumerate() {
+            let score = if weight_sum > 0.0 {
+                weight / weight_sum
+            } else {
+                1.0 / responses_len as f64
+            };
+            if score > max {
+                max = score;
+                max_count = 1;
+            } else if score == max {
+                max_count += 1;
+            }
+            sampled_scores[i].push(score);
+        }
+        // ties share the win
+        if winner.is_some_and(|winner| sampled_scores[winner].last() == Some(&max))
+        {
+            winner_wins += 1.0 / max_count as f64;
+        }
+    }
+
+    // percentile intervals
+    let alpha = (1.0 - confidence_level.to_f64().unwrap_or(0.95)) / 2.0;
+    let intervals = sampled_scores
+        .into_iter()
+        .map(|mut sampled| {
+            sampled.sort_by(f64::total_cmp);
+            objectiveai::vector::completions::response::ScoreInterval {
+                lower: to_decimal(percentile(&sampled, alpha)),
+                upper: to_decimal(percentile(&sampled, 1.0 - alpha)),
+            }
+        })
+        .collect();
+
+    objectiveai::vector::completions::response::Uncertainty {
+        intervals,
+        decisiveness: to_decimal(winner_wins / samples as f64),
+        samples: samples as u64,
+        confidence_level,
+    }
+}
+
+/// Draws a response index from a vote distribution.
+fn select(rng: &mut impl Rng, vote: &[f64], vote_sum: f64) -> usize {
+    let mut target = rng.random_range(0.0..vote_sum);
+    for (i, v) in vote.iter().enumerate() {
+        if target < *v {
+            return i;
+        }
+        target -= v;
+    }
+    // floating point remainder, fall back to the last non-zero entry
+    vote.iter().rposition(|v| *v > 0.0).unwrap_or(0)
+}
+
+/// Returns the index of the first maximum value, if any.
+fn argmax(values: impl Iterator<Item = f64>) -> Option<usize> {
+    let mut max: Option<(usize, f64)> = None;
+    for (i, value) in values.enumerate() {
+        if max.is_none_or(|(_, max)| value > max) {
+            max = Some((i, value));
+        }
+    }
+    max.map(|(i, _)| i)
+}
+
+/// Returns the `p` percentile of sorted values using nearest-rank.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Converts a probability into a decimal clamped to [0, 1].
+fn to_decimal(value: f64) -> Decimal {
+    Decimal::from_f64(value.clamp(0.0, 1.0))
+        .unwrap_or(Decimal::ZERO)
+        .round_dp(6)
+}
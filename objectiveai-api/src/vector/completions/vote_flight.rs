@@ -0,0 +1,160 @@
+//! Server-wide deduplication of identical in-flight upstream vote requests.
+
+use dashmap::DashMap;
+use futures::future::Shared;
+use std::sync::Arc;
+
+/// Identifies an upstream vote request which may be shared across requests.
+///
+/// `ordinal` distinguishes the repeated copies of an Ensemble LLM with a
+/// `count` greater than one, so that they still sample independently.
+/// Requests with different upstream parameters or credentials never share
+/// a call.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VoteFlightKey {
+    /// The Ensemble LLM ID.
+    pub ensemble_llm_id: String,
+    /// Which copy of the Ensemble LLM this is, from 0 to `count - 1`.
+    pub ordinal: usize,
+    /// Hash of the prompt messages.
+    pub prompt_id: String,
+    /// Hash of the tools, if any.
+    pub tools_id: Option<String>,
+    /// Hashes of the responses, in request order.
+    pub responses_ids: Vec<String>,
+    /// The sampling seed.
+    pub seed: Option<i64>,
+    /// JSON of the provider preferences, if any.
+    pub provider: Option<String>,
+    /// SHA-256 digests of the BYOK API key for each upstream, in
+    /// [`Upstream::ALL`](crate::chat::completions::upstream::Upstream::ALL)
+    /// order.
+    pub byok_ids: Vec<Option<String>>,
+}
+
+/// The finished result of an upstream vote request.
+#[derive(Debug, Clone)]
+pub struct VoteFlight {
+    /// The aggregated chat completion, including its usage.
+    pub completion:
+        objectiveai::vector::completions::response::streaming::ChatCompletionChunk,
+    /// The votes extracted from the chat completion.
+    pub votes: Vec<objectiveai::vector::completions::response::Vote>,
+}
+
+/// A request which receives the result of a [`VoteFlight`] it did not make
+/// the upstream call for.
+#[derive(Debug, Clone)]
+pub struct VoteFlightFollower {
+    /// The ID of the follower's vector completion.
+    pub id: String,
+    /// When the follower's vector completion was created.
+    pub created: u64,
+    /// The ID of the follower's Ensemble.
+    pub ensemble: String,
+    /// The index of the completion within the follower's vector completion.
+    pub completion_index: u64,
+    /// The index of the Ensemble LLM within the follower's Ensemble.
+    pub ensemble_index: usize,
+    /// The flat index of the Ensemble LLM within the follower's Ensemble.
+    pub flat_ensemble_index: usize,
+    /// The weight of the Ensemble LLM in the follower's Profile.
+    pub weight: rust_decimal::Decimal,
+}
+
+impl VoteFlight {
+    /// Converts the shared result into a chunk for a request which did not
+    /// make the upstream call.
+    ///
+    /// Votes are re-attributed to the follower's position in its own
+    /// Ensemble, and usage is zeroed as it was billed to the leading request.
+    pub fn into_chunk(
+        self,
+        follower: VoteFlightFollower,
+    ) -> objectiveai::vector::completions::response::streaming::VectorCompletionChunk
+    {
+        let VoteFlight {
+            mut completion,
+            mut votes,
+        } = self;
+        let VoteFlightFollower {
+            id,
+            created,
+            ensemble,
+            completion_index,
+            ensemble_index,
+            flat_ensemble_index,
+            weight,
+        } = follower;
+        completion.index = completion_index;
+        completion.inner.usage = None;
+        for vote in &mut votes {
+            vote.ensemble_index = ensemble_index as u64;
+            vote.flat_ensemble_index = flat_ensemble_index as u64;
+            vote.weight = weight;
+            vote.completion_index = Some(completion_index);
+        }
+        objectiveai::vector::completions::response::streaming::VectorCompletionChunk {
+            id,
+            completions: vec![completion],
+            votes,
+            scores: Vec::new(),
+            weights: Vec::new(),
+            created,
+            ensemble,
+            object: objectiveai::vector::completions::response::streaming::Object::VectorCompletionChunk,
+            usage: None,
+            uncertainty: None,
+            agreement: None,
+        }
+    }
+}
+
+/// In-flight upstream vote requests, shared by every request on the server.
+pub type VoteFlights =
+    DashMap<VoteFlightKey, Shared<tokio::sync::oneshot::Receiver<VoteFlight>>>;
+
+/// Held by the request which makes the upstream call for a [`VoteFlightKey`].
+///
+/// Removes the key once the flight finishes. If dropped before then, the
+/// waiting requests observe a closed channel and retry on their own.
+pub struct VoteFlightGuard {
+    flights: Arc<VoteFlights>,
+    key: Option<VoteFlightKey>,
+    tx: Option<tokio::sync::oneshot::Sender<VoteFlight>>,
+}
+
+impl VoteFlightGuard {
+    /// Creates a new guard for a flight registered under `key`.
+    pub fn new(
+        flights: Arc<VoteFlights>,
+        key: VoteFlightKey,
+        tx: tokio::sync::oneshot::Sender<VoteFlight>,
+    ) -> Self {
+        Self {
+            flights,
+            key: Some(key),
+            tx: Some(tx),
+        }
+    }
+
+    /// Removes the key and shares the result with the waiting requests.
+    pub fn finish(mut self, flight: VoteFlight) {
+        if let Some(key) = self.key.take() {
+            self.flights.remove(&key);
+        }
+        if let Some(tx) = self.tx.take() {
+            let _ = tx.send(flight);
+        }
+    }
+}
+
+impl Drop for VoteFlightGuard {
+    fn drop(&mut self) {
+        // remove the key before the sender drops, so that waiting requests
+        // never find the closed flight again
+        if let Some(key) = self.key.take() {
+            self.flights.remove(&key);
+        }
+    }
+}
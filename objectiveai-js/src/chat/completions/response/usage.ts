@@ -137,6 +137,12 @@ export const UsageSchema = z
       .describe(
         "Whether the completion used a BYOK (Bring Your Own Key) API Key.",
       ),
+    queue_wait_time: z
+      .uint32()
+      .optional()
+      .describe(
+        "The milliseconds spent waiting for upstream rate limits before the request was sent.",
+      ),
   })
   .describe("Token and cost usage statistics for the completion.");
 export type Usage = z.infer<typeof UsageSchema>;
@@ -1,8 +1,8 @@
 import z from "zod";
 
 export const OutputModeSchema = z
-  .enum(["instruction", "json_schema", "tool_call"])
+  .enum(["instruction", "json_schema", "tool_call", "grammar"])
   .describe(
-    'For Vector Completions only, specifies the LLM\'s voting output mode. For "instruction", the assistant is instructed to output a key. For "json_schema", the assistant is constrained to output a valid key using a JSON schema. For "tool_call", the assistant is instructed to output a tool call to select the key.'
+    'For Vector Completions only, specifies the LLM\'s voting output mode. For "instruction", the assistant is instructed to output a key. For "json_schema", the assistant is constrained to output a valid key using a JSON schema. For "tool_call", the assistant is instructed to output a tool call to select the key. For "grammar", the assistant is constrained to output a valid key using a decoding grammar, for self-hosted servers such as llama.cpp or vLLM.'
   );
 export type OutputMode = z.infer<typeof OutputModeSchema>;
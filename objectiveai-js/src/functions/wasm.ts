@@ -1,5 +1,7 @@
 import {
   validateFunctionInput as wasmValidateFunctionInput,
+  inputSchemaToJsonSchema as wasmInputSchemaToJsonSchema,
+  inputSchemaFromJsonSchema as wasmInputSchemaFromJsonSchema,
   compileFunctionInputMaps as wasmCompileFunctionInputMaps,
   compileFunctionTasks as wasmCompileFunctionTasks,
   compileFunctionOutput as wasmCompileFunctionOutput,
@@ -8,16 +10,71 @@ import {
   compileFunctionInputMerge as wasmCompileFunctionInputMerge,
 } from "../wasm/loader.js";
 import { Function } from "./function";
-import { CompiledFunctionOutput, InputValue, TaskOutputs } from "./expression";
+import {
+  CompiledFunctionOutput,
+  InputSchema,
+  InputValue,
+  TaskOutputs,
+} from "./expression";
 import { CompiledTasks } from "./task";
 import { mapsToRecords } from "src/mapsToRecords";
 
+export type InputType =
+  | "object"
+  | "array"
+  | "string"
+  | "integer"
+  | "number"
+  | "boolean"
+  | "text"
+  | "image"
+  | "audio"
+  | "video"
+  | "file";
+
+export type InputSchemaViolation = { path: string } & (
+  | { kind: "type"; expected: InputType; actual: InputType }
+  | { kind: "required"; property: string }
+  | { kind: "minimum"; minimum: number; actual: number }
+  | { kind: "maximum"; maximum: number; actual: number }
+  | { kind: "enum"; allowed: string[]; actual: string }
+  | { kind: "min_items"; min_items: number; actual: number }
+  | { kind: "max_items"; max_items: number; actual: number }
+  | { kind: "any_of"; violations: InputSchemaViolation[][] }
+);
+
 export function validateFunctionInput(
   function_: Function,
   input: InputValue,
 ): boolean | null {
+  const violations = functionInputViolations(function_, input);
+  return violations === null ? null : violations.length === 0;
+}
+
+export function functionInputViolations(
+  function_: Function,
+  input: InputValue,
+): InputSchemaViolation[] | null {
   const result = wasmValidateFunctionInput(function_, input);
-  return result === undefined ? null : result;
+  if (result === undefined) return null;
+  const unmapped = mapsToRecords(result);
+  return unmapped as InputSchemaViolation[];
+}
+
+export function inputSchemaToJsonSchema(
+  schema: InputSchema,
+): Record<string, unknown> {
+  const value = wasmInputSchemaToJsonSchema(schema);
+  const unmapped = mapsToRecords(value);
+  return unmapped as Record<string, unknown>;
+}
+
+export function inputSchemaFromJsonSchema(
+  jsonSchema: Record<string, unknown>,
+): InputSchema {
+  const value = wasmInputSchemaFromJsonSchema(jsonSchema);
+  const unmapped = mapsToRecords(value);
+  return unmapped as InputSchema;
 }
 
 export function compileFunctionInputMaps(
@@ -0,0 +1,55 @@
+import z from "zod";
+
+export const LlmAgreementSchema = z
+  .object({
+    model: z
+      .string()
+      .describe(
+        "The unique identifier of the Ensemble LLM which generated the vote."
+      ),
+    ensemble_index: z
+      .uint32()
+      .describe("The index of the Ensemble LLM in the Ensemble."),
+    flat_ensemble_index: z
+      .uint32()
+      .describe(
+        "The flat index of the Ensemble LLM in the expanded Ensemble, accounting for counts."
+      ),
+    agreement: z
+      .number()
+      .describe(
+        "One minus the Jensen-Shannon divergence (base 2) between the vote and the scores, in [0, 1]. 1 means the vote matches the consensus."
+      ),
+  })
+  .describe("The agreement of a single vote with the weighted consensus.");
+export type LlmAgreement = z.infer<typeof LlmAgreementSchema>;
+
+export const AgreementSchema = z
+  .object({
+    fleiss_kappa: z
+      .number()
+      .optional()
+      .describe(
+        "The free-marginal Fleiss' kappa over the votes, in [-1, 1]. 1 is perfect agreement, 0 is chance-level agreement. Absent when fewer than two votes have a positive weight."
+      ),
+    mean_pairwise_jensen_shannon_divergence: z
+      .number()
+      .optional()
+      .describe(
+        "The weighted mean Jensen-Shannon divergence (base 2) between every pair of votes, in [0, 1]. 0 means all votes are identical. Absent when fewer than two votes have a positive weight."
+      ),
+    normalized_score_entropy: z
+      .number()
+      .describe(
+        "The entropy of the scores normalized by the maximum possible entropy, in [0, 1]. 0 means all weight is on one response, 1 means the scores are uniform."
+      ),
+    llms: z
+      .array(LlmAgreementSchema)
+      .describe(
+        "The agreement of each vote not drawn from the RNG with the weighted consensus."
+      ),
+  })
+  .describe(
+    "Statistics describing how much the Ensemble LLMs agree with each other. Votes drawn from the RNG are left out, and every other vote counts with its weight."
+  );
+export type Agreement = z.infer<typeof AgreementSchema>;
@@ -1,5 +1,7 @@
+export * from "./agreement";
 export * from "./ensemble";
 export * from "./scores";
+export * from "./uncertainty";
 export * as Streaming from "./streaming";
 export * as Unary from "./unary";
 export * from "./usage";
@@ -8,6 +8,8 @@ import {
   ChatCompletionChunkSchema,
 } from "./chat_completion_chunk";
 import { UsageSchema } from "../usage";
+import { UncertaintySchema } from "../uncertainty";
+import { AgreementSchema } from "../agreement";
 import { ResponseObjectSchema } from "./response_object";
 import { merge } from "src/merge";
 
@@ -30,6 +32,12 @@ export const VectorCompletionChunkSchema = z
     ensemble: EnsembleSchema,
     object: ResponseObjectSchema,
     usage: UsageSchema.optional(),
+    uncertainty: UncertaintySchema.optional().describe(
+      "The bootstrapped uncertainty of the scores. Only present in the final chunk, and only when requested."
+    ),
+    agreement: AgreementSchema.optional().describe(
+      "Statistics describing how much the Ensemble LLMs agree with each other. Only present in the final chunk."
+    ),
   })
   .describe("A chunk in a streaming vector completion response.");
 export type VectorCompletionChunk = z.infer<typeof VectorCompletionChunkSchema>;
@@ -51,12 +59,20 @@ export namespace VectorCompletionChunk {
     const ensemble = a.ensemble;
     const object = a.object;
     const [usage, usageChanged] = merge(a.usage, b.usage);
+    const [uncertainty, uncertaintyChanged] =
+      b.uncertainty !== undefined
+        ? [b.uncertainty, true]
+        : [a.uncertainty, false];
+    const [agreement, agreementChanged] =
+      b.agreement !== undefined ? [b.agreement, true] : [a.agreement, false];
     if (
       completionsChanged ||
       votesChanged ||
       scoresChanged ||
       weightsChanged ||
-      usageChanged
+      usageChanged ||
+      uncertaintyChanged ||
+      agreementChanged
     ) {
       return [
         {
@@ -69,6 +85,8 @@ export namespace VectorCompletionChunk {
           ensemble,
           object,
           ...(usage !== undefined ? { usage } : {}),
+          ...(uncertainty !== undefined ? { uncertainty } : {}),
+          ...(agreement !== undefined ? { agreement } : {}),
         },
         true,
       ];
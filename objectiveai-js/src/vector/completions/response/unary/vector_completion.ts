@@ -5,6 +5,8 @@ import { ScoresSchema } from "../scores";
 import { WeightsSchema } from "../weights";
 import { EnsembleSchema } from "../ensemble";
 import { UsageSchema } from "../usage";
+import { UncertaintySchema } from "../uncertainty";
+import { AgreementSchema } from "../agreement";
 
 export const VectorCompletionSchema = z
   .object({
@@ -25,6 +27,10 @@ export const VectorCompletionSchema = z
     ensemble: EnsembleSchema,
     object: z.literal("vector.completion"),
     usage: UsageSchema,
+    uncertainty: UncertaintySchema.optional(),
+    agreement: AgreementSchema.optional().describe(
+      "Statistics describing how much the Ensemble LLMs agree with each other."
+    ),
   })
   .describe("A unary vector completion response.");
 export type VectorCompletion = z.infer<typeof VectorCompletionSchema>;
@@ -0,0 +1,31 @@
+import z from "zod";
+
+export const ScoreIntervalSchema = z
+  .object({
+    lower: z.number().describe("The lower bound of the score."),
+    upper: z.number().describe("The upper bound of the score."),
+  })
+  .describe("A confidence interval for a single response's score.");
+export type ScoreInterval = z.infer<typeof ScoreIntervalSchema>;
+
+export const UncertaintySchema = z
+  .object({
+    intervals: z
+      .array(ScoreIntervalSchema)
+      .describe(
+        "The confidence interval for each response's score. It is of the same length as the scores."
+      ),
+    decisiveness: z
+      .number()
+      .describe(
+        "The fraction of resamples in which the highest-scoring response remained the highest-scoring response. Near 1 when the ensemble is decisive, near `1 / responses` when the split is noise."
+      ),
+    samples: z.uint32().describe("The number of bootstrap resamples used."),
+    confidence_level: z
+      .number()
+      .describe("The confidence level of the intervals."),
+  })
+  .describe(
+    "The bootstrapped uncertainty of the scores, computed by resampling the votes of the Ensemble LLMs with replacement. Only present when requested."
+  );
+export type Uncertainty = z.infer<typeof UncertaintySchema>;
@@ -29,6 +29,12 @@ export const UsageSchema = z
     total_cost: z
       .number()
       .describe("The total cost in credits incurred including upstream costs."),
+    queue_wait_time: z
+      .uint32()
+      .optional()
+      .describe(
+        "The total milliseconds spent waiting for upstream rate limits before requests were sent.",
+      ),
   })
   .describe("Token and cost usage statistics for the completion.");
 export type Usage = z.infer<typeof UsageSchema>;
@@ -0,0 +1,376 @@
+//! Derive macros for the ObjectiveAI SDK.
+//!
+//! These macros are re-exported by the `objectiveai` crate when its `derive`
+//! feature is enabled, and should be used through it.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+
+/// Derives `objectiveai::functions::expression::FunctionInput`.
+///
+/// - Structs with named fields become object schemas. Every field is
+///   required, as object inputs must hold every property of their schema,
+///   so `Option` fields are rejected.
+/// - Newtype structs have the schema of their field.
+/// - Enums with only unit variants become string schemas enumerating the
+///   variant names.
+/// - Other enums become `anyOf` schemas with one entry per variant, and are
+///   converted untagged. Unit variants are strings, newtype variants have the
+///   schema of their field, and struct variants are objects.
+///
+/// Doc comments on the type, fields, and variants become the descriptions of
+/// their schemas. Fields and variants may be renamed with
+/// `#[function_input(rename = "...")]`.
+#[proc_macro_derive(FunctionInput, attributes(function_input))]
+pub fn derive_function_input(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match function_input(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn function_input(input: syn::DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let description = description(&input.attrs);
+    let (input_schema, into_input) = match &input.data {
+        syn::Data::Struct(data) => match &data.fields {
+            syn::Fields::Named(fields) => {
+                let fields = named_fields(fields)?;
+                (
+                    object_schema(&fields, &description),
+                    object_input(&fields, quote!(self.)),
+                )
+            }
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                (
+                    with_description(
+                        quote!(<#ty as ::objectiveai::functions::expression::FunctionInput>::input_schema()),
+                        &description,
+                    ),
+                    quote!(::objectiveai::functions::expression::FunctionInput::into_input(self.0)),
+                )
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "FunctionInput can only be derived for structs with named fields or a single unnamed field",
+                ));
+            }
+        },
+        syn::Data::Enum(data) if data.variants.is_empty() => {
+            return Err(syn::Error::new(
+                input.span(),
+                "FunctionInput cannot be derived for enums without variants",
+            ));
+        }
+        syn::Data::Enum(data) => enum_function_input(data, &description)?,
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new(
+                input.span(),
+                "FunctionInput cannot be derived for unions",
+            ));
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(
+            ::objectiveai::functions::expression::FunctionInput
+        ));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::objectiveai::functions::expression::FunctionInput
+            for #name #ty_generics #where_clause
+        {
+            fn input_schema() -> ::objectiveai::functions::expression::InputSchema {
+                #input_schema
+            }
+
+            fn into_input(self) -> ::objectiveai::functions::expression::Input {
+                #into_input
+            }
+        }
+    })
+}
+
+fn enum_function_input(
+    data: &syn::DataEnum,
+    description: &Option<String>,
+) -> syn::Result<(TokenStream2, TokenStream2)> {
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| {
+            Ok((
+                variant,
+                rename(&variant.attrs)?
+                    .unwrap_or_else(|| variant.ident.to_string()),
+            ))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // enums of unit variants are plain strings
+    if variants
+        .iter()
+        .all(|(variant, _)| matches!(variant.fields, syn::Fields::Unit))
+    {
+        let names = variants.iter().map(|(_, name)| name);
+        let arms = variants.iter().map(|(variant, name)| {
+            let ident = &variant.ident;
+            quote!(Self::#ident => #name)
+        });
+        return Ok((
+            string_enum_schema(names, description),
+            quote! {
+                ::objectiveai::functions::expression::Input::String(
+                    ::std::string::String::from(match self { #(#arms,)* }),
+                )
+            },
+        ));
+    }
+
+    let mut schemas = Vec::with_capacity(variants.len());
+    let mut arms = Vec::with_capacity(variants.len());
+    for (variant, name) in &variants {
+        let ident = &variant.ident;
+        let description = self::description(&variant.attrs);
+        match &variant.fields {
+            syn::Fields::Unit => {
+                schemas.push(string_enum_schema([name], &description));
+                arms.push(quote! {
+                    Self::#ident => ::objectiveai::functions::expression::Input::String(
+                        ::std::string::String::from(#name),
+                    )
+                });
+            }
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                schemas.push(with_description(
+                    quote!(<#ty as ::objectiveai::functions::expression::FunctionInput>::input_schema()),
+                    &description,
+                ));
+                arms.push(quote! {
+                    Self::#ident(value) => ::objectiveai::functions::expression::FunctionInput::into_input(value)
+                });
+            }
+            syn::Fields::Named(fields) => {
+                let fields = named_fields(fields)?;
+                schemas.push(object_schema(&fields, &description));
+                let idents = fields.iter().map(|field| &field.ident);
+                let input = object_input(&fields, quote!());
+                arms.push(quote! {
+                    Self::#ident { #(#idents),* } => #input
+                });
+            }
+            syn::Fields::Unnamed(_) => {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "FunctionInput variants can have at most one unnamed field",
+                ));
+            }
+        }
+    }
+    Ok((
+        quote! {
+            ::objectiveai::functions::expression::InputSchema::AnyOf(
+                ::objectiveai::functions::expression::AnyOfInputSchema {
+                    any_of: ::std::vec![#(#schemas),*],
+                },
+            )
+        },
+        quote!(match self { #(#arms,)* }),
+    ))
+}
+
+/// A named field of a struct or struct variant.
+struct Field<'a> {
+    ident: &'a syn::Ident,
+    name: String,
+    description: Option<String>,
+    ty: &'a syn::Type,
+}
+
+fn named_fields(fields: &syn::FieldsNamed) -> syn::Result<Vec<Field<'_>>> {
+    fields
+        .named
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            if is_option(&field.ty) {
+                return Err(syn::Error::new(
+                    field.ty.span(),
+                    "FunctionInput fields cannot be `Option`, as every property of an object input is required",
+                ));
+            }
+            Ok(Field {
+                ident,
+                name: rename(&field.attrs)?.unwrap_or_else(|| {
+                    ident.to_string().trim_start_matches("r#").to_string()
+                }),
+                description: description(&field.attrs),
+                ty: &field.ty,
+            })
+        })
+        .collect()
+}
+
+fn object_schema(
+    fields: &[Field<'_>],
+    description: &Option<String>,
+) -> TokenStream2 {
+    let properties = fields.iter().map(|field| {
+        let name = &field.name;
+        let ty = field.ty;
+        let schema = with_description(
+            quote!(<#ty as ::objectiveai::functions::expression::FunctionInput>::input_schema()),
+            &field.description,
+        );
+        quote!((::std::string::String::from(#name), #schema))
+    });
+    let required = fields.iter().map(|field| &field.name);
+    let description = option_string(description);
+    quote! {
+        ::objectiveai::functions::expression::InputSchema::Object(
+            ::objectiveai::functions::expression::ObjectInputSchema {
+                description: #description,
+                properties: ::std::iter::IntoIterator::into_iter([#(#properties),*])
+                    .collect(),
+                required: ::std::option::Option::Some(::std::vec![
+                    #(::std::string::String::from(#required)),*
+                ]),
+            },
+        )
+    }
+}
+
+/// Converts the fields into an object input. Each field is accessed as
+/// `#prefix #ident`.
+fn object_input(fields: &[Field<'_>], prefix: TokenStream2) -> TokenStream2 {
+    let inserts = fields.iter().map(|field| {
+        let name = &field.name;
+        let ident = field.ident;
+        quote! {
+            object.push((
+                ::std::string::String::from(#name),
+                ::objectiveai::functions::expression::FunctionInput::into_input(#prefix #ident),
+            ));
+        }
+    });
+    let len = fields.len();
+    quote! {{
+        let mut object = ::std::vec::Vec::with_capacity(#len);
+        #(#inserts)*
+        ::objectiveai::functions::expression::Input::Object(
+            ::std::iter::IntoIterator::into_iter(object).collect(),
+        )
+    }}
+}
+
+fn string_enum_schema<'a>(
+    names: impl IntoIterator<Item = &'a String>,
+    description: &Option<String>,
+) -> TokenStream2 {
+    let names = names.into_iter();
+    let description = option_string(description);
+    quote! {
+        ::objectiveai::functions::expression::InputSchema::String(
+            ::objectiveai::functions::expression::StringInputSchema {
+                description: #description,
+                r#enum: ::std::option::Option::Some(::std::vec![
+                    #(::std::string::String::from(#names)),*
+                ]),
+            },
+        )
+    }
+}
+
+fn with_description(
+    schema: TokenStream2,
+    description: &Option<String>,
+) -> TokenStream2 {
+    match description {
+        Some(description) => quote!(#schema.with_description(#description)),
+        None => schema,
+    }
+}
+
+fn option_string(value: &Option<String>) -> TokenStream2 {
+    match value {
+        Some(value) => quote! {
+            ::std::option::Option::Some(::std::string::String::from(#value))
+        },
+        None => quote!(::std::option::Option::None),
+    }
+}
+
+/// Returns whether `ty` is `Option<T>`.
+fn is_option(ty: &syn::Type) -> bool {
+    let syn::Type::Path(path) = ty else {
+        return false;
+    };
+    path.qself.is_none()
+        && path.path.segments.last().is_some_and(|segment| {
+            segment.ident == "Option"
+                && matches!(
+                    segment.arguments,
+                    syn::PathArguments::AngleBracketed(_)
+                )
+        })
+}
+
+/// Joins the doc comments of an item into a description.
+fn description(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(s),
+                        ..
+                    }),
+                ..
+            }) => Some(s.value()),
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    let description = lines.join("\n").trim().to_string();
+    if description.is_empty() {
+        None
+    } else {
+        Some(description)
+    }
+}
+
+/// Returns the name given by `#[function_input(rename = "...")]`, if any.
+fn rename(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
+    let mut rename = None;
+    for attr in attrs {
+        if !attr.path().is_ident("function_input") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                rename = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported function_input attribute"))
+            }
+        })?;
+    }
+    Ok(rename)
+}
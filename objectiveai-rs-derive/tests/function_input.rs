@@ -0,0 +1,63 @@
+use objectiveai::functions::expression::FunctionInput;
+
+/// A joke to rate.
+#[derive(FunctionInput)]
+struct Joke {
+    /// The text of the joke.
+    text: String,
+    /// Who the joke is told to.
+    audience: Audience,
+    #[function_input(rename = "tags")]
+    labels: Vec<String>,
+    rating: Rating,
+}
+
+#[derive(FunctionInput)]
+enum Audience {
+    Kids,
+    #[function_input(rename = "grown-ups")]
+    Adults,
+}
+
+#[derive(FunctionInput)]
+enum Rating {
+    Unrated,
+    Stars(u8),
+    Review { stars: u8, text: String },
+}
+
+#[derive(FunctionInput)]
+struct Text(String);
+
+/// Checks that a value conforms to the schema of its type.
+fn assert_valid<T: FunctionInput>(value: T) {
+    let schema = T::input_schema();
+    let input = value.into_input();
+    assert!(
+        schema.validate_input(&input),
+        "{input:?} does not conform to {schema:?}",
+    );
+}
+
+#[test]
+fn derived_inputs_conform_to_their_schemas() {
+    for (audience, rating) in [
+        (Audience::Kids, Rating::Unrated),
+        (Audience::Adults, Rating::Stars(4)),
+        (
+            Audience::Kids,
+            Rating::Review {
+                stars: 2,
+                text: "Heard it before.".to_string(),
+            },
+        ),
+    ] {
+        assert_valid(Joke {
+            text: "Why did the chicken cross the road?".to_string(),
+            audience,
+            labels: vec!["classic".to_string()],
+            rating,
+        });
+    }
+    assert_valid(Text("To get to the other side.".to_string()));
+}
@@ -0,0 +1,67 @@
+"""Python bindings for ObjectiveAI.
+
+Arguments and return values are plain Python objects shaped like the JSON
+the ObjectiveAI API accepts and returns. Every function raises `ValueError`
+if an argument cannot be deserialized or fails validation.
+"""
+
+from typing import Any, Optional, Union
+
+Json = Union[None, bool, int, float, str, list["Json"], dict[str, "Json"]]
+"""A JSON-compatible Python value."""
+
+def validate_ensemble_llm(llm: dict[str, Any]) -> dict[str, Any]:
+    """Validates an Ensemble LLM and returns it with its `id` populated."""
+
+def validate_ensemble(ensemble: dict[str, Any]) -> dict[str, Any]:
+    """Validates an Ensemble and returns it with its `id` populated."""
+
+def validate_function_input(
+    function: dict[str, Any], input: Json
+) -> Optional[list[dict[str, Any]]]:
+    """Returns every input schema violation, or `None` for inline Functions."""
+
+def input_schema_to_json_schema(schema: dict[str, Any]) -> dict[str, Any]:
+    """Converts an input schema into a JSON Schema 2020-12 document."""
+
+def input_schema_from_json_schema(json_schema: dict[str, Any]) -> dict[str, Any]:
+    """Converts a JSON Schema document into an input schema."""
+
+def compile_function_input_maps(
+    function: dict[str, Any], input: Json
+) -> Optional[list[list[Json]]]:
+    """Compiles the `input_maps` expressions, or `None` if undefined."""
+
+def compile_function_tasks(
+    function: dict[str, Any], input: Json
+) -> list[Union[None, dict[str, Any], list[dict[str, Any]]]]:
+    """Compiles every task, `None` for skipped tasks and a list for mapped tasks."""
+
+def compile_function_output(
+    function: dict[str, Any], input: Json, task_outputs: list[Json]
+) -> dict[str, Any]:
+    """Compiles the output expression, returning `output` and `valid`."""
+
+def compile_function_output_length(
+    function: dict[str, Any], input: Json
+) -> Optional[int]:
+    """Compiles the `output_length` expression of remote vector Functions."""
+
+def compile_function_input_split(
+    function: dict[str, Any], input: Json
+) -> Optional[list[Json]]:
+    """Compiles the `input_split` expression, or `None` if undefined."""
+
+def compile_function_input_merge(
+    function: dict[str, Any], input: list[Json]
+) -> Optional[Json]:
+    """Compiles the `input_merge` expression, or `None` if undefined."""
+
+def prompt_id(prompt: list[dict[str, Any]]) -> str:
+    """Computes the content-addressed ID of chat messages."""
+
+def tools_id(tools: list[dict[str, Any]]) -> str:
+    """Computes the content-addressed ID of a tools list."""
+
+def vector_response_id(response: Union[str, list[dict[str, Any]]]) -> str:
+    """Computes the content-addressed ID of a vector completion response option."""
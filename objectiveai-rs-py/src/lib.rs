@@ -0,0 +1,551 @@
+//! Python bindings for ObjectiveAI.
+//!
+//! This crate provides Python bindings for client-side validation and
+//! compilation of ObjectiveAI types, mirroring `objectiveai-rs-wasm-js`. It
+//! enables notebooks and data pipelines to:
+//!
+//! - Validate Ensemble LLM and Ensemble configurations
+//! - Compute content-addressed IDs (deterministic hashes)
+//! - Compile Function expressions exactly as the server does
+//! - Compute prompt, tools, and response IDs for caching/deduplication
+//!
+//! # Usage
+//!
+//! This crate is compiled to a Python extension module with `maturin` and
+//! imported as `objectiveai_py`. Arguments and return values are plain
+//! Python objects (dicts, lists, strings, numbers) shaped like the JSON the
+//! API accepts and returns.
+//!
+//! # Functions
+//!
+//! - [`validate_ensemble_llm`] - Validate and compute ID for an Ensemble LLM
+//! - [`validate_ensemble`] - Validate and compute ID for an Ensemble
+//! - [`validate_function_input`] - Validate function input against its schema
+//! - [`compile_function_input_maps`] - Compile function input maps
+//! - [`compile_function_tasks`] - Compile function tasks for a given input
+//! - [`compile_function_output`] - Compile function output from task results
+//! - [`compile_function_output_length`] - Compile function output length
+//! - [`compile_function_input_split`] - Split input into sub-inputs
+//! - [`compile_function_input_merge`] - Merge sub-inputs into one input
+//! - [`input_schema_to_json_schema`] - Convert an input schema to JSON Schema
+//! - [`input_schema_from_json_schema`] - Convert JSON Schema to an input schema
+//! - [`prompt_id`] - Compute content-addressed ID for chat messages
+//! - [`tools_id`] - Compute content-addressed ID for tools
+//! - [`vector_response_id`] - Compute content-addressed ID for a response option
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Deserializes a Python object by round-tripping it through `json.dumps`.
+fn from_py<T: serde::de::DeserializeOwned>(
+    value: &Bound<'_, PyAny>,
+) -> PyResult<T> {
+    let json: String = value
+        .py()
+        .import("json")?
+        .call_method1("dumps", (value,))?
+        .extract()?;
+    serde_json::from_str(&json)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Serializes a value into a Python object by round-tripping it through
+/// `json.loads`.
+fn to_py<T: serde::Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+/// Validates an Ensemble LLM configuration and computes its content-addressed ID.
+///
+/// Takes an Ensemble LLM definition, normalizes it (removes defaults, deduplicates),
+/// validates all fields, and computes a deterministic ID using XXHash3-128.
+///
+/// # Arguments
+///
+/// * `llm` - Python dict representing an Ensemble LLM configuration
+///
+/// # Returns
+///
+/// The validated Ensemble LLM with its computed `id` field populated.
+///
+/// # Errors
+///
+/// Raises `ValueError` if validation fails (e.g., invalid model name,
+/// out-of-range parameters, conflicting settings).
+#[pyfunction]
+pub fn validate_ensemble_llm(
+    py: Python<'_>,
+    llm: &Bound<'_, PyAny>,
+) -> PyResult<PyObject> {
+    // deserialize
+    let llm_base: objectiveai::ensemble_llm::EnsembleLlmBase = from_py(llm)?;
+    // prepare, validate, and compute ID
+    let llm: objectiveai::ensemble_llm::EnsembleLlm =
+        llm_base.try_into().map_err(PyValueError::new_err)?;
+    // serialize
+    to_py(py, &llm)
+}
+
+/// Validates an Ensemble configuration and computes its content-addressed ID.
+///
+/// Takes an Ensemble definition (a collection of Ensemble LLMs), validates each
+/// LLM, and computes a deterministic ID for the ensemble as a whole.
+///
+/// # Arguments
+///
+/// * `ensemble` - Python dict representing an Ensemble configuration
+///
+/// # Returns
+///
+/// The validated Ensemble with its computed `id` field populated and all
+/// member LLMs validated with their IDs.
+///
+/// # Errors
+///
+/// Raises `ValueError` if any LLM validation fails or the ensemble
+/// structure is invalid.
+#[pyfunction]
+pub fn validate_ensemble(
+    py: Python<'_>,
+    ensemble: &Bound<'_, PyAny>,
+) -> PyResult<PyObject> {
+    // deserialize
+    let ensemble_base: objectiveai::ensemble::EnsembleBase = from_py(ensemble)?;
+    // prepare, validate, and compute ID
+    let ensemble: objectiveai::ensemble::Ensemble =
+        ensemble_base.try_into().map_err(PyValueError::new_err)?;
+    // serialize
+    to_py(py, &ensemble)
+}
+
+/// Validates function input against its schema.
+///
+/// For remote functions, checks whether the provided input conforms to
+/// the function's JSON Schema definition and returns every violation found.
+/// For inline functions, returns `None` since they lack schema definitions.
+///
+/// # Arguments
+///
+/// * `function` - Python dict representing a Function definition
+/// * `input` - Python object representing the function input to validate
+///
+/// # Returns
+///
+/// - An empty list if the input is valid against the schema
+/// - A list of violations if the input is invalid, each with a JSON
+///   pointer `path` to the offending value and the violated constraint
+/// - `None` for inline functions (no schema to validate against)
+///
+/// # Errors
+///
+/// Raises `ValueError` if deserialization fails.
+#[pyfunction]
+pub fn validate_function_input(
+    py: Python<'_>,
+    function: &Bound<'_, PyAny>,
+    input: &Bound<'_, PyAny>,
+) -> PyResult<Option<PyObject>> {
+    // deserialize
+    let function: objectiveai::functions::Function = from_py(function)?;
+    let input: objectiveai::functions::expression::Input = from_py(input)?;
+    // validate input
+    let violations = function.input_violations(&input);
+    // serialize
+    violations
+        .map(|violations| to_py(py, &violations))
+        .transpose()
+}
+
+/// Converts an input schema into a JSON Schema 2020-12 document.
+///
+/// Media types (image, audio, video, file) become references to `$defs`
+/// describing the matching rich content parts, so that the document can be
+/// converted back with `input_schema_from_json_schema`.
+///
+/// # Arguments
+///
+/// * `schema` - Python dict representing an input schema
+///
+/// # Returns
+///
+/// The JSON Schema document.
+///
+/// # Errors
+///
+/// Raises `ValueError` if deserialization fails.
+#[pyfunction]
+pub fn input_schema_to_json_schema(
+    py: Python<'_>,
+    schema: &Bound<'_, PyAny>,
+) -> PyResult<PyObject> {
+    // deserialize
+    let schema: objectiveai::functions::expression::InputSchema =
+        from_py(schema)?;
+    // convert
+    let json_schema = schema.to_json_schema();
+    // serialize
+    to_py(py, &json_schema)
+}
+
+/// Converts a JSON Schema document into an input schema.
+///
+/// Supports the subset of JSON Schema which input schemas can express,
+/// including documents produced by `input_schema_to_json_schema`.
+///
+/// # Arguments
+///
+/// * `json_schema` - Python dict representing a JSON Schema document
+///
+/// # Returns
+///
+/// The input schema.
+///
+/// # Errors
+///
+/// Raises `ValueError` naming the unsupported keyword, invalid value, or
+/// property missing from `required` and its location if the document
+/// cannot be converted.
+#[pyfunction]
+pub fn input_schema_from_json_schema(
+    py: Python<'_>,
+    json_schema: &Bound<'_, PyAny>,
+) -> PyResult<PyObject> {
+    // deserialize
+    let json_schema: serde_json::Value = from_py(json_schema)?;
+    // convert
+    let schema =
+        objectiveai::functions::expression::InputSchema::from_json_schema(
+            &json_schema,
+        )
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    // serialize
+    to_py(py, &schema)
+}
+
+/// Compiles a Function's input_maps expressions for a given input.
+///
+/// Evaluates the `input_maps` expressions to transform the input into a 2D array
+/// that can be referenced by mapped tasks. Each sub-array can be accessed by
+/// tasks via their `map` index.
+///
+/// # Arguments
+///
+/// * `function` - Python dict representing a Function definition
+/// * `input` - Python object representing the function input
+///
+/// # Returns
+///
+/// - A list of input lists if `input_maps` is defined
+/// - `None` if the function has no `input_maps`
+///
+/// # Errors
+///
+/// Raises `ValueError` if expression evaluation fails.
+#[pyfunction]
+pub fn compile_function_input_maps(
+    py: Python<'_>,
+    function: &Bound<'_, PyAny>,
+    input: &Bound<'_, PyAny>,
+) -> PyResult<Option<PyObject>> {
+    // deserialize
+    let function: objectiveai::functions::Function = from_py(function)?;
+    let input: objectiveai::functions::expression::Input = from_py(input)?;
+    // compile input maps
+    let input_maps = function
+        .compile_input_maps(&input)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    // serialize
+    input_maps.map(|maps| to_py(py, &maps)).transpose()
+}
+
+/// Compiles a Function's task expressions for a given input.
+///
+/// Evaluates all JMESPath expressions in the function's tasks using the
+/// provided input data, producing the same tasks the server executes.
+///
+/// # Arguments
+///
+/// * `function` - Python dict representing a Function definition
+/// * `input` - Python object representing the function input
+///
+/// # Returns
+///
+/// A list where each element corresponds to a task definition:
+/// - `None` if the task was skipped (skip expression evaluated to true)
+/// - A task dict for non-mapped tasks
+/// - A list of task dicts for mapped tasks (expanded from input_maps)
+///
+/// # Errors
+///
+/// Raises `ValueError` if expression evaluation fails or types don't match.
+#[pyfunction]
+pub fn compile_function_tasks(
+    py: Python<'_>,
+    function: &Bound<'_, PyAny>,
+    input: &Bound<'_, PyAny>,
+) -> PyResult<PyObject> {
+    // deserialize
+    let function: objectiveai::functions::Function = from_py(function)?;
+    let input: objectiveai::functions::expression::Input = from_py(input)?;
+    // compile tasks
+    let tasks = function
+        .compile_tasks(&input)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    // serialize
+    to_py(py, &tasks)
+}
+
+/// Computes the final output of a Function given input and task results.
+///
+/// Evaluates the function's output expression using the provided input data
+/// and task outputs. Also validates that the output meets constraints:
+/// - Scalar functions: output must be in [0, 1]
+/// - Vector functions: output must sum to approximately 1
+///
+/// # Arguments
+///
+/// * `function` - Python dict representing a Function definition
+/// * `input` - Python object representing the function input
+/// * `task_outputs` - List of task outputs (from actual execution or mocked)
+///
+/// # Returns
+///
+/// A dict with:
+/// - `output`: The computed scalar or vector output
+/// - `valid`: Boolean indicating if the output meets constraints
+///
+/// # Errors
+///
+/// Raises `ValueError` if expression evaluation fails.
+#[pyfunction]
+pub fn compile_function_output(
+    py: Python<'_>,
+    function: &Bound<'_, PyAny>,
+    input: &Bound<'_, PyAny>,
+    task_outputs: &Bound<'_, PyAny>,
+) -> PyResult<PyObject> {
+    // deserialize
+    let function: objectiveai::functions::Function = from_py(function)?;
+    let input: objectiveai::functions::expression::Input = from_py(input)?;
+    let task_outputs: Vec<
+        Option<objectiveai::functions::expression::TaskOutput<'static>>,
+    > = from_py(task_outputs)?;
+    // compile output
+    let output = function
+        .compile_output(&input, &task_outputs)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    // serialize
+    to_py(py, &output)
+}
+
+/// Computes the expected output length for a vector Function.
+///
+/// Evaluates the `output_length` expression to determine how many elements
+/// the output vector should contain. This is only applicable to remote
+/// vector functions which have an `output_length` field.
+///
+/// # Arguments
+///
+/// * `function` - Python dict representing a Function definition
+/// * `input` - Python object representing the function input
+///
+/// # Returns
+///
+/// - The expected output length for remote vector functions
+/// - `None` for scalar functions or inline functions
+///
+/// # Errors
+///
+/// Raises `ValueError` if expression evaluation fails.
+#[pyfunction]
+pub fn compile_function_output_length(
+    function: &Bound<'_, PyAny>,
+    input: &Bound<'_, PyAny>,
+) -> PyResult<Option<u64>> {
+    // deserialize
+    let function: objectiveai::functions::Function = from_py(function)?;
+    let input: objectiveai::functions::expression::Input = from_py(input)?;
+    // compile output length
+    function
+        .compile_output_length(&input)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Compiles the `input_split` expression to split input into multiple sub-inputs.
+///
+/// Used by strategies like Swiss System that need to partition input into
+/// smaller pools. The expression transforms the original input into a list
+/// of inputs, where each element can be processed independently.
+///
+/// # Arguments
+///
+/// * `function` - Python dict representing a Function definition
+/// * `input` - Python object representing the function input to split
+///
+/// # Returns
+///
+/// - A list of split inputs for vector functions with `input_split` defined
+/// - `None` for scalar functions or functions without `input_split`
+///
+/// # Errors
+///
+/// Raises `ValueError` if expression evaluation fails.
+#[pyfunction]
+pub fn compile_function_input_split(
+    py: Python<'_>,
+    function: &Bound<'_, PyAny>,
+    input: &Bound<'_, PyAny>,
+) -> PyResult<Option<PyObject>> {
+    // deserialize
+    let function: objectiveai::functions::Function = from_py(function)?;
+    let input: objectiveai::functions::expression::Input = from_py(input)?;
+    // compile input split
+    let input_split = function
+        .compile_input_split(&input)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    // serialize
+    input_split.map(|split| to_py(py, &split)).transpose()
+}
+
+/// Compiles the `input_merge` expression to merge multiple sub-inputs back into one.
+///
+/// Used by strategies like Swiss System to recombine a subset of split inputs
+/// into a single input for pool execution. The expression transforms a list
+/// of inputs (a subset from `compile_function_input_split`) into a single
+/// merged input.
+///
+/// # Arguments
+///
+/// * `function` - Python dict representing a Function definition
+/// * `input` - List of inputs to merge (typically a subset from `compile_function_input_split`)
+///
+/// # Returns
+///
+/// - The merged input for vector functions with `input_merge` defined
+/// - `None` for scalar functions or functions without `input_merge`
+///
+/// # Errors
+///
+/// Raises `ValueError` if expression evaluation fails.
+#[pyfunction]
+pub fn compile_function_input_merge(
+    py: Python<'_>,
+    function: &Bound<'_, PyAny>,
+    input: &Bound<'_, PyAny>,
+) -> PyResult<Option<PyObject>> {
+    // deserialize
+    let function: objectiveai::functions::Function = from_py(function)?;
+    let input: Vec<objectiveai::functions::expression::Input> = from_py(input)?;
+    // compile input merge
+    let input_merge = function
+        .compile_input_merge(&objectiveai::functions::expression::Input::Array(
+            input,
+        ))
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    // serialize
+    input_merge.map(|merge| to_py(py, &merge)).transpose()
+}
+
+/// Computes a content-addressed ID for chat messages.
+///
+/// Normalizes the messages (consolidates text parts, removes empty content)
+/// and computes a deterministic hash. This ID is used for caching and
+/// deduplicating requests with identical prompts.
+///
+/// # Arguments
+///
+/// * `prompt` - List of chat messages
+///
+/// # Returns
+///
+/// A base62-encoded hash string uniquely identifying the prompt content.
+///
+/// # Errors
+///
+/// Raises `ValueError` if the messages cannot be deserialized.
+#[pyfunction]
+pub fn prompt_id(prompt: &Bound<'_, PyAny>) -> PyResult<String> {
+    // deserialize
+    let mut prompt: Vec<objectiveai::chat::completions::request::Message> =
+        from_py(prompt)?;
+    // prepare and compute ID
+    objectiveai::chat::completions::request::prompt::prepare(&mut prompt);
+    let id = objectiveai::chat::completions::request::prompt::id(&prompt);
+    Ok(id)
+}
+
+/// Computes a content-addressed ID for a tools list.
+///
+/// Computes a deterministic hash for the tools configuration. This ID is
+/// used for caching and deduplicating requests with identical tool sets.
+///
+/// # Arguments
+///
+/// * `tools` - List of tool definitions
+///
+/// # Returns
+///
+/// A base62-encoded hash string uniquely identifying the tools.
+///
+/// # Errors
+///
+/// Raises `ValueError` if the tools cannot be deserialized.
+#[pyfunction]
+pub fn tools_id(tools: &Bound<'_, PyAny>) -> PyResult<String> {
+    // deserialize
+    let tools: Vec<objectiveai::chat::completions::request::Tool> =
+        from_py(tools)?;
+    // compute ID
+    let id = objectiveai::chat::completions::request::tools::id(&tools);
+    Ok(id)
+}
+
+/// Computes a content-addressed ID for a vector completion response option.
+///
+/// Normalizes the response content (consolidates text parts, removes empty
+/// content) and computes a deterministic hash. This ID is used for caching
+/// and identifying individual response options in vector completions.
+///
+/// # Arguments
+///
+/// * `response` - A rich content object (text or multipart content)
+///
+/// # Returns
+///
+/// A base62-encoded hash string uniquely identifying the response content.
+///
+/// # Errors
+///
+/// Raises `ValueError` if the response cannot be deserialized.
+#[pyfunction]
+pub fn vector_response_id(response: &Bound<'_, PyAny>) -> PyResult<String> {
+    // deserialize
+    let mut response: objectiveai::chat::completions::request::RichContent =
+        from_py(response)?;
+    // prepare and compute ID
+    response.prepare();
+    let id = response.id();
+    Ok(id)
+}
+
+/// The `objectiveai_py` Python module.
+#[pymodule]
+fn objectiveai_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(validate_ensemble_llm, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_ensemble, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_function_input, m)?)?;
+    m.add_function(wrap_pyfunction!(input_schema_to_json_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(input_schema_from_json_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(compile_function_input_maps, m)?)?;
+    m.add_function(wrap_pyfunction!(compile_function_tasks, m)?)?;
+    m.add_function(wrap_pyfunction!(compile_function_output, m)?)?;
+    m.add_function(wrap_pyfunction!(compile_function_output_length, m)?)?;
+    m.add_function(wrap_pyfunction!(compile_function_input_split, m)?)?;
+    m.add_function(wrap_pyfunction!(compile_function_input_merge, m)?)?;
+    m.add_function(wrap_pyfunction!(prompt_id, m)?)?;
+    m.add_function(wrap_pyfunction!(tools_id, m)?)?;
+    m.add_function(wrap_pyfunction!(vector_response_id, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,299 @@
+//! HTTP client bindings for the ObjectiveAI API.
+//!
+//! Requests go through the browser's `fetch` API. Unary calls resolve to the
+//! response, and streaming calls resolve to a `ReadableStream` of chunks,
+//! which can be consumed with `for await`.
+
+use futures::StreamExt;
+use objectiveai::{chat, functions, vector};
+use wasm_bindgen::prelude::*;
+
+/// HTTP client for the ObjectiveAI API.
+///
+/// # Example
+///
+/// ```ignore
+/// const client = new Client(undefined, "your-api-key");
+/// const stream = await client.createChatCompletionStreaming({
+///   model: "openai/gpt-4o",
+///   messages: [{ role: "user", content: "Hello" }],
+/// });
+/// for await (const chunk of stream) {
+///   console.log(chunk);
+/// }
+/// ```
+#[wasm_bindgen]
+pub struct Client {
+    inner: objectiveai::HttpClient,
+}
+
+#[wasm_bindgen]
+impl Client {
+    /// Creates a new client.
+    ///
+    /// # Arguments
+    ///
+    /// * `apiBase` - Base URL for API requests (defaults to `https://api.objective-ai.io`)
+    /// * `apiKey` - API key for authentication
+    /// * `userAgent` - Optional User-Agent header value (ignored by browsers)
+    /// * `xTitle` - Optional X-Title header value
+    /// * `referer` - Optional Referer header value
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        apiBase: Option<String>,
+        apiKey: Option<String>,
+        userAgent: Option<String>,
+        xTitle: Option<String>,
+        referer: Option<String>,
+    ) -> Client {
+        Client {
+            inner: objectiveai::HttpClient::new(
+                reqwest::Client::new(),
+                apiBase,
+                apiKey,
+                userAgent,
+                xTitle,
+                referer,
+            ),
+        }
+    }
+
+    /// Creates a chat completion.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - JavaScript object representing chat completion parameters
+    ///
+    /// # Returns
+    ///
+    /// A promise of the chat completion.
+    ///
+    /// # Errors
+    ///
+    /// Rejects with an object with the `code` and `message` of the error.
+    pub fn createChatCompletionUnary(
+        &self,
+        params: JsValue,
+    ) -> js_sys::Promise {
+        let client = self.inner.clone();
+        wasm_bindgen_futures::future_to_promise(async move {
+            // deserialize
+            let params: chat::completions::request::ChatCompletionCreateParams =
+                serde_wasm_bindgen::from_value(params)?;
+            // request
+            let completion = chat::completions::create_chat_completion_unary(
+                &client, params,
+            )
+            .await
+            .map_err(error_to_js)?;
+            // serialize
+            Ok(to_js(&completion)?)
+        })
+    }
+
+    /// Creates a streaming chat completion.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - JavaScript object representing chat completion parameters
+    ///
+    /// # Returns
+    ///
+    /// A promise of a `ReadableStream` of chat completion chunks.
+    ///
+    /// # Errors
+    ///
+    /// Rejects, or errors the stream, with an object with the `code` and
+    /// `message` of the error.
+    pub fn createChatCompletionStreaming(
+        &self,
+        params: JsValue,
+    ) -> js_sys::Promise {
+        let client = self.inner.clone();
+        wasm_bindgen_futures::future_to_promise(async move {
+            // deserialize
+            let params: chat::completions::request::ChatCompletionCreateParams =
+                serde_wasm_bindgen::from_value(params)?;
+            // request
+            let stream = chat::completions::create_chat_completion_streaming(
+                &client, params,
+            )
+            .await
+            .map_err(error_to_js)?;
+            // serialize
+            Ok(readable_stream(stream))
+        })
+    }
+
+    /// Creates a vector completion.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - JavaScript object representing vector completion parameters
+    ///
+    /// # Returns
+    ///
+    /// A promise of the vector completion.
+    ///
+    /// # Errors
+    ///
+    /// Rejects with an object with the `code` and `message` of the error.
+    pub fn createVectorCompletionUnary(
+        &self,
+        params: JsValue,
+    ) -> js_sys::Promise {
+        let client = self.inner.clone();
+        wasm_bindgen_futures::future_to_promise(async move {
+            // deserialize
+            let params: vector::completions::request::VectorCompletionCreateParams =
+                serde_wasm_bindgen::from_value(params)?;
+            // request
+            let completion =
+                vector::completions::create_vector_completion_unary(
+                    &client, params,
+                )
+                .await
+                .map_err(error_to_js)?;
+            // serialize
+            Ok(to_js(&completion)?)
+        })
+    }
+
+    /// Creates a streaming vector completion.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - JavaScript object representing vector completion parameters
+    ///
+    /// # Returns
+    ///
+    /// A promise of a `ReadableStream` of vector completion chunks.
+    ///
+    /// # Errors
+    ///
+    /// Rejects, or errors the stream, with an object with the `code` and
+    /// `message` of the error.
+    pub fn createVectorCompletionStreaming(
+        &self,
+        params: JsValue,
+    ) -> js_sys::Promise {
+        let client = self.inner.clone();
+        wasm_bindgen_futures::future_to_promise(async move {
+            // deserialize
+            let params: vector::completions::request::VectorCompletionCreateParams =
+                serde_wasm_bindgen::from_value(params)?;
+            // request
+            let stream =
+                vector::completions::create_vector_completion_streaming(
+                    &client, params,
+                )
+                .await
+                .map_err(error_to_js)?;
+            // serialize
+            Ok(readable_stream(stream))
+        })
+    }
+
+    /// Creates a function execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - JavaScript object with the request `body`, and for
+    ///   remote Functions or Profiles, the `path` with `fowner`,
+    ///   `frepository`, `fcommit`, `powner`, `prepository`, and `pcommit`
+    ///
+    /// # Returns
+    ///
+    /// A promise of the function execution.
+    ///
+    /// # Errors
+    ///
+    /// Rejects with an object with the `code` and `message` of the error.
+    pub fn createFunctionExecutionUnary(
+        &self,
+        request: JsValue,
+    ) -> js_sys::Promise {
+        let client = self.inner.clone();
+        wasm_bindgen_futures::future_to_promise(async move {
+            // deserialize
+            let request: functions::executions::request::Request =
+                serde_wasm_bindgen::from_value(request)?;
+            // request
+            let execution =
+                functions::executions::create_function_execution_unary(
+                    &client, request,
+                )
+                .await
+                .map_err(error_to_js)?;
+            // serialize
+            Ok(to_js(&execution)?)
+        })
+    }
+
+    /// Creates a streaming function execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - JavaScript object with the request `body`, and for
+    ///   remote Functions or Profiles, the `path` with `fowner`,
+    ///   `frepository`, `fcommit`, `powner`, `prepository`, and `pcommit`
+    ///
+    /// # Returns
+    ///
+    /// A promise of a `ReadableStream` of function execution chunks.
+    ///
+    /// # Errors
+    ///
+    /// Rejects, or errors the stream, with an object with the `code` and
+    /// `message` of the error.
+    pub fn createFunctionExecutionStreaming(
+        &self,
+        request: JsValue,
+    ) -> js_sys::Promise {
+        let client = self.inner.clone();
+        wasm_bindgen_futures::future_to_promise(async move {
+            // deserialize
+            let request: functions::executions::request::Request =
+                serde_wasm_bindgen::from_value(request)?;
+            // request
+            let stream =
+                functions::executions::create_function_execution_streaming(
+                    &client, request,
+                )
+                .await
+                .map_err(error_to_js)?;
+            // serialize
+            Ok(readable_stream(stream))
+        })
+    }
+}
+
+/// Serializes a value into plain JavaScript objects and arrays.
+///
+/// Maps such as inputs and outputs become objects rather than the `Map`s
+/// `serde_wasm_bindgen::to_value` produces, so that responses have the same
+/// shape as the JSON returned by the API.
+fn to_js<T: serde::Serialize>(
+    value: &T,
+) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+}
+
+/// Converts an HTTP error into an object with its `code` and `message`.
+fn error_to_js(error: objectiveai::HttpError) -> JsValue {
+    let error = objectiveai::error::ResponseError::from(&error);
+    to_js(&error).unwrap_or_else(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Converts a stream of chunks into a JavaScript `ReadableStream`.
+fn readable_stream<T: serde::Serialize>(
+    stream: impl futures::Stream<Item = Result<T, objectiveai::HttpError>> + 'static,
+) -> JsValue {
+    let stream = stream.map(|chunk| match chunk {
+        Ok(chunk) => to_js(&chunk).map_err(Into::into),
+        Err(e) => Err(error_to_js(e)),
+    });
+    wasm_streams::ReadableStream::from_stream(stream)
+        .into_raw()
+        .into()
+}
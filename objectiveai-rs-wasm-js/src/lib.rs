@@ -7,6 +7,7 @@
 //! - Compute content-addressed IDs (deterministic hashes)
 //! - Compile Function expressions for previewing during authoring
 //! - Compute prompt, tools, and response IDs for caching/deduplication
+//! - Call the ObjectiveAI API from the browser, streaming typed chunks
 //!
 //! # Usage
 //!
@@ -19,13 +20,24 @@
 //! - [`validateEnsemble`] - Validate and compute ID for an Ensemble
 //! - [`compileFunctionTasks`] - Compile function tasks for a given input
 //! - [`compileFunctionOutput`] - Compile function output from task results
+//! - [`inputSchemaToJsonSchema`] - Convert an input schema to JSON Schema
+//! - [`inputSchemaFromJsonSchema`] - Convert JSON Schema to an input schema
 //! - [`promptId`] - Compute content-addressed ID for chat messages
 //! - [`toolsId`] - Compute content-addressed ID for tools
 //! - [`vectorResponseId`] - Compute content-addressed ID for a response option
+//!
+//! # Client
+//!
+//! - [`Client`] - HTTP client for chat completions, vector completions, and
+//!   function executions, using the browser's `fetch` API
 
 #![allow(non_snake_case)]
 use wasm_bindgen::prelude::*;
 
+mod client;
+
+pub use client::*;
+
 /// Validates an Ensemble LLM configuration and computes its content-addressed ID.
 ///
 /// Takes an Ensemble LLM definition, normalizes it (removes defaults, deduplicates),
@@ -92,8 +104,8 @@ pub fn validateEnsemble(ensemble: JsValue) -> Result<JsValue, JsValue> {
 /// Validates function input against its schema.
 ///
 /// For remote functions, checks whether the provided input conforms to
-/// the function's JSON Schema definition. For inline functions, returns
-/// `null` since they lack schema definitions.
+/// the function's JSON Schema definition and returns every violation found.
+/// For inline functions, returns `null` since they lack schema definitions.
 ///
 /// # Arguments
 ///
@@ -102,8 +114,9 @@ pub fn validateEnsemble(ensemble: JsValue) -> Result<JsValue, JsValue> {
 ///
 /// # Returns
 ///
-/// - `true` if the input is valid against the schema
-/// - `false` if the input is invalid
+/// - An empty array if the input is valid against the schema
+/// - An array of violations if the input is invalid, each with a JSON
+///   pointer `path` to the offending value and the violated constraint
 /// - `null` for inline functions (no schema to validate against)
 ///
 /// # Errors
@@ -113,14 +126,84 @@ pub fn validateEnsemble(ensemble: JsValue) -> Result<JsValue, JsValue> {
 pub fn validateFunctionInput(
     function: JsValue,
     input: JsValue,
-) -> Result<Option<bool>, JsValue> {
+) -> Result<Option<JsValue>, JsValue> {
     // deserialize
     let function: objectiveai::functions::Function =
         serde_wasm_bindgen::from_value(function)?;
     let input: objectiveai::functions::expression::Input =
         serde_wasm_bindgen::from_value(input)?;
     // validate input
-    Ok(function.validate_input(&input))
+    let violations = function.input_violations(&input);
+    // serialize
+    let violations: Option<JsValue> = violations
+        .map(|violations| serde_wasm_bindgen::to_value(&violations))
+        .transpose()?;
+    Ok(violations)
+}
+
+/// Converts an input schema into a JSON Schema 2020-12 document.
+///
+/// Media types (image, audio, video, file) become references to `$defs`
+/// describing the matching rich content parts, so that the document can be
+/// converted back with `inputSchemaFromJsonSchema`.
+///
+/// # Arguments
+///
+/// * `schema` - JavaScript object representing an input schema
+///
+/// # Returns
+///
+/// The JSON Schema document.
+///
+/// # Errors
+///
+/// Returns an error if deserialization fails.
+#[wasm_bindgen]
+pub fn inputSchemaToJsonSchema(schema: JsValue) -> Result<JsValue, JsValue> {
+    // deserialize
+    let schema: objectiveai::functions::expression::InputSchema =
+        serde_wasm_bindgen::from_value(schema)?;
+    // convert
+    let json_schema = schema.to_json_schema();
+    // serialize
+    let json_schema: JsValue = serde_wasm_bindgen::to_value(&json_schema)?;
+    Ok(json_schema)
+}
+
+/// Converts a JSON Schema document into an input schema.
+///
+/// Supports the subset of JSON Schema which input schemas can express,
+/// including documents produced by `inputSchemaToJsonSchema`.
+///
+/// # Arguments
+///
+/// * `jsonSchema` - JavaScript object representing a JSON Schema document
+///
+/// # Returns
+///
+/// The input schema.
+///
+/// # Errors
+///
+/// Returns an error string naming the unsupported keyword, invalid value,
+/// or property missing from `required` and its location if the document
+/// cannot be converted.
+#[wasm_bindgen]
+pub fn inputSchemaFromJsonSchema(
+    jsonSchema: JsValue,
+) -> Result<JsValue, JsValue> {
+    // deserialize
+    let json_schema: serde_json::Value =
+        serde_wasm_bindgen::from_value(jsonSchema)?;
+    // convert
+    let schema =
+        objectiveai::functions::expression::InputSchema::from_json_schema(
+            &json_schema,
+        )
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    // serialize
+    let schema: JsValue = serde_wasm_bindgen::to_value(&schema)?;
+    Ok(schema)
 }
 
 /// Compiles a Function's input_maps expressions for a given input.
@@ -0,0 +1,58 @@
+//! Blocking HTTP functions for authentication endpoints.
+
+use crate::{BlockingHttpClient, HttpError};
+
+/// Blocking version of [`super::create_api_key`].
+pub fn create_api_key(
+    client: &BlockingHttpClient,
+    request: super::request::CreateApiKeyRequest,
+) -> Result<super::response::CreateApiKeyResponse, HttpError> {
+    client.block_on(super::create_api_key(&client.inner, request))
+}
+
+/// Blocking version of [`super::create_openrouter_byok_api_key`].
+pub fn create_openrouter_byok_api_key(
+    client: &BlockingHttpClient,
+    request: super::request::CreateOpenRouterByokApiKeyRequest,
+) -> Result<super::response::CreateOpenRouterByokApiKeyResponse, HttpError> {
+    client.block_on(super::create_openrouter_byok_api_key(
+        &client.inner,
+        request,
+    ))
+}
+
+/// Blocking version of [`super::disable_api_key`].
+pub fn disable_api_key(
+    client: &BlockingHttpClient,
+    request: super::request::DisableApiKeyRequest,
+) -> Result<super::response::DisableApiKeyResponse, HttpError> {
+    client.block_on(super::disable_api_key(&client.inner, request))
+}
+
+/// Blocking version of [`super::delete_openrouter_byok_api_key`].
+pub fn delete_openrouter_byok_api_key(
+    client: &BlockingHttpClient,
+) -> Result<(), HttpError> {
+    client.block_on(super::delete_openrouter_byok_api_key(&client.inner))
+}
+
+/// Blocking version of [`super::list_api_keys`].
+pub fn list_api_keys(
+    client: &BlockingHttpClient,
+) -> Result<super::response::ListApiKeyResponse, HttpError> {
+    client.block_on(super::list_api_keys(&client.inner))
+}
+
+/// Blocking version of [`super::get_openrouter_byok_api_key`].
+pub fn get_openrouter_byok_api_key(
+    client: &BlockingHttpClient,
+) -> Result<super::response::GetOpenRouterByokApiKeyResponse, HttpError> {
+    client.block_on(super::get_openrouter_byok_api_key(&client.inner))
+}
+
+/// Blocking version of [`super::get_credits`].
+pub fn get_credits(
+    client: &BlockingHttpClient,
+) -> Result<super::response::GetCreditsResponse, HttpError> {
+    client.block_on(super::get_credits(&client.inner))
+}
@@ -25,3 +25,6 @@ mod http;
 
 #[cfg(feature = "http")]
 pub use http::*;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
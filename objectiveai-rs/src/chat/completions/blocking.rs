@@ -0,0 +1,27 @@
+//! Blocking HTTP functions for chat completions.
+
+use crate::{BlockingHttpClient, BlockingStream, HttpError};
+
+/// Blocking version of [`super::create_chat_completion_unary`].
+pub fn create_chat_completion_unary(
+    client: &BlockingHttpClient,
+    params: super::request::ChatCompletionCreateParams,
+) -> Result<super::response::unary::ChatCompletion, HttpError> {
+    client.block_on(super::create_chat_completion_unary(&client.inner, params))
+}
+
+/// Blocking version of [`super::create_chat_completion_streaming`].
+pub fn create_chat_completion_streaming(
+    client: &BlockingHttpClient,
+    params: super::request::ChatCompletionCreateParams,
+) -> Result<
+    BlockingStream<
+        Result<super::response::streaming::ChatCompletionChunk, HttpError>,
+    >,
+    HttpError,
+> {
+    client.block_on_stream(super::create_chat_completion_streaming(
+        &client.inner,
+        params,
+    ))
+}
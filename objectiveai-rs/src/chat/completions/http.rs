@@ -1,6 +1,6 @@
 //! HTTP functions for chat completions.
 
-use crate::{HttpClient, HttpError};
+use crate::{HttpClient, HttpError, MaybeSend};
 use futures::Stream;
 
 /// Creates a chat completion (non-streaming).
@@ -49,7 +49,7 @@ pub async fn create_chat_completion_streaming(
             HttpError,
         >,
     >
-    + Send
+    + MaybeSend
     + 'static
     + use<>,
     HttpError,
@@ -78,6 +78,42 @@ pub enum MessageExpression {
 }
 
 impl MessageExpression {
+    /// Creates a developer message expression with the given content.
+    pub fn developer(
+        content: impl Into<
+            functions::expression::WithExpression<SimpleContentExpression>,
+        >,
+    ) -> Self {
+        MessageExpression::Developer(DeveloperMessageExpression {
+            content: content.into(),
+            name: None,
+        })
+    }
+
+    /// Creates a system message expression with the given content.
+    pub fn system(
+        content: impl Into<
+            functions::expression::WithExpression<SimpleContentExpression>,
+        >,
+    ) -> Self {
+        MessageExpression::System(SystemMessageExpression {
+            content: content.into(),
+            name: None,
+        })
+    }
+
+    /// Creates a user message expression with the given content.
+    pub fn user(
+        content: impl Into<
+            functions::expression::WithExpression<RichContentExpression>,
+        >,
+    ) -> Self {
+        MessageExpression::User(UserMessageExpression {
+            content: content.into(),
+            name: None,
+        })
+    }
+
     /// Compiles the expression into a concrete [`Message`].
     ///
     /// Evaluates all JMESPath expressions using the provided parameters
@@ -578,6 +614,26 @@ pub enum SimpleContentExpression {
     ),
 }
 
+impl From<&str>
+    for functions::expression::WithExpression<SimpleContentExpression>
+{
+    fn from(text: &str) -> Self {
+        functions::expression::WithExpression::Value(
+            SimpleContentExpression::Text(text.to_string()),
+        )
+    }
+}
+
+impl From<String>
+    for functions::expression::WithExpression<SimpleContentExpression>
+{
+    fn from(text: String) -> Self {
+        functions::expression::WithExpression::Value(
+            SimpleContentExpression::Text(text),
+        )
+    }
+}
+
 impl SimpleContentExpression {
     /// Compiles the expression into a concrete [`SimpleContent`].
     pub fn compile(
@@ -739,6 +795,26 @@ pub enum RichContentExpression {
     ),
 }
 
+impl From<&str>
+    for functions::expression::WithExpression<RichContentExpression>
+{
+    fn from(text: &str) -> Self {
+        functions::expression::WithExpression::Value(
+            RichContentExpression::Text(text.to_string()),
+        )
+    }
+}
+
+impl From<String>
+    for functions::expression::WithExpression<RichContentExpression>
+{
+    fn from(text: String) -> Self {
+        functions::expression::WithExpression::Value(
+            RichContentExpression::Text(text),
+        )
+    }
+}
+
 impl RichContentExpression {
     /// Compiles the expression into a concrete [`RichContent`].
     pub fn compile(
@@ -31,6 +31,10 @@ pub struct Usage {
     pub cost_multiplier: rust_decimal::Decimal,
     /// Whether this request used Bring Your Own Key (BYOK).
     pub is_byok: bool,
+    /// Milliseconds spent waiting for upstream rate limits before the
+    /// request was sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_wait_time: Option<u64>,
 }
 
 impl Usage {
@@ -74,6 +78,9 @@ impl Usage {
             _ => {}
         }
         self.total_cost += other.total_cost;
+        if let Some(other_queue_wait_time) = other.queue_wait_time {
+            *self.queue_wait_time.get_or_insert(0) += other_queue_wait_time;
+        }
     }
 }
 
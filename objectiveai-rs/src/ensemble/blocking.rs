@@ -0,0 +1,26 @@
+//! Blocking HTTP functions for Ensemble endpoints.
+
+use crate::{BlockingHttpClient, HttpError};
+
+/// Blocking version of [`super::list_ensembles`].
+pub fn list_ensembles(
+    client: &BlockingHttpClient,
+) -> Result<super::response::ListEnsemble, HttpError> {
+    client.block_on(super::list_ensembles(&client.inner))
+}
+
+/// Blocking version of [`super::get_ensemble`].
+pub fn get_ensemble(
+    client: &BlockingHttpClient,
+    ensemble_id: &str,
+) -> Result<super::response::GetEnsemble, HttpError> {
+    client.block_on(super::get_ensemble(&client.inner, ensemble_id))
+}
+
+/// Blocking version of [`super::get_ensemble_usage`].
+pub fn get_ensemble_usage(
+    client: &BlockingHttpClient,
+    ensemble_id: &str,
+) -> Result<super::response::UsageEnsemble, HttpError> {
+    client.block_on(super::get_ensemble_usage(&client.inner, ensemble_id))
+}
@@ -0,0 +1,44 @@
+//! Builder for Ensembles.
+
+use crate::ensemble_llm;
+
+/// Builds an [`EnsembleBase`](super::EnsembleBase).
+///
+/// [`build`](Self::build) validates the Ensemble as the API would, including
+/// that it contains between 1 and 128 total LLMs.
+///
+/// # Example
+///
+/// ```ignore
+/// let ensemble = EnsembleBuilder::new()
+///     .llm(EnsembleLlmBuilder::new("openai/gpt-4o").count(2).build()?)
+///     .llm(EnsembleLlmBuilder::new("google/gemini-2.5-pro").build()?)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EnsembleBuilder {
+    llms: Vec<ensemble_llm::EnsembleLlmBaseWithFallbacksAndCount>,
+}
+
+impl EnsembleBuilder {
+    /// Creates a builder for an Ensemble with no LLMs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an LLM to the Ensemble.
+    pub fn llm(
+        mut self,
+        llm: ensemble_llm::EnsembleLlmBaseWithFallbacksAndCount,
+    ) -> Self {
+        self.llms.push(llm);
+        self
+    }
+
+    /// Validates the Ensemble and returns it.
+    pub fn build(self) -> Result<super::EnsembleBase, String> {
+        let ensemble = super::EnsembleBase { llms: self.llms };
+        super::Ensemble::try_from(ensemble.clone())?;
+        Ok(ensemble)
+    }
+}
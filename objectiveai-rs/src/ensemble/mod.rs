@@ -65,9 +65,11 @@
 //! println!("Ensemble ID: {}", ensemble.id);
 //! ```
 
+mod builder;
 mod ensemble;
 pub mod response;
 
+pub use builder::*;
 pub use ensemble::*;
 
 #[cfg(feature = "http")]
@@ -75,3 +77,6 @@ mod http;
 
 #[cfg(feature = "http")]
 pub use http::*;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
@@ -0,0 +1,29 @@
+//! Blocking HTTP functions for Ensemble LLM endpoints.
+
+use crate::{BlockingHttpClient, HttpError};
+
+/// Blocking version of [`super::list_ensemble_llms`].
+pub fn list_ensemble_llms(
+    client: &BlockingHttpClient,
+) -> Result<super::response::ListEnsembleLlm, HttpError> {
+    client.block_on(super::list_ensemble_llms(&client.inner))
+}
+
+/// Blocking version of [`super::get_ensemble_llm`].
+pub fn get_ensemble_llm(
+    client: &BlockingHttpClient,
+    ensemble_llm_id: &str,
+) -> Result<super::response::GetEnsembleLlm, HttpError> {
+    client.block_on(super::get_ensemble_llm(&client.inner, ensemble_llm_id))
+}
+
+/// Blocking version of [`super::get_ensemble_llm_usage`].
+pub fn get_ensemble_llm_usage(
+    client: &BlockingHttpClient,
+    ensemble_llm_id: &str,
+) -> Result<super::response::UsageEnsembleLlm, HttpError> {
+    client.block_on(super::get_ensemble_llm_usage(
+        &client.inner,
+        ensemble_llm_id,
+    ))
+}
@@ -0,0 +1,200 @@
+//! Builder for Ensemble LLMs.
+
+use crate::chat;
+use indexmap::IndexMap;
+
+/// Builds an [`EnsembleLlmBaseWithFallbacksAndCount`](super::EnsembleLlmBaseWithFallbacksAndCount).
+///
+/// The model is required up front, and every other parameter is optional.
+/// [`build`](Self::build) validates the LLM and its fallbacks.
+///
+/// # Example
+///
+/// ```ignore
+/// let llm = EnsembleLlmBuilder::new("openai/gpt-4o")
+///     .output_mode(OutputMode::JsonSchema)
+///     .temperature(0.7)
+///     .count(3)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct EnsembleLlmBuilder {
+    base: super::EnsembleLlmBase,
+    count: u64,
+    fallbacks: Vec<super::EnsembleLlmBase>,
+}
+
+impl EnsembleLlmBuilder {
+    /// Creates a builder for an LLM using the given upstream model.
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            base: super::EnsembleLlmBase {
+                model: model.into(),
+                ..Default::default()
+            },
+            count: 1,
+            fallbacks: Vec::new(),
+        }
+    }
+
+    /// Sets the number of instances of this LLM in the ensemble.
+    pub fn count(mut self, count: u64) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Adds a fallback LLM, tried in order if the primary fails.
+    pub fn fallback(mut self, fallback: super::EnsembleLlmBase) -> Self {
+        self.fallbacks.push(fallback);
+        self
+    }
+
+    /// Sets the output mode for vector completions.
+    pub fn output_mode(mut self, output_mode: super::OutputMode) -> Self {
+        self.base.output_mode = output_mode;
+        self
+    }
+
+    /// Enables synthetic reasoning for vector completions.
+    pub fn synthetic_reasoning(mut self, synthetic_reasoning: bool) -> Self {
+        self.base.synthetic_reasoning = Some(synthetic_reasoning);
+        self
+    }
+
+    /// Sets the number of top log probabilities to vote with.
+    pub fn top_logprobs(mut self, top_logprobs: u64) -> Self {
+        self.base.top_logprobs = Some(top_logprobs);
+        self
+    }
+
+    /// Adds a message prepended to the user's prompt.
+    pub fn prefix_message(
+        mut self,
+        message: chat::completions::request::Message,
+    ) -> Self {
+        self.base
+            .prefix_messages
+            .get_or_insert_with(Vec::new)
+            .push(message);
+        self
+    }
+
+    /// Adds a message appended after the user's prompt.
+    pub fn suffix_message(
+        mut self,
+        message: chat::completions::request::Message,
+    ) -> Self {
+        self.base
+            .suffix_messages
+            .get_or_insert_with(Vec::new)
+            .push(message);
+        self
+    }
+
+    /// Sets the frequency penalty (-2.0 to 2.0).
+    pub fn frequency_penalty(mut self, frequency_penalty: f64) -> Self {
+        self.base.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    /// Sets the token ID to bias mapping (-100 to 100).
+    pub fn logit_bias(mut self, logit_bias: IndexMap<String, i64>) -> Self {
+        self.base.logit_bias = Some(logit_bias);
+        self
+    }
+
+    /// Sets the maximum tokens in the completion.
+    pub fn max_completion_tokens(mut self, max_completion_tokens: u64) -> Self {
+        self.base.max_completion_tokens = Some(max_completion_tokens);
+        self
+    }
+
+    /// Sets the presence penalty (-2.0 to 2.0).
+    pub fn presence_penalty(mut self, presence_penalty: f64) -> Self {
+        self.base.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    /// Sets the stop sequences.
+    pub fn stop(mut self, stop: super::Stop) -> Self {
+        self.base.stop = Some(stop);
+        self
+    }
+
+    /// Sets the sampling temperature (0.0 to 2.0).
+    pub fn temperature(mut self, temperature: f64) -> Self {
+        self.base.temperature = Some(temperature);
+        self
+    }
+
+    /// Sets the nucleus sampling probability (0.0 to 1.0).
+    pub fn top_p(mut self, top_p: f64) -> Self {
+        self.base.top_p = Some(top_p);
+        self
+    }
+
+    /// Sets the maximum tokens (OpenRouter variant).
+    pub fn max_tokens(mut self, max_tokens: u64) -> Self {
+        self.base.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Sets the minimum probability threshold for sampling (0.0 to 1.0).
+    pub fn min_p(mut self, min_p: f64) -> Self {
+        self.base.min_p = Some(min_p);
+        self
+    }
+
+    /// Sets the provider routing preferences.
+    pub fn provider(mut self, provider: super::Provider) -> Self {
+        self.base.provider = Some(provider);
+        self
+    }
+
+    /// Sets the reasoning configuration.
+    pub fn reasoning(mut self, reasoning: super::Reasoning) -> Self {
+        self.base.reasoning = Some(reasoning);
+        self
+    }
+
+    /// Sets the repetition penalty (0.0 to 2.0).
+    pub fn repetition_penalty(mut self, repetition_penalty: f64) -> Self {
+        self.base.repetition_penalty = Some(repetition_penalty);
+        self
+    }
+
+    /// Sets the top-a sampling parameter (0.0 to 1.0).
+    pub fn top_a(mut self, top_a: f64) -> Self {
+        self.base.top_a = Some(top_a);
+        self
+    }
+
+    /// Sets the top-k sampling parameter.
+    pub fn top_k(mut self, top_k: u64) -> Self {
+        self.base.top_k = Some(top_k);
+        self
+    }
+
+    /// Sets the output verbosity hint.
+    pub fn verbosity(mut self, verbosity: super::Verbosity) -> Self {
+        self.base.verbosity = Some(verbosity);
+        self
+    }
+
+    /// Validates the LLM and its fallbacks and returns it.
+    pub fn build(
+        self,
+    ) -> Result<super::EnsembleLlmBaseWithFallbacksAndCount, String> {
+        let llm = super::EnsembleLlmBaseWithFallbacksAndCount {
+            count: self.count,
+            inner: self.base,
+            fallbacks: if self.fallbacks.is_empty() {
+                None
+            } else {
+                Some(self.fallbacks)
+            },
+        };
+        super::EnsembleLlmWithFallbacksAndCount::try_from(llm.clone())?;
+        Ok(llm)
+    }
+}
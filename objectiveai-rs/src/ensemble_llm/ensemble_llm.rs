@@ -32,7 +32,7 @@ pub struct EnsembleLlmBase {
     ///
     /// When enabled, forces the LLM to output a `_think` field before voting,
     /// simulating chain-of-thought reasoning. Requires `output_mode` to be
-    /// `JsonSchema` or `ToolCall` (not `Instruction`).
+    /// `JsonSchema`, `ToolCall`, or `Grammar` (not `Instruction`).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub synthetic_reasoning: Option<bool>,
 
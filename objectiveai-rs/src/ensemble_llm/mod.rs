@@ -42,6 +42,7 @@
 //! println!("ID: {}", llm.id); // Deterministic content-addressed ID
 //! ```
 
+mod builder;
 mod ensemble_llm;
 mod output_mode;
 mod provider;
@@ -50,6 +51,7 @@ pub mod response;
 mod stop;
 mod verbosity;
 
+pub use builder::*;
 pub use ensemble_llm::*;
 pub use output_mode::*;
 pub use provider::*;
@@ -62,3 +64,6 @@ mod http;
 
 #[cfg(feature = "http")]
 pub use http::*;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
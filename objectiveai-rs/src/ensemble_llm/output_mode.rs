@@ -28,6 +28,12 @@ pub enum OutputMode {
     ///
     /// Requires model support for tool/function calling.
     ToolCall,
+    /// A decoding grammar (GBNF, regex, or choice list) built from the
+    /// possible keys.
+    ///
+    /// Intended for self-hosted OpenAI-compatible servers such as llama.cpp
+    /// or vLLM, which guarantee exactly one valid key per response.
+    Grammar,
 }
 
 impl std::default::Default for OutputMode {
@@ -0,0 +1,89 @@
+//! Blocking HTTP functions for Function endpoints.
+
+use crate::{BlockingHttpClient, HttpError};
+
+/// Blocking version of [`super::list_functions`].
+pub fn list_functions(
+    client: &BlockingHttpClient,
+) -> Result<super::response::ListFunction, HttpError> {
+    client.block_on(super::list_functions(&client.inner))
+}
+
+/// Blocking version of [`super::get_function`].
+pub fn get_function(
+    client: &BlockingHttpClient,
+    owner: &str,
+    repository: &str,
+    commit: Option<&str>,
+) -> Result<super::response::GetFunction, HttpError> {
+    client.block_on(super::get_function(
+        &client.inner,
+        owner,
+        repository,
+        commit,
+    ))
+}
+
+/// Blocking version of [`super::get_function_usage`].
+pub fn get_function_usage(
+    client: &BlockingHttpClient,
+    fowner: &str,
+    frepository: &str,
+    fcommit: Option<&str>,
+) -> Result<super::response::UsageFunction, HttpError> {
+    client.block_on(super::get_function_usage(
+        &client.inner,
+        fowner,
+        frepository,
+        fcommit,
+    ))
+}
+
+/// Blocking version of [`super::list_function_profile_pairs`].
+pub fn list_function_profile_pairs(
+    client: &BlockingHttpClient,
+) -> Result<super::response::ListFunctionProfilePair, HttpError> {
+    client.block_on(super::list_function_profile_pairs(&client.inner))
+}
+
+/// Blocking version of [`super::get_function_profile_pair`].
+pub fn get_function_profile_pair(
+    client: &BlockingHttpClient,
+    fowner: &str,
+    frepository: &str,
+    fcommit: Option<&str>,
+    powner: &str,
+    prepository: &str,
+    pcommit: Option<&str>,
+) -> Result<super::response::GetFunctionProfilePair, HttpError> {
+    client.block_on(super::get_function_profile_pair(
+        &client.inner,
+        fowner,
+        frepository,
+        fcommit,
+        powner,
+        prepository,
+        pcommit,
+    ))
+}
+
+/// Blocking version of [`super::get_function_profile_pair_usage`].
+pub fn get_function_profile_pair_usage(
+    client: &BlockingHttpClient,
+    fowner: &str,
+    frepository: &str,
+    fcommit: Option<&str>,
+    powner: &str,
+    prepository: &str,
+    pcommit: Option<&str>,
+) -> Result<super::response::UsageFunctionProfilePair, HttpError> {
+    client.block_on(super::get_function_profile_pair_usage(
+        &client.inner,
+        fowner,
+        frepository,
+        fcommit,
+        powner,
+        prepository,
+        pcommit,
+    ))
+}
@@ -0,0 +1,411 @@
+//! Builders for inline Functions and their tasks.
+
+use crate::chat;
+
+/// Builds a scalar [`InlineFunction`](super::InlineFunction).
+///
+/// The output expression is required up front. Use [`Expression::new`]
+/// to write `$jmespath` expressions.
+///
+/// # Example
+///
+/// ```ignore
+/// let function = ScalarFunctionBuilder::new(Expression::new("tasks[0].scores[0]"))
+///     .task(
+///         VectorCompletionTaskBuilder::new()
+///             .message(MessageExpression::user(Expression::new("input.joke")))
+///             .response("funny")
+///             .response("not funny")
+///             .build()?,
+///     )
+///     .build()?;
+/// ```
+///
+/// [`Expression::new`]: super::expression::Expression::new
+#[derive(Debug, Clone)]
+pub struct ScalarFunctionBuilder {
+    input_maps: Option<super::expression::InputMaps>,
+    tasks: Vec<super::TaskExpression>,
+    output: super::expression::Expression,
+}
+
+impl ScalarFunctionBuilder {
+    /// Creates a builder for a Function computing its score with `output`.
+    pub fn new(output: super::expression::Expression) -> Self {
+        Self {
+            input_maps: None,
+            tasks: Vec::new(),
+            output,
+        }
+    }
+
+    /// Sets the expressions producing the sub-arrays for mapped tasks.
+    pub fn input_maps(
+        mut self,
+        input_maps: super::expression::InputMaps,
+    ) -> Self {
+        self.input_maps = Some(input_maps);
+        self
+    }
+
+    /// Adds a task.
+    pub fn task(mut self, task: super::TaskExpression) -> Self {
+        self.tasks.push(task);
+        self
+    }
+
+    /// Validates the Function and returns it.
+    pub fn build(self) -> Result<super::InlineFunction, String> {
+        validate_task_maps(self.input_maps.as_ref(), &self.tasks)?;
+        Ok(super::InlineFunction::Scalar {
+            input_maps: self.input_maps,
+            tasks: self.tasks,
+            output: self.output,
+        })
+    }
+}
+
+/// Builds a vector [`InlineFunction`](super::InlineFunction).
+///
+/// The output expression is required up front. See
+/// [`ScalarFunctionBuilder`] for an example.
+#[derive(Debug, Clone)]
+pub struct VectorFunctionBuilder {
+    input_maps: Option<super::expression::InputMaps>,
+    tasks: Vec<super::TaskExpression>,
+    output: super::expression::Expression,
+    input_split: Option<
+        super::expression::WithExpression<Vec<super::expression::Input>>,
+    >,
+    input_merge:
+        Option<super::expression::WithExpression<super::expression::Input>>,
+}
+
+impl VectorFunctionBuilder {
+    /// Creates a builder for a Function computing its scores with `output`.
+    pub fn new(output: super::expression::Expression) -> Self {
+        Self {
+            input_maps: None,
+            tasks: Vec::new(),
+            output,
+            input_split: None,
+            input_merge: None,
+        }
+    }
+
+    /// Sets the expressions producing the sub-arrays for mapped tasks.
+    pub fn input_maps(
+        mut self,
+        input_maps: super::expression::InputMaps,
+    ) -> Self {
+        self.input_maps = Some(input_maps);
+        self
+    }
+
+    /// Adds a task.
+    pub fn task(mut self, task: super::TaskExpression) -> Self {
+        self.tasks.push(task);
+        self
+    }
+
+    /// Sets the expression splitting an input into one input per output
+    /// element.
+    pub fn input_split(
+        mut self,
+        input_split: impl Into<
+            super::expression::WithExpression<Vec<super::expression::Input>>,
+        >,
+    ) -> Self {
+        self.input_split = Some(input_split.into());
+        self
+    }
+
+    /// Sets the expression merging split inputs back into a single input.
+    pub fn input_merge(
+        mut self,
+        input_merge: impl Into<
+            super::expression::WithExpression<super::expression::Input>,
+        >,
+    ) -> Self {
+        self.input_merge = Some(input_merge.into());
+        self
+    }
+
+    /// Validates the Function and returns it.
+    pub fn build(self) -> Result<super::InlineFunction, String> {
+        validate_task_maps(self.input_maps.as_ref(), &self.tasks)?;
+        Ok(super::InlineFunction::Vector {
+            input_maps: self.input_maps,
+            tasks: self.tasks,
+            output: self.output,
+            input_split: self.input_split,
+            input_merge: self.input_merge,
+        })
+    }
+}
+
+fn validate_task_maps(
+    input_maps: Option<&super::expression::InputMaps>,
+    tasks: &[super::TaskExpression],
+) -> Result<(), String> {
+    if input_maps.is_none()
+        && let Some(i) =
+            tasks.iter().position(|task| task.input_map().is_some())
+    {
+        return Err(format!(
+            "task {} is mapped, but the Function has no `input_maps`",
+            i
+        ));
+    }
+    Ok(())
+}
+
+/// Builds a [`TaskExpression`](super::TaskExpression) running a vector
+/// completion.
+///
+/// Messages and responses may be added one at a time, each a literal or an
+/// expression, or provided all at once by a single expression.
+#[derive(Debug, Clone)]
+pub struct VectorCompletionTaskBuilder {
+    task: super::VectorCompletionTaskExpression,
+}
+
+impl Default for VectorCompletionTaskBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VectorCompletionTaskBuilder {
+    /// Creates a builder for a task with no messages or responses.
+    pub fn new() -> Self {
+        Self {
+            task: super::VectorCompletionTaskExpression {
+                skip: None,
+                map: None,
+                messages: super::expression::WithExpression::Value(Vec::new()),
+                tools: None,
+                responses: super::expression::WithExpression::Value(Vec::new()),
+            },
+        }
+    }
+
+    /// Skips the task when the expression evaluates to true.
+    pub fn skip(mut self, skip: super::expression::Expression) -> Self {
+        self.task.skip = Some(skip);
+        self
+    }
+
+    /// Maps the task over `input_maps[map]`.
+    pub fn map(mut self, map: u64) -> Self {
+        self.task.map = Some(map);
+        self
+    }
+
+    /// Adds a message to the prompt.
+    pub fn message(
+        self,
+        message: chat::completions::request::MessageExpression,
+    ) -> Self {
+        self.push_message(super::expression::WithExpression::Value(message))
+    }
+
+    /// Adds the messages produced by an expression to the prompt.
+    pub fn message_expression(
+        self,
+        expression: super::expression::Expression,
+    ) -> Self {
+        self.push_message(super::expression::WithExpression::Expression(
+            expression,
+        ))
+    }
+
+    /// Sets the entire prompt to the messages produced by an expression,
+    /// replacing any messages added so far.
+    pub fn messages(
+        mut self,
+        expression: super::expression::Expression,
+    ) -> Self {
+        self.task.messages =
+            super::expression::WithExpression::Expression(expression);
+        self
+    }
+
+    /// Adds a tool provided to the LLMs as read-only context.
+    pub fn tool(
+        mut self,
+        tool: chat::completions::request::ToolExpression,
+    ) -> Self {
+        let tool = super::expression::WithExpression::Value(tool);
+        match &mut self.task.tools {
+            Some(super::expression::WithExpression::Value(Some(tools))) => {
+                tools.push(tool);
+            }
+            _ => {
+                self.task.tools =
+                    Some(super::expression::WithExpression::Value(Some(vec![
+                        tool,
+                    ])));
+            }
+        }
+        self
+    }
+
+    /// Adds a possible response for the LLMs to vote for.
+    pub fn response(
+        mut self,
+        response: impl Into<
+            super::expression::WithExpression<
+                chat::completions::request::RichContentExpression,
+            >,
+        >,
+    ) -> Self {
+        let response = response.into();
+        match &mut self.task.responses {
+            super::expression::WithExpression::Value(responses) => {
+                responses.push(response);
+            }
+            super::expression::WithExpression::Expression(_) => {
+                self.task.responses =
+                    super::expression::WithExpression::Value(vec![response]);
+            }
+        }
+        self
+    }
+
+    /// Sets every response to those produced by an expression, replacing
+    /// any responses added so far.
+    pub fn responses(
+        mut self,
+        expression: super::expression::Expression,
+    ) -> Self {
+        self.task.responses =
+            super::expression::WithExpression::Expression(expression);
+        self
+    }
+
+    /// Validates the task and returns it.
+    ///
+    /// Literal messages must not be empty, and literal responses must
+    /// contain at least two entries. Expressions are only checked when the
+    /// Function is compiled.
+    pub fn build(self) -> Result<super::TaskExpression, String> {
+        if let super::expression::WithExpression::Value(messages) =
+            &self.task.messages
+            && messages.is_empty()
+        {
+            return Err("`messages` cannot be empty".to_string());
+        }
+        if let super::expression::WithExpression::Value(responses) =
+            &self.task.responses
+            && responses.len() < 2
+            && responses.iter().all(|response| {
+                matches!(response, super::expression::WithExpression::Value(_))
+            })
+        {
+            return Err(format!(
+                "expected two or more responses, got {}",
+                responses.len()
+            ));
+        }
+        Ok(super::TaskExpression::VectorCompletion(self.task))
+    }
+
+    fn push_message(
+        mut self,
+        message: super::expression::WithExpression<
+            chat::completions::request::MessageExpression,
+        >,
+    ) -> Self {
+        match &mut self.task.messages {
+            super::expression::WithExpression::Value(messages) => {
+                messages.push(message);
+            }
+            super::expression::WithExpression::Expression(_) => {
+                self.task.messages =
+                    super::expression::WithExpression::Value(vec![message]);
+            }
+        }
+        self
+    }
+}
+
+/// Builds a [`TaskExpression`](super::TaskExpression) calling a remote
+/// Function.
+///
+/// The Function's repository, commit, and input are required up front.
+/// Finish with [`scalar`](Self::scalar) or [`vector`](Self::vector)
+/// depending on the type of the Function.
+#[derive(Debug, Clone)]
+pub struct FunctionTaskBuilder {
+    owner: String,
+    repository: String,
+    commit: String,
+    skip: Option<super::expression::Expression>,
+    map: Option<u64>,
+    input:
+        super::expression::WithExpression<super::expression::InputExpression>,
+}
+
+impl FunctionTaskBuilder {
+    /// Creates a builder for a task calling `owner/repository` at `commit`.
+    pub fn new(
+        owner: impl Into<String>,
+        repository: impl Into<String>,
+        commit: impl Into<String>,
+        input: impl Into<
+            super::expression::WithExpression<
+                super::expression::InputExpression,
+            >,
+        >,
+    ) -> Self {
+        Self {
+            owner: owner.into(),
+            repository: repository.into(),
+            commit: commit.into(),
+            skip: None,
+            map: None,
+            input: input.into(),
+        }
+    }
+
+    /// Skips the task when the expression evaluates to true.
+    pub fn skip(mut self, skip: super::expression::Expression) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    /// Maps the task over `input_maps[map]`.
+    pub fn map(mut self, map: u64) -> Self {
+        self.map = Some(map);
+        self
+    }
+
+    /// Returns a task calling a scalar Function.
+    pub fn scalar(self) -> super::TaskExpression {
+        super::TaskExpression::ScalarFunction(
+            super::ScalarFunctionTaskExpression {
+                owner: self.owner,
+                repository: self.repository,
+                commit: self.commit,
+                skip: self.skip,
+                map: self.map,
+                input: self.input,
+            },
+        )
+    }
+
+    /// Returns a task calling a vector Function.
+    pub fn vector(self) -> super::TaskExpression {
+        super::TaskExpression::VectorFunction(
+            super::VectorFunctionTaskExpression {
+                owner: self.owner,
+                repository: self.repository,
+                commit: self.commit,
+                skip: self.skip,
+                map: self.map,
+                input: self.input,
+            },
+        )
+    }
+}
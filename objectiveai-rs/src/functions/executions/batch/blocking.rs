@@ -0,0 +1,17 @@
+//! Blocking HTTP functions for batch function executions.
+
+use crate::{BlockingHttpClient, BlockingStream, HttpError};
+
+/// Blocking version of [`super::create_function_execution_batch_streaming`].
+pub fn create_function_execution_batch_streaming(
+    client: &BlockingHttpClient,
+    params: super::FunctionExecutionBatchCreateParams,
+) -> Result<
+    BlockingStream<Result<super::FunctionExecutionBatchChunk, HttpError>>,
+    HttpError,
+> {
+    client.block_on_stream(super::create_function_execution_batch_streaming(
+        &client.inner,
+        params,
+    ))
+}
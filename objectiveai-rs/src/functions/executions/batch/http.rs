@@ -0,0 +1,33 @@
+//! HTTP functions for batch function executions.
+
+use crate::{HttpClient, HttpError, MaybeSend};
+use futures::Stream;
+
+/// Creates a streaming batch function execution.
+///
+/// Executes a Function over many inputs via `POST /functions/batch` and
+/// returns a stream of chunks: one per item as it finishes, in completion
+/// order, followed by a summary with the combined retry token.
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client to use
+/// * `params` - The batch parameters
+///
+/// # Returns
+///
+/// A stream of batch chunks.
+pub async fn create_function_execution_batch_streaming(
+    client: &HttpClient,
+    params: super::FunctionExecutionBatchCreateParams,
+) -> Result<
+    impl Stream<Item = Result<super::FunctionExecutionBatchChunk, HttpError>>
+    + MaybeSend
+    + 'static
+    + use<>,
+    HttpError,
+> {
+    client
+        .send_streaming(reqwest::Method::POST, "functions/batch", Some(params))
+        .await
+}
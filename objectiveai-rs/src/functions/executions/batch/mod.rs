@@ -0,0 +1,22 @@
+//! Batch function execution types.
+//!
+//! A batch executes one Function and Profile over many inputs, streaming a
+//! result for each input as it finishes and a combined retry token once
+//! every input has finished.
+
+mod request;
+mod response;
+mod retry_token;
+
+pub use request::*;
+pub use response::*;
+pub use retry_token::*;
+
+#[cfg(feature = "http")]
+mod http;
+
+#[cfg(feature = "http")]
+pub use http::*;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
@@ -0,0 +1,56 @@
+//! Batch function execution request types.
+
+use crate::functions;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for executing a Function over many inputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionExecutionBatchCreateParams {
+    /// The execution shared by every item. Its `input` is replaced by each
+    /// item's input, and its `retry_token` by each item's entry in
+    /// `retry_token`.
+    pub request: functions::executions::request::Request,
+    /// The inputs to execute the Function over.
+    pub inputs: BatchInputs,
+    /// How many items are executed at once.
+    /// Defaults to [`DEFAULT_BATCH_CONCURRENCY`] if not specified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<u64>,
+    /// If present, reuses votes from a previous batch over the same inputs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_token: Option<String>,
+}
+
+/// How many items of a batch are executed at once by default.
+pub const DEFAULT_BATCH_CONCURRENCY: u64 = 8;
+
+/// The inputs of a batch, either as an array or as JSONL.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BatchInputs {
+    /// An array of inputs.
+    Array(Vec<functions::expression::Input>),
+    /// A string with one JSON input per line. Blank lines are skipped.
+    Jsonl(String),
+}
+
+impl BatchInputs {
+    /// Parses the inputs, returning the line number and error of the first
+    /// JSONL line which is not a valid input.
+    pub fn into_inputs(
+        self,
+    ) -> Result<Vec<functions::expression::Input>, (usize, serde_json::Error)>
+    {
+        match self {
+            BatchInputs::Array(inputs) => Ok(inputs),
+            BatchInputs::Jsonl(jsonl) => jsonl
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| {
+                    serde_json::from_str(line).map_err(|e| (i + 1, e))
+                })
+                .collect(),
+        }
+    }
+}
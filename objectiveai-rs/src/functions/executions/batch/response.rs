@@ -0,0 +1,52 @@
+//! Batch function execution response types.
+
+use crate::{error, functions};
+use serde::{Deserialize, Serialize};
+
+/// A chunk of a streaming batch function execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FunctionExecutionBatchChunk {
+    /// An item finished.
+    Item(Box<FunctionExecutionBatchItem>),
+    /// Every item finished.
+    Done(FunctionExecutionBatchSummary),
+}
+
+/// The result of one item of a batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionExecutionBatchItem {
+    /// The index of the item's input.
+    pub index: u64,
+    /// The execution, if it could be started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution:
+        Option<functions::executions::response::unary::FunctionExecution>,
+    /// Error details if the item failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<error::ResponseError>,
+}
+
+impl FunctionExecutionBatchItem {
+    /// Returns the error which failed the item, whether the execution could
+    /// not be started or finished with an error.
+    pub fn error(&self) -> Option<&error::ResponseError> {
+        self.error.as_ref().or_else(|| {
+            self.execution
+                .as_ref()
+                .and_then(|execution| execution.error.as_ref())
+        })
+    }
+}
+
+/// The totals of a finished batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionExecutionBatchSummary {
+    /// How many items succeeded.
+    pub succeeded: u64,
+    /// How many items failed.
+    pub failed: u64,
+    /// Token for retrying the batch, reusing the votes of every item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_token: Option<String>,
+}
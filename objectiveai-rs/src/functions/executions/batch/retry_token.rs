@@ -0,0 +1,42 @@
+//! Retry token for reusing votes from a previous batch.
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+/// Token that enables reusing votes from a previous batch function execution.
+///
+/// Contains the retry token of each item's execution, by item index.
+/// Serialized as base64-encoded JSON.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BatchRetryToken(pub Vec<Option<String>>);
+
+impl BatchRetryToken {
+    /// Returns the retry token of the item at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.0.get(index).and_then(Option::as_deref)
+    }
+
+    /// Sets the retry token of the item at `index`.
+    pub fn set(&mut self, index: usize, token: Option<String>) {
+        if self.0.len() <= index {
+            self.0.resize(index + 1, None);
+        }
+        self.0[index] = token;
+    }
+
+    /// Attempts to deserialize a token from a base64-encoded string.
+    pub fn try_from_string(s: &str) -> Option<Self> {
+        let json = base64::engine::general_purpose::STANDARD.decode(s).ok()?;
+        let token = serde_json::from_slice(&json).ok()?;
+        Some(token)
+    }
+}
+
+/// Serializes the token to a base64-encoded string.
+impl std::fmt::Display for BatchRetryToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(self).unwrap();
+        f.write_str(&base64::engine::general_purpose::STANDARD.encode(json))
+    }
+}
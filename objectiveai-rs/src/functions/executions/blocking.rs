@@ -0,0 +1,93 @@
+//! Blocking HTTP functions for function executions.
+
+use crate::{BlockingHttpClient, BlockingStream, HttpError};
+
+/// Blocking version of [`super::create_function_execution_unary`].
+pub fn create_function_execution_unary(
+    client: &BlockingHttpClient,
+    request: super::request::Request,
+) -> Result<super::response::unary::FunctionExecution, HttpError> {
+    client.block_on(super::create_function_execution_unary(
+        &client.inner,
+        request,
+    ))
+}
+
+/// Blocking version of [`super::create_function_execution_streaming`].
+pub fn create_function_execution_streaming(
+    client: &BlockingHttpClient,
+    request: super::request::Request,
+) -> Result<
+    BlockingStream<
+        Result<super::response::streaming::FunctionExecutionChunk, HttpError>,
+    >,
+    HttpError,
+> {
+    client.block_on_stream(super::create_function_execution_streaming(
+        &client.inner,
+        request,
+    ))
+}
+
+/// Blocking version of [`super::get_function_execution`].
+pub fn get_function_execution(
+    client: &BlockingHttpClient,
+    id: &str,
+) -> Result<super::StoredFunctionExecution, HttpError> {
+    client.block_on(super::get_function_execution(&client.inner, id))
+}
+
+/// Blocking version of [`super::list_function_executions`].
+pub fn list_function_executions(
+    client: &BlockingHttpClient,
+    params: &super::ListFunctionExecutionsParams,
+) -> Result<super::ListFunctionExecution, HttpError> {
+    client.block_on(super::list_function_executions(&client.inner, params))
+}
+
+/// Blocking version of [`super::replay_function_execution_unary`].
+pub fn replay_function_execution_unary(
+    client: &BlockingHttpClient,
+    params: super::FunctionExecutionReplayCreateParams,
+) -> Result<super::response::unary::FunctionExecution, HttpError> {
+    client.block_on(super::replay_function_execution_unary(
+        &client.inner,
+        params,
+    ))
+}
+
+/// Blocking version of [`super::replay_function_execution_streaming`].
+pub fn replay_function_execution_streaming(
+    client: &BlockingHttpClient,
+    params: super::FunctionExecutionReplayCreateParams,
+) -> Result<
+    BlockingStream<
+        Result<super::response::streaming::FunctionExecutionChunk, HttpError>,
+    >,
+    HttpError,
+> {
+    client.block_on_stream(super::replay_function_execution_streaming(
+        &client.inner,
+        params,
+    ))
+}
+
+/// Blocking version of [`super::create_function_execution_streaming_resumable`].
+pub fn create_function_execution_streaming_resumable(
+    client: &BlockingHttpClient,
+    request: super::request::Request,
+    max_resumes: u64,
+) -> Result<
+    BlockingStream<
+        Result<super::response::streaming::FunctionExecutionChunk, HttpError>,
+    >,
+    HttpError,
+> {
+    client.block_on_stream(
+        super::create_function_execution_streaming_resumable(
+            &client.inner,
+            request,
+            max_resumes,
+        ),
+    )
+}
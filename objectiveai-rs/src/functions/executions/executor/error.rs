@@ -0,0 +1,150 @@
+//! Errors that can occur during client-side Function execution.
+
+use crate::{error, functions};
+
+/// Errors that can occur during client-side Function execution.
+///
+/// Failed vector completions and invalid outputs do not fail the
+/// execution. They are recorded on the resulting task or execution, as the
+/// API does.
+#[derive(Debug, thiserror::Error)]
+pub enum ExecutorError {
+    /// Failed to resolve a Function definition.
+    #[error("fetch function error: {0}")]
+    FetchFunction(error::ResponseError),
+    /// The requested Function was not found.
+    #[error("function not found")]
+    FunctionNotFound,
+    /// Failed to resolve a Profile definition.
+    #[error("fetch profile error: {0}")]
+    FetchProfile(error::ResponseError),
+    /// The requested Profile was not found.
+    #[error("profile not found")]
+    ProfileNotFound,
+    /// The Profile is invalid for the Function.
+    #[error("invalid profile")]
+    InvalidProfile,
+    /// The Ensemble definition is invalid.
+    #[error("invalid ensemble: {0}")]
+    InvalidEnsemble(String),
+    /// The retry token is malformed.
+    #[error("invalid retry token")]
+    InvalidRetryToken,
+    /// A JMESPath expression in the Function is invalid.
+    #[error("invalid function expression: {0}")]
+    InvalidExpression(#[from] functions::expression::ExpressionError),
+    /// The input does not match the Function's input schema.
+    #[error(
+        "Input does not match function input schema: {}",
+        join_violations(.0)
+    )]
+    InputSchemaMismatch(Vec<functions::expression::InputSchemaViolation>),
+    /// Scalar output is not in [0, 1] range.
+    #[error("invalid scalar output, expected number between 0 and 1")]
+    InvalidScalarOutput,
+    /// Vector output does not sum to 1 or has wrong length.
+    #[error(
+        "invalid vector output, expected vector of numbers summing to 1 of length {0}"
+    )]
+    InvalidVectorOutput(usize),
+    /// The request uses a strategy other than the default, which only the
+    /// API supports.
+    #[error("unsupported strategy, only the default strategy runs locally")]
+    UnsupportedStrategy,
+    /// The request enables reasoning summaries, which only the API supports.
+    #[error("unsupported reasoning, reasoning summaries do not run locally")]
+    UnsupportedReasoning,
+}
+
+fn join_violations(
+    violations: &[functions::expression::InputSchemaViolation],
+) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl error::StatusError for ExecutorError {
+    fn status(&self) -> u16 {
+        match self {
+            ExecutorError::FetchFunction(e) => e.status(),
+            ExecutorError::FunctionNotFound => 404,
+            ExecutorError::FetchProfile(e) => e.status(),
+            ExecutorError::ProfileNotFound => 404,
+            ExecutorError::InvalidProfile => 400,
+            ExecutorError::InvalidEnsemble(_) => 400,
+            ExecutorError::InvalidRetryToken => 400,
+            ExecutorError::InvalidExpression(_) => 400,
+            ExecutorError::InputSchemaMismatch(_) => 400,
+            ExecutorError::InvalidScalarOutput => 400,
+            ExecutorError::InvalidVectorOutput(_) => 400,
+            ExecutorError::UnsupportedStrategy => 400,
+            ExecutorError::UnsupportedReasoning => 400,
+        }
+    }
+
+    fn message(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "kind": "executor",
+            "error": match self {
+                ExecutorError::FetchFunction(e) => serde_json::json!({
+                    "kind": "fetch_function",
+                    "error": e.message(),
+                }),
+                ExecutorError::FunctionNotFound => serde_json::json!({
+                    "kind": "function_not_found",
+                    "error": "function not found",
+                }),
+                ExecutorError::FetchProfile(e) => serde_json::json!({
+                    "kind": "fetch_profile",
+                    "error": e.message(),
+                }),
+                ExecutorError::ProfileNotFound => serde_json::json!({
+                    "kind": "profile_not_found",
+                    "error": "profile not found",
+                }),
+                ExecutorError::InvalidProfile => serde_json::json!({
+                    "kind": "invalid_profile",
+                    "error": "invalid profile",
+                }),
+                ExecutorError::InvalidEnsemble(msg) => serde_json::json!({
+                    "kind": "invalid_ensemble",
+                    "error": msg,
+                }),
+                ExecutorError::InvalidRetryToken => serde_json::json!({
+                    "kind": "invalid_retry_token",
+                    "error": "invalid retry token",
+                }),
+                ExecutorError::InvalidExpression(e) => serde_json::json!({
+                    "kind": "invalid_expression",
+                    "error": e.to_string(),
+                }),
+                ExecutorError::InputSchemaMismatch(violations) => {
+                    serde_json::json!({
+                        "kind": "input_schema_mismatch",
+                        "error": "Input does not match function input schema",
+                        "violations": violations,
+                    })
+                }
+                ExecutorError::InvalidScalarOutput => serde_json::json!({
+                    "kind": "invalid_scalar_output",
+                    "error": self.to_string(),
+                }),
+                ExecutorError::InvalidVectorOutput(_) => serde_json::json!({
+                    "kind": "invalid_vector_output",
+                    "error": self.to_string(),
+                }),
+                ExecutorError::UnsupportedStrategy => serde_json::json!({
+                    "kind": "unsupported_strategy",
+                    "error": self.to_string(),
+                }),
+                ExecutorError::UnsupportedReasoning => serde_json::json!({
+                    "kind": "unsupported_reasoning",
+                    "error": self.to_string(),
+                }),
+            }
+        }))
+    }
+}
@@ -0,0 +1,890 @@
+//! The client-side Function executor.
+
+use crate::{error, functions, vector};
+use functions::executions::{RetryToken, request, response};
+use functions::expression::{FunctionOutput, TaskOutput, TaskOutputOwned};
+
+/// Executes Functions in the current process.
+///
+/// Runs in three steps, mirroring the API:
+///
+/// 1. Resolves the Function and Profile of the request and of every nested
+///    Function task, compiling each Function's tasks against its input.
+/// 2. Runs every vector completion task concurrently.
+/// 3. Computes each Function's output from its task outputs, innermost
+///    first.
+///
+/// Only the default strategy is supported, and reasoning summaries are not.
+///
+/// # Example
+///
+/// ```ignore
+/// let executor = Executor::new(completer, StaticResolver::new());
+/// let execution = executor.execute(request).await?;
+/// println!("{:?}", execution.output);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Executor<V, R> {
+    /// Performs the vector completions of vector completion tasks.
+    pub vector_completer: V,
+    /// Resolves remote Functions and Profiles.
+    pub resolver: R,
+}
+
+impl<V, R> Executor<V, R>
+where
+    V: super::VectorCompleter,
+    R: super::FunctionResolver,
+{
+    /// Creates an executor from its vector completion and resolution
+    /// backends.
+    pub fn new(vector_completer: V, resolver: R) -> Self {
+        Self {
+            vector_completer,
+            resolver,
+        }
+    }
+
+    /// Executes a function execution request.
+    ///
+    /// Returns the execution the API would return for the request, with
+    /// every task nested under the Function which ran it. The request's
+    /// `stream` field is ignored.
+    pub async fn execute(
+        &self,
+        request: request::Request,
+    ) -> Result<response::unary::FunctionExecution, super::ExecutorError> {
+        // validate unsupported options
+        let base = request.base();
+        if base.reasoning.is_some() {
+            return Err(super::ExecutorError::UnsupportedReasoning);
+        }
+        if let Some(request::Strategy::SwissSystem { .. }) = base.strategy {
+            return Err(super::ExecutorError::UnsupportedStrategy);
+        }
+
+        // parse retry token if provided
+        let retry_token = base
+            .retry_token
+            .as_deref()
+            .map(|token| {
+                RetryToken::try_from_string(token)
+                    .ok_or(super::ExecutorError::InvalidRetryToken)
+            })
+            .transpose()?;
+
+        // timestamp the execution
+        let created = chrono::Utc::now().timestamp() as u64;
+
+        // take function, profile, and common parameters
+        let (function, profile, base) = match request {
+            request::Request::FunctionInlineProfileInline { body } => (
+                FunctionParam::Inline(body.function),
+                ProfileParam::Inline(body.profile),
+                body.base,
+            ),
+            request::Request::FunctionInlineProfileRemote { path, body } => (
+                FunctionParam::Inline(body.function),
+                ProfileParam::Remote {
+                    owner: path.powner,
+                    repository: path.prepository,
+                    commit: path.pcommit,
+                },
+                body.base,
+            ),
+            request::Request::FunctionRemoteProfileInline { path, body } => (
+                FunctionParam::Remote {
+                    owner: path.fowner,
+                    repository: path.frepository,
+                    commit: path.fcommit,
+                },
+                ProfileParam::Inline(body.profile),
+                body.base,
+            ),
+            request::Request::FunctionRemoteProfileRemote { path, body } => (
+                FunctionParam::Remote {
+                    owner: path.fowner,
+                    repository: path.frepository,
+                    commit: path.fcommit,
+                },
+                ProfileParam::Remote {
+                    owner: path.powner,
+                    repository: path.prepository,
+                    commit: path.pcommit,
+                },
+                body,
+            ),
+        };
+
+        // resolve and compile every function
+        let nodes = self
+            .resolve_nodes(function, profile, base.input.clone())
+            .await?;
+
+        // assign task indices
+        let task_index_lens = task_index_lens(&nodes);
+        let task_indices = task_indices(&nodes, &task_index_lens);
+
+        // run every vector completion
+        let mut outcomes = self
+            .run_vector_completions(
+                &nodes,
+                &task_indices,
+                &base,
+                retry_token.as_ref(),
+            )
+            .await;
+
+        // compute function outputs, innermost first
+        for (i, node) in nodes.into_iter().enumerate().rev() {
+            if let Node::Function(node) = node {
+                outcomes[i] = Some(complete_function(
+                    node,
+                    task_indices[i],
+                    task_index_lens[i],
+                    &task_indices,
+                    &mut outcomes,
+                    created,
+                ));
+            }
+        }
+
+        match outcomes.swap_remove(0) {
+            Some(Outcome::Function { task, .. }) => Ok(task.inner),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Resolves the request's Function and every nested Function, breadth
+    /// first. Every node is preceded by the Function containing it.
+    async fn resolve_nodes(
+        &self,
+        function: FunctionParam,
+        profile: ProfileParam,
+        input: functions::expression::Input,
+    ) -> Result<Vec<Node>, super::ExecutorError> {
+        let mut nodes: Vec<Option<Node>> = vec![None];
+        let mut pending = vec![Pending {
+            index: 0,
+            path: Vec::new(),
+            function,
+            profile,
+            input,
+        }];
+        while !pending.is_empty() {
+            let resolved = futures::future::try_join_all(
+                pending.drain(..).map(|pending| self.resolve(pending)),
+            )
+            .await?;
+            for resolved in resolved {
+                let index = resolved.index;
+                let node = compile(resolved, &mut nodes, &mut pending)?;
+                nodes[index] = Some(Node::Function(node));
+            }
+        }
+        Ok(nodes
+            .into_iter()
+            .map(|node| node.expect("every function is resolved"))
+            .collect())
+    }
+
+    /// Resolves the Function and Profile of a Function.
+    async fn resolve(
+        &self,
+        Pending {
+            index,
+            path,
+            function,
+            profile,
+            input,
+        }: Pending,
+    ) -> Result<Resolved, super::ExecutorError> {
+        let ((function_id, function), (profile_id, profile)) =
+            futures::future::try_join(
+                self.resolve_function(function),
+                self.resolve_profile(profile),
+            )
+            .await?;
+        Ok(Resolved {
+            index,
+            path,
+            function_id,
+            function,
+            profile_id,
+            profile,
+            input,
+        })
+    }
+
+    async fn resolve_function(
+        &self,
+        function: FunctionParam,
+    ) -> Result<(Option<String>, functions::Function), super::ExecutorError>
+    {
+        match function {
+            FunctionParam::Remote {
+                owner,
+                repository,
+                commit,
+            } => {
+                let function = self
+                    .resolver
+                    .get_function(&owner, &repository, commit.as_deref())
+                    .await
+                    .map_err(super::ExecutorError::FetchFunction)?
+                    .ok_or(super::ExecutorError::FunctionNotFound)?;
+                Ok((
+                    Some(format!(
+                        "{}/{}/{}",
+                        function.owner, function.repository, function.commit
+                    )),
+                    functions::Function::Remote(function.inner),
+                ))
+            }
+            FunctionParam::Inline(function) => {
+                Ok((None, functions::Function::Inline(function)))
+            }
+        }
+    }
+
+    async fn resolve_profile(
+        &self,
+        profile: ProfileParam,
+    ) -> Result<(Option<String>, functions::Profile), super::ExecutorError>
+    {
+        match profile {
+            ProfileParam::Remote {
+                owner,
+                repository,
+                commit,
+            } => {
+                let profile = self
+                    .resolver
+                    .get_profile(&owner, &repository, commit.as_deref())
+                    .await
+                    .map_err(super::ExecutorError::FetchProfile)?
+                    .ok_or(super::ExecutorError::ProfileNotFound)?;
+                Ok((
+                    Some(format!(
+                        "{}/{}/{}",
+                        profile.owner, profile.repository, profile.commit
+                    )),
+                    functions::Profile::Remote(profile.inner),
+                ))
+            }
+            ProfileParam::Inline(profile) => {
+                Ok((None, functions::Profile::Inline(profile)))
+            }
+        }
+    }
+
+    /// Runs every vector completion node concurrently, returning the
+    /// outcomes indexed by node.
+    async fn run_vector_completions(
+        &self,
+        nodes: &[Node],
+        task_indices: &[u64],
+        base: &request::FunctionRemoteProfileRemoteRequestBody,
+        retry_token: Option<&RetryToken>,
+    ) -> Vec<Option<Outcome>> {
+        let completions = nodes.iter().enumerate().filter_map(|(i, node)| {
+            let Node::VectorCompletion(node) = node else {
+                return None;
+            };
+            let retry = retry_token
+                .and_then(|token| token.0.get(task_indices[i] as usize))
+                .cloned()
+                .flatten();
+            let params =
+                vector::completions::request::VectorCompletionCreateParams {
+                    retry: retry.clone(),
+                    from_cache: base.from_cache,
+                    from_rng: base.from_rng,
+                    messages: node.task.messages.clone(),
+                    provider: base.provider,
+                    ensemble: node.ensemble.clone(),
+                    profile: node.profile.clone(),
+                    seed: base.seed,
+                    stream: None,
+                    tools: node.task.tools.clone(),
+                    responses: node.task.responses.clone(),
+                    uncertainty: base.uncertainty,
+                    backoff_max_elapsed_time: base.backoff_max_elapsed_time,
+                    first_chunk_timeout: base.first_chunk_timeout,
+                    other_chunk_timeout: base.other_chunk_timeout,
+                };
+            Some(async move {
+                let result = self
+                    .vector_completer
+                    .create_vector_completion(params)
+                    .await;
+                (i, retry, result)
+            })
+        });
+        let mut outcomes = Vec::with_capacity(nodes.len());
+        outcomes.resize_with(nodes.len(), || None);
+        for (i, retry, result) in futures::future::join_all(completions).await {
+            let Node::VectorCompletion(node) = &nodes[i] else {
+                unreachable!();
+            };
+            let (inner, error, retry) = match result {
+                Ok(completion) => {
+                    // vector completions with no successful votes are not
+                    // stored, so reuse the same retry next time
+                    let retry = if completion
+                        .completions
+                        .iter()
+                        .any(|completion| completion.error.is_none())
+                    {
+                        Some(completion.id.clone())
+                    } else {
+                        retry
+                    };
+                    (completion, None, retry)
+                }
+                Err(e) => (
+                    default_vector_completion(node.task.responses.len()),
+                    Some(e),
+                    retry,
+                ),
+            };
+            outcomes[i] = Some(Outcome::VectorCompletion {
+                task: response::unary::VectorCompletionTask {
+                    index: 0,
+                    task_index: task_indices[i],
+                    task_path: node.path.clone(),
+                    inner,
+                    error,
+                },
+                retry,
+            });
+        }
+        outcomes
+    }
+}
+
+/// Source of a Function to resolve.
+enum FunctionParam {
+    Remote {
+        owner: String,
+        repository: String,
+        commit: Option<String>,
+    },
+    Inline(functions::InlineFunction),
+}
+
+/// Source of a Profile to resolve.
+enum ProfileParam {
+    Remote {
+        owner: String,
+        repository: String,
+        commit: Option<String>,
+    },
+    Inline(functions::InlineProfile),
+}
+
+impl ProfileParam {
+    fn from_task_profile(
+        profile: functions::TaskProfile,
+    ) -> Result<Self, super::ExecutorError> {
+        match profile {
+            functions::TaskProfile::RemoteFunction {
+                owner,
+                repository,
+                commit,
+            } => Ok(ProfileParam::Remote {
+                owner,
+                repository,
+                commit,
+            }),
+            functions::TaskProfile::InlineFunction(profile) => {
+                Ok(ProfileParam::Inline(profile))
+            }
+            functions::TaskProfile::VectorCompletion { .. } => {
+                Err(super::ExecutorError::InvalidProfile)
+            }
+        }
+    }
+}
+
+/// A Function awaiting resolution, whose node is reserved at `index`.
+struct Pending {
+    index: usize,
+    path: Vec<u64>,
+    function: FunctionParam,
+    profile: ProfileParam,
+    input: functions::expression::Input,
+}
+
+/// A resolved Function awaiting compilation.
+struct Resolved {
+    index: usize,
+    path: Vec<u64>,
+    function_id: Option<String>,
+    function: functions::Function,
+    profile_id: Option<String>,
+    profile: functions::Profile,
+    input: functions::expression::Input,
+}
+
+/// A Function or vector completion to execute.
+enum Node {
+    Function(FunctionNode),
+    VectorCompletion(VectorCompletionNode),
+}
+
+struct FunctionNode {
+    path: Vec<u64>,
+    function_id: Option<String>,
+    profile_id: Option<String>,
+    input: functions::expression::Input,
+    output: functions::expression::Expression,
+    r#type: FunctionType,
+    /// The node indices of each task, `None` if skipped.
+    tasks: Vec<Option<TaskNodes>>,
+}
+
+#[derive(Clone, Copy)]
+enum FunctionType {
+    Scalar,
+    Vector { output_length: Option<u64> },
+}
+
+enum TaskNodes {
+    Function(usize),
+    MapFunction(Vec<usize>),
+    VectorCompletion(usize),
+    MapVectorCompletion(Vec<usize>),
+}
+
+struct VectorCompletionNode {
+    path: Vec<u64>,
+    ensemble: vector::completions::request::Ensemble,
+    profile: Vec<rust_decimal::Decimal>,
+    task: functions::VectorCompletionTask,
+}
+
+/// The result of executing a node.
+enum Outcome {
+    Function {
+        task: response::unary::FunctionExecutionTask,
+        retry_token: RetryToken,
+    },
+    VectorCompletion {
+        task: response::unary::VectorCompletionTask,
+        retry: Option<String>,
+    },
+}
+
+/// Validates a resolved Function and compiles its tasks, reserving nodes for
+/// its nested Functions and adding its vector completions.
+fn compile(
+    Resolved {
+        index: _,
+        path,
+        function_id,
+        function,
+        profile_id,
+        profile,
+        input,
+    }: Resolved,
+    nodes: &mut Vec<Option<Node>>,
+    pending: &mut Vec<Pending>,
+) -> Result<FunctionNode, super::ExecutorError> {
+    // validate input against input_schema
+    if let Some(violations) = function.input_violations(&input)
+        && !violations.is_empty()
+    {
+        return Err(super::ExecutorError::InputSchemaMismatch(violations));
+    }
+
+    // validate profile length
+    let task_profiles = match profile {
+        functions::Profile::Remote(profile) => profile.tasks,
+        functions::Profile::Inline(profile) => profile.tasks,
+    };
+    if task_profiles.len() != function.tasks().len() {
+        return Err(super::ExecutorError::InvalidProfile);
+    }
+
+    // take output and type
+    let output = function.output().clone();
+    let r#type = match &function {
+        functions::Function::Remote(functions::RemoteFunction::Scalar {
+            ..
+        })
+        | functions::Function::Inline(functions::InlineFunction::Scalar {
+            ..
+        }) => FunctionType::Scalar,
+        _ => FunctionType::Vector {
+            output_length: function.clone().compile_output_length(&input)?,
+        },
+    };
+
+    // compile tasks
+    let compiled_tasks = function.compile_tasks(&input)?;
+    let mut tasks = Vec::with_capacity(compiled_tasks.len());
+    for (i, (task, profile)) in
+        compiled_tasks.into_iter().zip(task_profiles).enumerate()
+    {
+        let mut task_path = path.clone();
+        task_path.push(i as u64);
+        tasks.push(match task {
+            None => None,
+            Some(functions::CompiledTask::One(task)) => {
+                Some(compile_task(task, task_path, profile, nodes, pending)?)
+            }
+            Some(functions::CompiledTask::Many(many)) => {
+                let vector_completions = many.first().is_none_or(|task| {
+                    matches!(task, functions::Task::VectorCompletion(_))
+                });
+                let mut indices = Vec::with_capacity(many.len());
+                for (j, task) in many.into_iter().enumerate() {
+                    let mut task_path = task_path.clone();
+                    task_path.push(j as u64);
+                    match compile_task(
+                        task,
+                        task_path,
+                        profile.clone(),
+                        nodes,
+                        pending,
+                    )? {
+                        TaskNodes::Function(index)
+                        | TaskNodes::VectorCompletion(index) => {
+                            indices.push(index)
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+                Some(if vector_completions {
+                    TaskNodes::MapVectorCompletion(indices)
+                } else {
+                    TaskNodes::MapFunction(indices)
+                })
+            }
+        });
+    }
+
+    Ok(FunctionNode {
+        path,
+        function_id,
+        profile_id,
+        input,
+        output,
+        r#type,
+        tasks,
+    })
+}
+
+/// Adds the node of a single compiled task.
+fn compile_task(
+    task: functions::Task,
+    path: Vec<u64>,
+    profile: functions::TaskProfile,
+    nodes: &mut Vec<Option<Node>>,
+    pending: &mut Vec<Pending>,
+) -> Result<TaskNodes, super::ExecutorError> {
+    match task {
+        functions::Task::ScalarFunction(functions::ScalarFunctionTask {
+            owner,
+            repository,
+            commit,
+            input,
+        })
+        | functions::Task::VectorFunction(functions::VectorFunctionTask {
+            owner,
+            repository,
+            commit,
+            input,
+        }) => {
+            let index = nodes.len();
+            nodes.push(None);
+            pending.push(Pending {
+                index,
+                path,
+                function: FunctionParam::Remote {
+                    owner,
+                    repository,
+                    commit: Some(commit),
+                },
+                profile: ProfileParam::from_task_profile(profile)?,
+                input,
+            });
+            Ok(TaskNodes::Function(index))
+        }
+        functions::Task::VectorCompletion(task) => {
+            let functions::TaskProfile::VectorCompletion { ensemble, profile } =
+                profile
+            else {
+                return Err(super::ExecutorError::InvalidProfile);
+            };
+            // validate provided ensembles, the backend validates the rest
+            if let vector::completions::request::Ensemble::Provided(ensemble) =
+                &ensemble
+            {
+                let ensemble: crate::ensemble::Ensemble = ensemble
+                    .clone()
+                    .try_into()
+                    .map_err(super::ExecutorError::InvalidEnsemble)?;
+                if profile.len() != ensemble.llms.len() {
+                    return Err(super::ExecutorError::InvalidProfile);
+                }
+            }
+            let index = nodes.len();
+            nodes.push(Some(Node::VectorCompletion(VectorCompletionNode {
+                path,
+                ensemble,
+                profile,
+                task,
+            })));
+            Ok(TaskNodes::VectorCompletion(index))
+        }
+    }
+}
+
+/// Returns the number of retry token entries of each node.
+fn task_index_lens(nodes: &[Node]) -> Vec<usize> {
+    let mut lens = vec![1; nodes.len()];
+    for (i, node) in nodes.iter().enumerate().rev() {
+        if let Node::Function(node) = node {
+            lens[i] = node
+                .tasks
+                .iter()
+                .map(|task| match task {
+                    None => 1,
+                    Some(TaskNodes::Function(index)) => lens[*index],
+                    Some(TaskNodes::MapFunction(indices)) => indices
+                        .iter()
+                        .map(|index| lens[*index])
+                        .sum::<usize>()
+                        .max(1),
+                    Some(TaskNodes::VectorCompletion(_)) => 1,
+                    Some(TaskNodes::MapVectorCompletion(indices)) => {
+                        indices.len().max(1)
+                    }
+                })
+                .sum();
+        }
+    }
+    lens
+}
+
+/// Returns the task index of each node, its offset in the retry token.
+fn task_indices(nodes: &[Node], task_index_lens: &[usize]) -> Vec<u64> {
+    let mut indices = vec![0; nodes.len()];
+    for (i, node) in nodes.iter().enumerate() {
+        let Node::Function(node) = node else {
+            continue;
+        };
+        let mut offset = indices[i];
+        for task in &node.tasks {
+            let start = offset;
+            match task {
+                None => offset += 1,
+                Some(
+                    TaskNodes::Function(index)
+                    | TaskNodes::VectorCompletion(index),
+                ) => {
+                    indices[*index] = offset;
+                    offset += task_index_lens[*index] as u64;
+                }
+                Some(
+                    TaskNodes::MapFunction(children)
+                    | TaskNodes::MapVectorCompletion(children),
+                ) => {
+                    for index in children {
+                        indices[*index] = offset;
+                        offset += task_index_lens[*index] as u64;
+                    }
+                    offset = offset.max(start + 1);
+                }
+            }
+        }
+    }
+    indices
+}
+
+/// Computes a Function's output from the outcomes of its tasks.
+fn complete_function(
+    node: FunctionNode,
+    task_index: u64,
+    task_index_len: usize,
+    task_indices: &[u64],
+    outcomes: &mut [Option<Outcome>],
+    created: u64,
+) -> Outcome {
+    let mut tasks = Vec::new();
+    let mut task_outputs = Vec::with_capacity(node.tasks.len());
+    let mut tasks_errors = false;
+    let mut usage = vector::completions::response::Usage::default();
+    let mut retry_token = RetryToken(vec![None; task_index_len]);
+
+    // take the outcome of each task node
+    let mut take = |index: usize| {
+        let local_index = (task_indices[index] - task_index) as usize;
+        match outcomes[index].take() {
+            Some(Outcome::Function {
+                mut task,
+                retry_token: child_retry_token,
+            }) => {
+                tasks_errors |=
+                    task.inner.error.is_some() || task.inner.tasks_errors;
+                usage.push(&task.inner.usage);
+                retry_token.insert(local_index, child_retry_token);
+                let output =
+                    TaskOutputOwned::Function(task.inner.output.clone());
+                task.index = tasks.len() as u64;
+                tasks.push(response::unary::Task::FunctionExecution(task));
+                output
+            }
+            Some(Outcome::VectorCompletion { mut task, retry }) => {
+                tasks_errors |= task.error.is_some()
+                    || task
+                        .inner
+                        .completions
+                        .iter()
+                        .any(|completion| completion.error.is_some());
+                usage.push(&task.inner.usage);
+                retry_token.0[local_index] = retry;
+                let output = TaskOutputOwned::VectorCompletion(
+                    task.inner.clone().into(),
+                );
+                task.index = tasks.len() as u64;
+                tasks.push(response::unary::Task::VectorCompletion(task));
+                output
+            }
+            None => unreachable!(),
+        }
+    };
+    for task in node.tasks {
+        task_outputs.push(task.map(|task| {
+            TaskOutput::Owned(match task {
+                TaskNodes::Function(index)
+                | TaskNodes::VectorCompletion(index) => take(index),
+                TaskNodes::MapFunction(indices) => {
+                    TaskOutputOwned::MapFunction(
+                        indices
+                            .into_iter()
+                            .map(|index| match take(index) {
+                                TaskOutputOwned::Function(output) => output,
+                                _ => unreachable!(),
+                            })
+                            .collect(),
+                    )
+                }
+                TaskNodes::MapVectorCompletion(indices) => {
+                    TaskOutputOwned::MapVectorCompletion(
+                        indices
+                            .into_iter()
+                            .map(|index| match take(index) {
+                                TaskOutputOwned::VectorCompletion(output) => {
+                                    output
+                                }
+                                _ => unreachable!(),
+                            })
+                            .collect(),
+                    )
+                }
+            })
+        }));
+    }
+
+    // compile output
+    let params =
+        functions::expression::Params::Ref(functions::expression::ParamsRef {
+            input: &node.input,
+            tasks: &task_outputs,
+            map: None,
+        });
+    let (output, output_error) = match (
+        node.r#type,
+        node.output.compile_one::<FunctionOutput>(&params),
+    ) {
+        (FunctionType::Scalar, Ok(FunctionOutput::Scalar(scalar)))
+            if scalar >= rust_decimal::Decimal::ZERO
+                && scalar <= rust_decimal::Decimal::ONE =>
+        {
+            (FunctionOutput::Scalar(scalar), None)
+        }
+        (FunctionType::Scalar, Ok(output)) => (
+            output.into_err(),
+            Some(super::ExecutorError::InvalidScalarOutput),
+        ),
+        (
+            FunctionType::Vector { output_length },
+            Ok(FunctionOutput::Vector(vector)),
+        ) if output_length.is_none_or(|len| len == vector.len() as u64)
+            && {
+                let sum = vector.iter().sum::<rust_decimal::Decimal>();
+                sum >= rust_decimal::dec!(0.99)
+                    && sum <= rust_decimal::dec!(1.01)
+            } =>
+        {
+            (FunctionOutput::Vector(vector), None)
+        }
+        (FunctionType::Vector { output_length }, Ok(output)) => (
+            output.into_err(),
+            Some(super::ExecutorError::InvalidVectorOutput(
+                output_length.unwrap_or_default() as usize,
+            )),
+        ),
+        (_, Err(e)) => (
+            FunctionOutput::Err(serde_json::Value::Null),
+            Some(super::ExecutorError::from(e)),
+        ),
+    };
+
+    let (id, object) = match node.r#type {
+        FunctionType::Scalar => (
+            response_id("sclfnc", created),
+            response::unary::Object::ScalarFunctionExecution,
+        ),
+        FunctionType::Vector { .. } => (
+            response_id("vctfnc", created),
+            response::unary::Object::VectorFunctionExecution,
+        ),
+    };
+    Outcome::Function {
+        task: response::unary::FunctionExecutionTask {
+            index: 0,
+            task_index,
+            task_path: node.path,
+            swiss_pool_index: None,
+            swiss_round: None,
+            inner: response::unary::FunctionExecution {
+                id,
+                tasks,
+                tasks_errors,
+                reasoning: None,
+                output,
+                error: output_error.map(|e| error::ResponseError::from(&e)),
+                retry_token: Some(retry_token.to_string()),
+                created,
+                function: node.function_id,
+                profile: node.profile_id,
+                object,
+                usage,
+            },
+        },
+        retry_token,
+    }
+}
+
+/// Generates a unique response ID, as the API does.
+fn response_id(prefix: &str, created: u64) -> String {
+    format!("{}-{}-{}", prefix, uuid::Uuid::new_v4().simple(), created)
+}
+
+/// The completion recorded for a failed vector completion, with uniform
+/// scores.
+fn default_vector_completion(
+    responses_len: usize,
+) -> vector::completions::response::unary::VectorCompletion {
+    if responses_len == 0 {
+        Default::default()
+    } else {
+        vector::completions::response::unary::VectorCompletion::default_from_request_responses_len(
+            responses_len,
+        )
+    }
+}
@@ -0,0 +1,116 @@
+//! Pluggable resolution of remote Functions and Profiles for the executor.
+
+use crate::{error, functions};
+
+/// Resolves the remote Functions and Profiles of an
+/// [`Executor`](super::Executor).
+///
+/// Implemented by the HTTP client, which fetches them from the ObjectiveAI
+/// API, and by [`StaticResolver`].
+pub trait FunctionResolver {
+    /// Resolves a Function by owner/repository/commit. Returns `None` if
+    /// the Function is not found.
+    fn get_function(
+        &self,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> impl Future<
+        Output = Result<
+            Option<functions::response::GetFunction>,
+            error::ResponseError,
+        >,
+    >;
+
+    /// Resolves a Profile by owner/repository/commit. Returns `None` if the
+    /// Profile is not found.
+    fn get_profile(
+        &self,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> impl Future<
+        Output = Result<
+            Option<functions::profiles::response::GetProfile>,
+            error::ResponseError,
+        >,
+    >;
+}
+
+/// Resolves Functions and Profiles from fixed lists.
+///
+/// A lookup without a commit matches the first entry with the owner and
+/// repository. The default resolver resolves nothing, which suffices for
+/// inline Functions and Profiles without remote Function tasks.
+#[derive(Debug, Clone, Default)]
+pub struct StaticResolver {
+    /// The Functions to resolve.
+    pub functions: Vec<functions::response::GetFunction>,
+    /// The Profiles to resolve.
+    pub profiles: Vec<functions::profiles::response::GetProfile>,
+}
+
+impl StaticResolver {
+    /// Creates a resolver which resolves nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a Function to resolve.
+    pub fn function(
+        mut self,
+        function: functions::response::GetFunction,
+    ) -> Self {
+        self.functions.push(function);
+        self
+    }
+
+    /// Adds a Profile to resolve.
+    pub fn profile(
+        mut self,
+        profile: functions::profiles::response::GetProfile,
+    ) -> Self {
+        self.profiles.push(profile);
+        self
+    }
+}
+
+impl FunctionResolver for StaticResolver {
+    async fn get_function(
+        &self,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> Result<Option<functions::response::GetFunction>, error::ResponseError>
+    {
+        Ok(self
+            .functions
+            .iter()
+            .find(|function| {
+                function.owner == owner
+                    && function.repository == repository
+                    && commit.is_none_or(|commit| function.commit == commit)
+            })
+            .cloned())
+    }
+
+    async fn get_profile(
+        &self,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> Result<
+        Option<functions::profiles::response::GetProfile>,
+        error::ResponseError,
+    > {
+        Ok(self
+            .profiles
+            .iter()
+            .find(|profile| {
+                profile.owner == owner
+                    && profile.repository == repository
+                    && commit.is_none_or(|commit| profile.commit == commit)
+            })
+            .cloned())
+    }
+}
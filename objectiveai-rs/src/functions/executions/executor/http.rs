@@ -0,0 +1,51 @@
+//! Executor backends using the HTTP client.
+
+use crate::{HttpClient, error, error::StatusError as _, functions, vector};
+
+impl super::VectorCompleter for HttpClient {
+    async fn create_vector_completion(
+        &self,
+        params: vector::completions::request::VectorCompletionCreateParams,
+    ) -> Result<
+        vector::completions::response::unary::VectorCompletion,
+        error::ResponseError,
+    > {
+        vector::completions::create_vector_completion_unary(self, params)
+            .await
+            .map_err(|e| error::ResponseError::from(&e))
+    }
+}
+
+impl super::FunctionResolver for HttpClient {
+    async fn get_function(
+        &self,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> Result<Option<functions::response::GetFunction>, error::ResponseError>
+    {
+        match functions::get_function(self, owner, repository, commit).await {
+            Ok(function) => Ok(Some(function)),
+            Err(e) if e.status() == 404 => Ok(None),
+            Err(e) => Err(error::ResponseError::from(&e)),
+        }
+    }
+
+    async fn get_profile(
+        &self,
+        owner: &str,
+        repository: &str,
+        commit: Option<&str>,
+    ) -> Result<
+        Option<functions::profiles::response::GetProfile>,
+        error::ResponseError,
+    > {
+        match functions::profiles::get_profile(self, owner, repository, commit)
+            .await
+        {
+            Ok(profile) => Ok(Some(profile)),
+            Err(e) if e.status() == 404 => Ok(None),
+            Err(e) => Err(error::ResponseError::from(&e)),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Client-side Function execution.
+//!
+//! The [`Executor`] runs a function execution [`Request`] in the current
+//! process instead of on the ObjectiveAI API. It resolves remote Functions
+//! and Profiles, compiles every task, runs the vector completion tasks, and
+//! computes the output of every Function, producing the same
+//! [`FunctionExecution`] the API would.
+//!
+//! Where vector completions and remote definitions come from is pluggable:
+//!
+//! - [`VectorCompleter`] - Performs vector completions. Implemented by the
+//!   HTTP client and by closures, which can script votes in tests.
+//! - [`FunctionResolver`] - Resolves remote Functions and Profiles.
+//!   Implemented by the HTTP client and by [`StaticResolver`].
+//!
+//! [`Request`]: super::request::Request
+//! [`FunctionExecution`]: super::response::unary::FunctionExecution
+
+mod error;
+mod executor;
+mod function_resolver;
+mod vector_completer;
+
+pub use error::*;
+pub use executor::*;
+pub use function_resolver::*;
+pub use vector_completer::*;
+
+#[cfg(feature = "http")]
+mod http;
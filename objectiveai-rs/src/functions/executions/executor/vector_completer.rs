@@ -0,0 +1,58 @@
+//! Pluggable vector completions for the executor.
+
+use crate::{error, vector};
+
+/// Performs the vector completions of an [`Executor`](super::Executor).
+///
+/// Implemented by the HTTP client, which runs them on the ObjectiveAI API,
+/// and by closures taking the parameters and returning a future of the
+/// completion, which can produce scripted votes.
+///
+/// # Example
+///
+/// ```ignore
+/// let completer = |params: VectorCompletionCreateParams| async move {
+///     let n = params.responses.len();
+///     Ok(VectorCompletion {
+///         scores: vec![Decimal::ONE / Decimal::from(n); n],
+///         weights: vec![Decimal::ONE; n],
+///         ..Default::default()
+///     })
+/// };
+/// ```
+pub trait VectorCompleter {
+    /// Creates a vector completion. Errors are recorded on the task, which
+    /// then outputs uniform scores.
+    fn create_vector_completion(
+        &self,
+        params: vector::completions::request::VectorCompletionCreateParams,
+    ) -> impl Future<
+        Output = Result<
+            vector::completions::response::unary::VectorCompletion,
+            error::ResponseError,
+        >,
+    >;
+}
+
+impl<F, Fut> VectorCompleter for F
+where
+    F: Fn(vector::completions::request::VectorCompletionCreateParams) -> Fut,
+    Fut: Future<
+        Output = Result<
+            vector::completions::response::unary::VectorCompletion,
+            error::ResponseError,
+        >,
+    >,
+{
+    fn create_vector_completion(
+        &self,
+        params: vector::completions::request::VectorCompletionCreateParams,
+    ) -> impl Future<
+        Output = Result<
+            vector::completions::response::unary::VectorCompletion,
+            error::ResponseError,
+        >,
+    > {
+        self(params)
+    }
+}
@@ -1,6 +1,6 @@
 //! HTTP functions for function executions.
 
-use crate::{HttpClient, HttpError};
+use crate::{HttpClient, HttpError, MaybeSend};
 use futures::Stream;
 
 /// Creates a function execution (non-streaming).
@@ -156,7 +156,7 @@ pub async fn create_function_execution_streaming(
             HttpError,
         >,
     >
-    + Send
+    + MaybeSend
     + 'static
     + use<>,
     HttpError,
@@ -288,3 +288,114 @@ pub async fn create_function_execution_streaming(
         }
     }
 }
+
+/// Retrieves a persisted function execution by ID.
+///
+/// Only available from servers which persist completed executions.
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client to use
+/// * `id` - The function execution ID
+///
+/// # Returns
+///
+/// The execution along with the request which started it and its compiled
+/// tasks.
+pub async fn get_function_execution(
+    client: &HttpClient,
+    id: &str,
+) -> Result<super::StoredFunctionExecution, HttpError> {
+    client
+        .send_unary(
+            reqwest::Method::GET,
+            format!("functions/executions/{}", id),
+            None::<String>,
+        )
+        .await
+}
+
+/// Lists persisted function executions, newest first.
+///
+/// Only available from servers which persist completed executions.
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client to use
+/// * `params` - Filters for the executions to list
+///
+/// # Returns
+///
+/// A summary of each matching execution.
+pub async fn list_function_executions(
+    client: &HttpClient,
+    params: &super::ListFunctionExecutionsParams,
+) -> Result<super::ListFunctionExecution, HttpError> {
+    let query = serde_urlencoded::to_string(params)
+        .expect("list parameters are always URL-encodable");
+    let path = if query.is_empty() {
+        "functions/executions".to_string()
+    } else {
+        format!("functions/executions?{}", query)
+    };
+    client
+        .send_unary(reqwest::Method::GET, path, None::<String>)
+        .await
+}
+
+/// Replays a function execution with a different Profile (non-streaming).
+///
+/// Re-executes a persisted execution, or a request with a retry token, via
+/// `POST /functions/replay`, reusing the votes of every Ensemble LLM the new
+/// Profile shares with the original.
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client to use
+/// * `params` - The replay parameters
+///
+/// # Returns
+///
+/// The complete function execution response.
+pub async fn replay_function_execution_unary(
+    client: &HttpClient,
+    mut params: super::FunctionExecutionReplayCreateParams,
+) -> Result<super::response::unary::FunctionExecution, HttpError> {
+    params.stream = None;
+    client
+        .send_unary(reqwest::Method::POST, "functions/replay", Some(params))
+        .await
+}
+
+/// Replays a function execution with a different Profile (streaming).
+///
+/// See [`replay_function_execution_unary`].
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client to use
+/// * `params` - The replay parameters
+///
+/// # Returns
+///
+/// A stream of function execution chunks.
+pub async fn replay_function_execution_streaming(
+    client: &HttpClient,
+    mut params: super::FunctionExecutionReplayCreateParams,
+) -> Result<
+    impl Stream<
+        Item = Result<
+            super::response::streaming::FunctionExecutionChunk,
+            HttpError,
+        >,
+    >
+    + MaybeSend
+    + 'static
+    + use<>,
+    HttpError,
+> {
+    params.stream = Some(true);
+    client
+        .send_streaming(reqwest::Method::POST, "functions/replay", Some(params))
+        .await
+}
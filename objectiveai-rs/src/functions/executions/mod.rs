@@ -8,14 +8,31 @@
 //! - Inline Function + Remote Profile
 //! - Inline Function + Inline Profile
 
+pub mod batch;
 pub mod request;
 pub mod response;
+mod replay;
 mod retry_token;
+mod stored;
 
+pub use replay::*;
 pub use retry_token::*;
+pub use stored::*;
 
 #[cfg(feature = "http")]
 mod http;
 
 #[cfg(feature = "http")]
 pub use http::*;
+
+#[cfg(feature = "http")]
+mod resumable;
+
+#[cfg(feature = "http")]
+pub use resumable::*;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+#[cfg(feature = "executor")]
+pub mod executor;
@@ -0,0 +1,168 @@
+//! Function execution replay types.
+//!
+//! A replay re-executes a previous execution with a different Profile,
+//! reusing the votes of every Ensemble LLM the new Profile shares with the
+//! original, so that only LLMs whose votes are missing are called.
+
+use crate::functions;
+use serde::{Deserialize, Serialize};
+
+/// Parameters for replaying a function execution with a different Profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionExecutionReplayCreateParams {
+    /// The execution to replay.
+    pub execution: ReplayExecution,
+    /// The Profile to replay the execution with.
+    pub profile: ReplayProfile,
+    /// Whether to stream the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+}
+
+/// The execution to replay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ReplayExecution {
+    /// An execution persisted by the server.
+    Stored {
+        /// The function execution ID.
+        id: String,
+    },
+    /// A request, whose `retry_token` selects the votes to reuse.
+    Request(Box<super::request::Request>),
+}
+
+/// The Profile to replay an execution with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ReplayProfile {
+    /// A GitHub-hosted Profile.
+    Remote {
+        /// Profile repository owner.
+        owner: String,
+        /// Profile repository name.
+        repository: String,
+        /// Profile Git commit SHA (optional).
+        commit: Option<String>,
+    },
+    /// An inline Profile.
+    Inline(functions::InlineProfile),
+}
+
+impl super::request::Request {
+    /// Pins a remote Function to the commit of `function`, the
+    /// `owner/repository/commit` ID an execution of the request reported.
+    ///
+    /// Replaying the request then compiles the same tasks as the execution,
+    /// even after the Function's repository moves on. Requests with an
+    /// inline Function, or whose Function `function` does not identify, are
+    /// left unchanged.
+    pub fn pin_function_commit(&mut self, function: &str) {
+        let mut id = function.splitn(3, '/');
+        let (Some(owner), Some(repository), Some(commit)) =
+            (id.next(), id.next(), id.next())
+        else {
+            return;
+        };
+        let (fowner, frepository, fcommit) = match self {
+            Self::FunctionRemoteProfileInline { path, .. } => {
+                (&path.fowner, &path.frepository, &mut path.fcommit)
+            }
+            Self::FunctionRemoteProfileRemote { path, .. } => {
+                (&path.fowner, &path.frepository, &mut path.fcommit)
+            }
+            _ => return,
+        };
+        if fowner == owner && frepository == repository {
+            *fcommit = Some(commit.to_string());
+        }
+    }
+
+    /// Replaces the Profile of the request, keeping its Function and common
+    /// execution parameters.
+    pub fn with_profile(self, profile: ReplayProfile) -> Self {
+        enum Function {
+            Remote(super::request::FunctionRemoteProfileInlineRequestPath),
+            Inline(functions::InlineFunction),
+        }
+        let (function, base) = match self {
+            Self::FunctionInlineProfileInline { body } => {
+                (Function::Inline(body.function), body.base)
+            }
+            Self::FunctionInlineProfileRemote { body, .. } => {
+                (Function::Inline(body.function), body.base)
+            }
+            Self::FunctionRemoteProfileInline { path, body } => {
+                (Function::Remote(path), body.base)
+            }
+            Self::FunctionRemoteProfileRemote { path, body } => (
+                Function::Remote(
+                    super::request::FunctionRemoteProfileInlineRequestPath {
+                        fowner: path.fowner,
+                        frepository: path.frepository,
+                        fcommit: path.fcommit,
+                    },
+                ),
+                body,
+            ),
+        };
+        match (function, profile) {
+            (Function::Inline(function), ReplayProfile::Inline(profile)) => {
+                Self::FunctionInlineProfileInline {
+                    body:
+                        super::request::FunctionInlineProfileInlineRequestBody {
+                            function,
+                            profile,
+                            base,
+                        },
+                }
+            }
+            (
+                Function::Inline(function),
+                ReplayProfile::Remote {
+                    owner,
+                    repository,
+                    commit,
+                },
+            ) => Self::FunctionInlineProfileRemote {
+                path: super::request::FunctionInlineProfileRemoteRequestPath {
+                    powner: owner,
+                    prepository: repository,
+                    pcommit: commit,
+                },
+                body: super::request::FunctionInlineProfileRemoteRequestBody {
+                    function,
+                    base,
+                },
+            },
+            (Function::Remote(path), ReplayProfile::Inline(profile)) => {
+                Self::FunctionRemoteProfileInline {
+                    path,
+                    body:
+                        super::request::FunctionRemoteProfileInlineRequestBody {
+                            profile,
+                            base,
+                        },
+                }
+            }
+            (
+                Function::Remote(path),
+                ReplayProfile::Remote {
+                    owner,
+                    repository,
+                    commit,
+                },
+            ) => Self::FunctionRemoteProfileRemote {
+                path: super::request::FunctionRemoteProfileRemoteRequestPath {
+                    fowner: path.fowner,
+                    frepository: path.frepository,
+                    fcommit: path.fcommit,
+                    powner: owner,
+                    prepository: repository,
+                    pcommit: commit,
+                },
+                body: base,
+            },
+        }
+    }
+}
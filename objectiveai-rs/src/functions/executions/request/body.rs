@@ -73,6 +73,10 @@ pub struct FunctionRemoteProfileRemoteRequestBody {
     /// Whether to stream the response.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
+    /// If present, vector completion task outputs include bootstrapped
+    /// confidence intervals on their scores.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uncertainty: Option<crate::vector::completions::request::Uncertainty>,
 
     // --- Retry configuration ---
     /// Maximum elapsed time (ms) for exponential backoff retries.
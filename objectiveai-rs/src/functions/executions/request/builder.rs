@@ -0,0 +1,212 @@
+//! Builder for function execution requests.
+
+use crate::{chat, functions};
+
+/// Builds a function execution [`Request`](super::Request).
+///
+/// The input is required up front. Finish with the method matching where
+/// the Function and Profile come from.
+///
+/// # Example
+///
+/// ```ignore
+/// let request = RequestBuilder::new(input)
+///     .from_cache(true)
+///     .function_remote_profile_remote(FunctionRemoteProfileRemoteRequestPath {
+///         fowner: "objectiveai".to_string(),
+///         frepository: "joke-ranker".to_string(),
+///         fcommit: None,
+///         powner: "objectiveai".to_string(),
+///         prepository: "joke-ranker-profile".to_string(),
+///         pcommit: None,
+///     })?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct RequestBuilder {
+    base: super::FunctionRemoteProfileRemoteRequestBody,
+}
+
+impl RequestBuilder {
+    /// Creates a builder executing a Function on `input`.
+    pub fn new(input: functions::expression::Input) -> Self {
+        Self {
+            base: super::FunctionRemoteProfileRemoteRequestBody {
+                retry_token: None,
+                from_cache: None,
+                from_rng: None,
+                reasoning: None,
+                strategy: None,
+                input,
+                provider: None,
+                seed: None,
+                stream: None,
+                uncertainty: None,
+                backoff_max_elapsed_time: None,
+                first_chunk_timeout: None,
+                other_chunk_timeout: None,
+            },
+        }
+    }
+
+    /// Reuses votes from a previous execution with this retry token.
+    pub fn retry_token(mut self, retry_token: impl Into<String>) -> Self {
+        self.base.retry_token = Some(retry_token.into());
+        self
+    }
+
+    /// Sets whether to use cached votes when available.
+    pub fn from_cache(mut self, from_cache: bool) -> Self {
+        self.base.from_cache = Some(from_cache);
+        self
+    }
+
+    /// Sets whether remaining votes are generated randomly.
+    pub fn from_rng(mut self, from_rng: bool) -> Self {
+        self.base.from_rng = Some(from_rng);
+        self
+    }
+
+    /// Enables reasoning summaries of the execution.
+    pub fn reasoning(mut self, reasoning: super::Reasoning) -> Self {
+        self.base.reasoning = Some(reasoning);
+        self
+    }
+
+    /// Sets the execution strategy.
+    pub fn strategy(mut self, strategy: super::Strategy) -> Self {
+        self.base.strategy = Some(strategy);
+        self
+    }
+
+    /// Sets the provider routing preferences.
+    pub fn provider(
+        mut self,
+        provider: chat::completions::request::Provider,
+    ) -> Self {
+        self.base.provider = Some(provider);
+        self
+    }
+
+    /// Sets the random seed.
+    pub fn seed(mut self, seed: i64) -> Self {
+        self.base.seed = Some(seed);
+        self
+    }
+
+    /// Sets whether to stream the response.
+    pub fn stream(mut self, stream: bool) -> Self {
+        self.base.stream = Some(stream);
+        self
+    }
+
+    /// Requests bootstrapped confidence intervals on the output.
+    pub fn uncertainty(
+        mut self,
+        uncertainty: crate::vector::completions::request::Uncertainty,
+    ) -> Self {
+        self.base.uncertainty = Some(uncertainty);
+        self
+    }
+
+    /// Sets the maximum elapsed time (ms) for exponential backoff retries.
+    pub fn backoff_max_elapsed_time(
+        mut self,
+        backoff_max_elapsed_time: u64,
+    ) -> Self {
+        self.base.backoff_max_elapsed_time = Some(backoff_max_elapsed_time);
+        self
+    }
+
+    /// Sets the timeout (ms) for receiving the first chunk.
+    pub fn first_chunk_timeout(mut self, first_chunk_timeout: u64) -> Self {
+        self.base.first_chunk_timeout = Some(first_chunk_timeout);
+        self
+    }
+
+    /// Sets the timeout (ms) between subsequent chunks.
+    pub fn other_chunk_timeout(mut self, other_chunk_timeout: u64) -> Self {
+        self.base.other_chunk_timeout = Some(other_chunk_timeout);
+        self
+    }
+
+    /// Returns a request executing an inline Function with an inline
+    /// Profile.
+    ///
+    /// Fails if the Profile does not have one entry per task of the
+    /// Function.
+    pub fn function_inline_profile_inline(
+        self,
+        function: functions::InlineFunction,
+        profile: functions::InlineProfile,
+    ) -> Result<super::Request, String> {
+        self.validate_uncertainty()?;
+        if profile.tasks.len() != function.tasks().len() {
+            return Err(format!(
+                "profile length must match function tasks length, expected {}, got {}",
+                function.tasks().len(),
+                profile.tasks.len()
+            ));
+        }
+        Ok(super::Request::FunctionInlineProfileInline {
+            body: super::FunctionInlineProfileInlineRequestBody {
+                function,
+                profile,
+                base: self.base,
+            },
+        })
+    }
+
+    /// Returns a request executing an inline Function with a remote
+    /// Profile.
+    pub fn function_inline_profile_remote(
+        self,
+        function: functions::InlineFunction,
+        path: super::FunctionInlineProfileRemoteRequestPath,
+    ) -> Result<super::Request, String> {
+        self.validate_uncertainty()?;
+        Ok(super::Request::FunctionInlineProfileRemote {
+            path,
+            body: super::FunctionInlineProfileRemoteRequestBody {
+                function,
+                base: self.base,
+            },
+        })
+    }
+
+    /// Returns a request executing a remote Function with an inline
+    /// Profile.
+    pub fn function_remote_profile_inline(
+        self,
+        path: super::FunctionRemoteProfileInlineRequestPath,
+        profile: functions::InlineProfile,
+    ) -> Result<super::Request, String> {
+        self.validate_uncertainty()?;
+        Ok(super::Request::FunctionRemoteProfileInline {
+            path,
+            body: super::FunctionRemoteProfileInlineRequestBody {
+                profile,
+                base: self.base,
+            },
+        })
+    }
+
+    /// Returns a request executing a remote Function with a remote
+    /// Profile.
+    pub fn function_remote_profile_remote(
+        self,
+        path: super::FunctionRemoteProfileRemoteRequestPath,
+    ) -> Result<super::Request, String> {
+        self.validate_uncertainty()?;
+        Ok(super::Request::FunctionRemoteProfileRemote {
+            path,
+            body: self.base,
+        })
+    }
+
+    fn validate_uncertainty(&self) -> Result<(), String> {
+        match &self.base.uncertainty {
+            Some(uncertainty) => uncertainty.validate(),
+            None => Ok(()),
+        }
+    }
+}
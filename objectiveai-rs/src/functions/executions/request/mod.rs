@@ -1,12 +1,14 @@
 //! Request types for function executions.
 
 mod body;
+mod builder;
 mod path;
 mod reasoning;
 mod request;
 mod strategy;
 
 pub use body::*;
+pub use builder::*;
 pub use path::*;
 pub use reasoning::*;
 pub use request::*;
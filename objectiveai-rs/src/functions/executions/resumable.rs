@@ -0,0 +1,422 @@
+//! Streaming function executions which resume after dropped connections.
+
+use super::response::streaming::{
+    FunctionExecutionChunk, TaskChunk, VectorCompletionTaskChunk,
+};
+use crate::{HttpClient, HttpError, MaybeSend};
+use futures::{Stream, StreamExt};
+use std::collections::{HashMap, HashSet};
+
+/// Creates a streaming function execution which resumes after the
+/// connection drops.
+///
+/// Behaves like [`super::create_function_execution_streaming`], except that
+/// when the stream fails with [`HttpError::StreamError`], or ends before the
+/// chunk holding the execution's output, the request is reissued with a
+/// retry token covering every vector completion task which has completed so
+/// far, and the continuation is yielded on the same stream. Completed tasks
+/// are not yielded again, and chunks keep the ID and task indices of the
+/// original execution. Tasks which were still streaming when the connection
+/// dropped, or which failed entirely, start over.
+///
+/// Swiss System executions only report a retry token once they finish, so
+/// they are reissued with the retry token of the original request and run
+/// again from the start. Their completed tasks are still not yielded again.
+///
+/// Whether the execution finished is judged by its final chunk rather than
+/// by how the stream ended, as on wasm32 a dropped connection ends the
+/// stream the same way the server finishing it does.
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client to use
+/// * `request` - The function execution request
+/// * `max_resumes` - How many times to reissue the request before yielding
+///   the stream error, or [`HttpError::StreamIncomplete`] if the stream
+///   ended early
+///
+/// # Returns
+///
+/// A stream of function execution chunks.
+pub async fn create_function_execution_streaming_resumable(
+    client: &HttpClient,
+    request: super::request::Request,
+    max_resumes: u64,
+) -> Result<
+    impl Stream<Item = Result<FunctionExecutionChunk, HttpError>>
+    + MaybeSend
+    + 'static
+    + use<>,
+    HttpError,
+> {
+    let retry_token = match &request.base().retry_token {
+        Some(retry_token) => super::RetryToken::try_from_string(retry_token)
+            .unwrap_or(super::RetryToken(Vec::new())),
+        None => super::RetryToken(Vec::new()),
+    };
+    let stream = boxed(
+        super::create_function_execution_streaming(client, request.clone())
+            .await?,
+    );
+    let state = Resumable {
+        client: client.clone(),
+        request,
+        stream,
+        resumes_left: max_resumes,
+        failed: false,
+        progress: Progress {
+            retry_token,
+            ..Default::default()
+        },
+    };
+    Ok(futures::stream::unfold(state, Resumable::next))
+}
+
+/// A boxed chunk stream, which is only `Send` on native targets.
+#[cfg(not(target_arch = "wasm32"))]
+type ChunkStream = futures::stream::BoxStream<
+    'static,
+    Result<FunctionExecutionChunk, HttpError>,
+>;
+
+#[cfg(target_arch = "wasm32")]
+type ChunkStream = futures::stream::LocalBoxStream<
+    'static,
+    Result<FunctionExecutionChunk, HttpError>,
+>;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn boxed(
+    stream: impl Stream<Item = Result<FunctionExecutionChunk, HttpError>>
+    + Send
+    + 'static,
+) -> ChunkStream {
+    stream.boxed()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn boxed(
+    stream: impl Stream<Item = Result<FunctionExecutionChunk, HttpError>> + 'static,
+) -> ChunkStream {
+    stream.boxed_local()
+}
+
+struct Resumable {
+    client: HttpClient,
+    request: super::request::Request,
+    stream: ChunkStream,
+    resumes_left: u64,
+    /// Whether the server reported an error, after which the stream may
+    /// end without an output.
+    failed: bool,
+    progress: Progress,
+}
+
+impl Resumable {
+    async fn next(
+        mut self,
+    ) -> Option<(Result<FunctionExecutionChunk, HttpError>, Self)> {
+        loop {
+            if self.progress.finished {
+                return None;
+            }
+            let result = match self.stream.next().await {
+                Some(result) => result,
+                // the server reported why it stopped
+                None if self.failed => return None,
+                None => Err(HttpError::StreamIncomplete),
+            };
+            match result {
+                Ok(mut chunk) => {
+                    self.progress.process(&mut chunk);
+                    return Some((Ok(chunk), self));
+                }
+                // the server closed the stream after reporting an error
+                #[cfg(not(target_arch = "wasm32"))]
+                Err(HttpError::StreamError(
+                    reqwest_eventsource::Error::StreamEnded,
+                )) if self.failed => return None,
+                Err(
+                    HttpError::StreamError(_) | HttpError::StreamIncomplete,
+                ) if self.resumes_left > 0 => {
+                    self.resumes_left -= 1;
+                    match self.resume().await {
+                        Ok(stream) => self.stream = stream,
+                        Err(e) => {
+                            self.progress.finished = true;
+                            return Some((Err(e), self));
+                        }
+                    }
+                }
+                Err(e) => {
+                    match e {
+                        HttpError::StreamError(_)
+                        | HttpError::StreamIncomplete => {
+                            self.progress.finished = true;
+                        }
+                        HttpError::ApiError(_) => self.failed = true,
+                        _ => {}
+                    }
+                    return Some((Err(e), self));
+                }
+            }
+        }
+    }
+
+    /// Reissues the request with the retry token built so far.
+    async fn resume(&mut self) -> Result<ChunkStream, HttpError> {
+        let mut request = self.request.clone();
+        if self.progress.retry_token.0.iter().any(Option::is_some) {
+            request.base_mut().retry_token =
+                Some(self.progress.retry_token.to_string());
+        }
+        self.progress.resumes += 1;
+        Ok(boxed(
+            super::create_function_execution_streaming(&self.client, request)
+                .await?,
+        ))
+    }
+}
+
+/// Progress of an execution across every request made for it.
+#[derive(Default)]
+struct Progress {
+    /// The ID of the execution, from the first request.
+    id: Option<String>,
+    /// The creation time of the execution, from the first request.
+    created: u64,
+    /// How many times the request has been reissued.
+    resumes: u64,
+    /// Retry token covering every completed vector completion task.
+    retry_token: super::RetryToken,
+    /// Vector completion tasks which are still streaming, by task path.
+    streaming: HashMap<Vec<PathSegment>, VectorCompletionTaskChunk>,
+    /// Task paths of the vector completion tasks which have completed.
+    completed: HashSet<Vec<PathSegment>>,
+    /// Index of each task, by task path.
+    indices: HashMap<Vec<PathSegment>, u64>,
+    /// Next free index among the children of each parent, by task path.
+    next_indices: HashMap<Vec<PathSegment>, u64>,
+    /// Whether the execution has completed.
+    finished: bool,
+}
+
+/// One step of a task path, which identifies a task by its own task index
+/// and those of its parents.
+///
+/// Task indices restart in every pool and round of a Swiss System
+/// execution, so each step also holds the pool and round of the task.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+struct PathSegment {
+    swiss_round: Option<u64>,
+    swiss_pool_index: Option<u64>,
+    task_index: u64,
+}
+
+impl Progress {
+    fn process(&mut self, chunk: &mut FunctionExecutionChunk) {
+        if self.resumes == 0 {
+            if self.id.is_none() {
+                self.id = Some(chunk.id.clone());
+                self.created = chunk.created;
+            }
+        } else if let Some(id) = &self.id {
+            chunk.id = id.clone();
+            chunk.created = self.created;
+        }
+        let tasks = std::mem::take(&mut chunk.tasks);
+        chunk.tasks = self.process_tasks(&[], tasks);
+        if let Some(retry_token) = chunk
+            .retry_token
+            .as_deref()
+            .and_then(super::RetryToken::try_from_string)
+        {
+            self.retry_token = retry_token;
+        }
+        if chunk.output.is_some() {
+            self.finished = true;
+        }
+    }
+
+    fn process_tasks(
+        &mut self,
+        parent: &[PathSegment],
+        tasks: Vec<TaskChunk>,
+    ) -> Vec<TaskChunk> {
+        let mut processed = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            match task {
+                TaskChunk::VectorCompletion(mut task) => {
+                    let mut path = parent.to_vec();
+                    path.push(PathSegment {
+                        task_index: task.task_index,
+                        ..Default::default()
+                    });
+                    // completed tasks were already yielded in full
+                    if self.completed.contains(&path) {
+                        continue;
+                    }
+                    task.index = self.index(&path, task.index);
+                    self.push_vector_completion(path, &task);
+                    processed.push(TaskChunk::VectorCompletion(task));
+                }
+                TaskChunk::FunctionExecution(mut task) => {
+                    let mut path = parent.to_vec();
+                    path.push(PathSegment {
+                        swiss_round: task.swiss_round,
+                        swiss_pool_index: task.swiss_pool_index,
+                        task_index: task.task_index,
+                    });
+                    task.index = self.index(&path, task.index);
+                    let tasks = std::mem::take(&mut task.inner.tasks);
+                    task.inner.tasks = self.process_tasks(&path, tasks);
+                    processed.push(TaskChunk::FunctionExecution(task));
+                }
+            }
+        }
+        processed
+    }
+
+    /// Returns the index of the task at `path`, keeping the index it had in
+    /// the first request across resumes.
+    fn index(&mut self, path: &[PathSegment], index: u64) -> u64 {
+        if let Some(index) = self.indices.get(path) {
+            return *index;
+        }
+        let parent = path[..path.len() - 1].to_vec();
+        let next = self.next_indices.entry(parent).or_default();
+        let index = if self.resumes == 0 { index } else { *next };
+        *next = (*next).max(index + 1);
+        self.indices.insert(path.to_vec(), index);
+        index
+    }
+
+    fn push_vector_completion(
+        &mut self,
+        path: Vec<PathSegment>,
+        task: &VectorCompletionTaskChunk,
+    ) {
+        match self.streaming.get_mut(&path) {
+            Some(aggregate) => aggregate.push(task),
+            None => {
+                self.streaming.insert(path.clone(), task.clone());
+            }
+        }
+        // usage is only present in the final chunk of a vector completion
+        if task.inner.usage.is_none() {
+            return;
+        }
+        let aggregate = self.streaming.remove(&path).unwrap();
+        // vector completions without any successful completion are not
+        // stored, so they cannot be retried and run again on resume
+        if aggregate.inner.id.is_empty()
+            || aggregate
+                .inner
+                .completions
+                .iter()
+                .all(|c| c.error.is_some())
+        {
+            return;
+        }
+        // the retry token of a Swiss System execution only covers its first
+        // round once it finishes, so none is built for it mid-stream
+        let is_swiss_system =
+            path.iter().any(|segment| segment.swiss_round.is_some());
+        self.completed.insert(path);
+        if is_swiss_system {
+            return;
+        }
+        let task_index = task.task_index as usize;
+        if self.retry_token.0.len() <= task_index {
+            self.retry_token.0.resize(task_index + 1, None);
+        }
+        self.retry_token.0[task_index] = Some(aggregate.inner.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::executions::response::streaming::{
+        FunctionExecutionTaskChunk, Object,
+    };
+    use crate::vector::completions::response;
+
+    fn chunk(tasks: Vec<TaskChunk>) -> FunctionExecutionChunk {
+        FunctionExecutionChunk {
+            id: "fnex-1".to_string(),
+            tasks,
+            tasks_errors: None,
+            reasoning: None,
+            output: None,
+            error: None,
+            retry_token: None,
+            created: 1,
+            function: None,
+            profile: None,
+            object: Object::VectorFunctionExecutionChunk,
+            usage: None,
+        }
+    }
+
+    /// A chunk of the vector completion task 0 of a Swiss System pool.
+    fn pool_chunk(pool: u64, id: &str, last: bool) -> FunctionExecutionChunk {
+        let vector_completion = VectorCompletionTaskChunk {
+            index: 0,
+            task_index: 0,
+            task_path: vec![0],
+            inner: response::streaming::VectorCompletionChunk {
+                id: id.to_string(),
+                completions: vec![
+                    response::streaming::ChatCompletionChunk::default(),
+                ],
+                usage: last.then(response::Usage::default),
+                ..Default::default()
+            },
+            error: None,
+        };
+        chunk(vec![TaskChunk::FunctionExecution(
+            FunctionExecutionTaskChunk {
+                index: 0,
+                task_index: 0,
+                task_path: Vec::new(),
+                swiss_pool_index: Some(pool),
+                swiss_round: Some(1),
+                inner: chunk(vec![TaskChunk::VectorCompletion(
+                    vector_completion,
+                )]),
+            },
+        )])
+    }
+
+    #[test]
+    fn swiss_system_pools_are_tracked_apart() {
+        let mut progress = Progress::default();
+        // the pools stream concurrently, and both restart at task index 0
+        let stream = [
+            pool_chunk(0, "vctcpl-0", false),
+            pool_chunk(1, "vctcpl-1", false),
+            pool_chunk(0, "vctcpl-0", true),
+            pool_chunk(1, "vctcpl-1", true),
+        ];
+        for (i, mut chunk) in stream.into_iter().enumerate() {
+            progress.process(&mut chunk);
+            let [TaskChunk::FunctionExecution(pool)] = chunk.tasks.as_slice()
+            else {
+                panic!("chunk {i} lost its pool");
+            };
+            assert_eq!(pool.swiss_pool_index, Some(i as u64 % 2));
+            assert_eq!(pool.index, 0);
+            let [TaskChunk::VectorCompletion(task)] =
+                pool.inner.tasks.as_slice()
+            else {
+                panic!("chunk {i} lost its vector completion task");
+            };
+            assert_eq!(task.inner.id, format!("vctcpl-{}", i % 2));
+        }
+        assert!(progress.streaming.is_empty());
+        assert_eq!(progress.completed.len(), 2);
+        // no retry token is built for Swiss System executions mid-stream
+        assert!(progress.retry_token.0.iter().all(Option::is_none));
+    }
+}
@@ -0,0 +1,114 @@
+//! Persisted function execution types.
+
+use crate::{functions, vector};
+use serde::{Deserialize, Serialize};
+
+/// A completed function execution as persisted by the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredFunctionExecution {
+    /// The request which started the execution.
+    pub request: super::request::Request,
+    /// The tasks compiled from the Function and input, if the Function could
+    /// be compiled again when the execution was persisted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compiled_tasks: Option<Vec<Option<functions::CompiledTask>>>,
+    /// The execution, including every nested task result and vote.
+    #[serde(flatten)]
+    pub inner: super::response::unary::FunctionExecution,
+}
+
+/// Filters for listing persisted function executions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListFunctionExecutionsParams {
+    /// Only executions of this remote Function, as `owner/repository` or
+    /// `owner/repository/commit`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// Only executions with this remote Profile, as `owner/repository` or
+    /// `owner/repository/commit`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Only executions created at or after this Unix timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<u64>,
+    /// Only executions created before this Unix timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<u64>,
+    /// Only executions which failed (`true`) or succeeded (`false`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed: Option<bool>,
+    /// Maximum number of executions to return, newest first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+impl ListFunctionExecutionsParams {
+    /// Returns whether an execution passes the filters, ignoring `limit`.
+    pub fn matches(&self, execution: &StoredFunctionExecution) -> bool {
+        fn matches_id(filter: Option<&str>, id: Option<&str>) -> bool {
+            match (filter, id) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(filter), Some(id)) => {
+                    id == filter
+                        || id
+                            .strip_prefix(filter)
+                            .is_some_and(|commit| commit.starts_with('/'))
+                }
+            }
+        }
+        let inner = &execution.inner;
+        matches_id(self.function.as_deref(), inner.function.as_deref())
+            && matches_id(self.profile.as_deref(), inner.profile.as_deref())
+            && self
+                .created_after
+                .is_none_or(|created_after| inner.created >= created_after)
+            && self
+                .created_before
+                .is_none_or(|created_before| inner.created < created_before)
+            && self
+                .failed
+                .is_none_or(|failed| inner.error.is_some() == failed)
+    }
+}
+
+/// Response from listing persisted function executions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListFunctionExecution {
+    /// The matching executions, newest first.
+    pub data: Vec<ListFunctionExecutionItem>,
+}
+
+/// A persisted function execution in a list response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListFunctionExecutionItem {
+    /// Unique identifier for the execution.
+    pub id: String,
+    /// Unix timestamp when the execution was created.
+    pub created: u64,
+    /// ID of the function used (if remote).
+    pub function: Option<String>,
+    /// ID of the profile used (if remote).
+    pub profile: Option<String>,
+    /// The final output (scalar or vector score).
+    pub output: functions::expression::FunctionOutput,
+    /// Whether the execution failed.
+    pub failed: bool,
+    /// Aggregated token and cost usage.
+    pub usage: vector::completions::response::Usage,
+}
+
+impl From<&StoredFunctionExecution> for ListFunctionExecutionItem {
+    fn from(execution: &StoredFunctionExecution) -> Self {
+        let inner = &execution.inner;
+        Self {
+            id: inner.id.clone(),
+            created: inner.created,
+            function: inner.function.clone(),
+            profile: inner.profile.clone(),
+            output: inner.output.clone(),
+            failed: inner.error.is_some(),
+            usage: inner.usage.clone(),
+        }
+    }
+}
@@ -29,6 +29,13 @@ pub struct Expression {
 }
 
 impl Expression {
+    /// Creates an expression from a JMESPath expression string.
+    pub fn new(jmespath: impl Into<String>) -> Self {
+        Self {
+            jmespath: jmespath.into(),
+        }
+    }
+
     /// Compiles the expression, allowing array results.
     ///
     /// Returns `OneOrMany::One` for single values or `OneOrMany::Many` for arrays.
@@ -113,6 +120,12 @@ pub enum WithExpression<T> {
     Value(T),
 }
 
+impl<T> From<Expression> for WithExpression<T> {
+    fn from(expression: Expression) -> Self {
+        WithExpression::Expression(expression)
+    }
+}
+
 impl<T> std::default::Default for WithExpression<T>
 where
     T: Default,
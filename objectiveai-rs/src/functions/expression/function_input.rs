@@ -0,0 +1,219 @@
+//! Rust types usable as Function input.
+
+use crate::chat;
+
+/// A Rust type which can be passed to a Function as input.
+///
+/// Describes the [`InputSchema`](super::InputSchema) every value of the type
+/// conforms to, and converts values into [`Input`](super::Input). With the
+/// `derive` feature, `#[derive(FunctionInput)]` implements it for structs
+/// and enums whose fields implement it. Comparing the serialized
+/// [`input_schema`](Self::input_schema) with that of
+/// [`RemoteFunction::input_schema`](crate::functions::RemoteFunction::input_schema)
+/// checks that a type matches a Function.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(FunctionInput)]
+/// struct Joke {
+///     /// The text of the joke.
+///     text: String,
+///     /// Who the joke is told to.
+///     audience: String,
+/// }
+///
+/// let schema = Joke::input_schema();
+/// let input = Joke {
+///     text: "...".to_string(),
+///     audience: "kids".to_string(),
+/// }
+/// .into_input();
+/// assert!(schema.validate_input(&input));
+/// ```
+pub trait FunctionInput {
+    /// Returns the schema every value of this type conforms to.
+    fn input_schema() -> super::InputSchema;
+
+    /// Converts the value into Function input.
+    fn into_input(self) -> super::Input;
+}
+
+impl FunctionInput for String {
+    fn input_schema() -> super::InputSchema {
+        super::InputSchema::String(super::StringInputSchema {
+            description: None,
+            r#enum: None,
+        })
+    }
+
+    fn into_input(self) -> super::Input {
+        super::Input::String(self)
+    }
+}
+
+impl FunctionInput for &str {
+    fn input_schema() -> super::InputSchema {
+        String::input_schema()
+    }
+
+    fn into_input(self) -> super::Input {
+        super::Input::String(self.to_string())
+    }
+}
+
+impl FunctionInput for bool {
+    fn input_schema() -> super::InputSchema {
+        super::InputSchema::Boolean(super::BooleanInputSchema {
+            description: None,
+        })
+    }
+
+    fn into_input(self) -> super::Input {
+        super::Input::Boolean(self)
+    }
+}
+
+macro_rules! impl_integer_function_input {
+    ($($ty:ty),*) => {
+        $(
+            impl FunctionInput for $ty {
+                fn input_schema() -> super::InputSchema {
+                    super::InputSchema::Integer(super::IntegerInputSchema {
+                        description: None,
+                        minimum: None,
+                        maximum: None,
+                    })
+                }
+
+                fn into_input(self) -> super::Input {
+                    super::Input::Integer(i64::from(self))
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_function_input!(i8, i16, i32, i64, u8, u16, u32);
+
+impl FunctionInput for f64 {
+    fn input_schema() -> super::InputSchema {
+        super::InputSchema::Number(super::NumberInputSchema {
+            description: None,
+            minimum: None,
+            maximum: None,
+        })
+    }
+
+    fn into_input(self) -> super::Input {
+        super::Input::Number(self)
+    }
+}
+
+impl FunctionInput for f32 {
+    fn input_schema() -> super::InputSchema {
+        f64::input_schema()
+    }
+
+    fn into_input(self) -> super::Input {
+        super::Input::Number(self as f64)
+    }
+}
+
+impl<T: FunctionInput> FunctionInput for Vec<T> {
+    fn input_schema() -> super::InputSchema {
+        super::InputSchema::Array(super::ArrayInputSchema {
+            description: None,
+            min_items: None,
+            max_items: None,
+            items: Box::new(T::input_schema()),
+        })
+    }
+
+    fn into_input(self) -> super::Input {
+        super::Input::Array(
+            self.into_iter().map(FunctionInput::into_input).collect(),
+        )
+    }
+}
+
+impl<T: FunctionInput, const N: usize> FunctionInput for [T; N] {
+    fn input_schema() -> super::InputSchema {
+        super::InputSchema::Array(super::ArrayInputSchema {
+            description: None,
+            min_items: Some(N as u64),
+            max_items: Some(N as u64),
+            items: Box::new(T::input_schema()),
+        })
+    }
+
+    fn into_input(self) -> super::Input {
+        super::Input::Array(
+            self.into_iter().map(FunctionInput::into_input).collect(),
+        )
+    }
+}
+
+impl<T: FunctionInput> FunctionInput for Box<T> {
+    fn input_schema() -> super::InputSchema {
+        T::input_schema()
+    }
+
+    fn into_input(self) -> super::Input {
+        (*self).into_input()
+    }
+}
+
+impl FunctionInput for chat::completions::request::ImageUrl {
+    fn input_schema() -> super::InputSchema {
+        super::InputSchema::Image(super::ImageInputSchema { description: None })
+    }
+
+    fn into_input(self) -> super::Input {
+        super::Input::RichContentPart(
+            chat::completions::request::RichContentPart::ImageUrl {
+                image_url: self,
+            },
+        )
+    }
+}
+
+impl FunctionInput for chat::completions::request::InputAudio {
+    fn input_schema() -> super::InputSchema {
+        super::InputSchema::Audio(super::AudioInputSchema { description: None })
+    }
+
+    fn into_input(self) -> super::Input {
+        super::Input::RichContentPart(
+            chat::completions::request::RichContentPart::InputAudio {
+                input_audio: self,
+            },
+        )
+    }
+}
+
+impl FunctionInput for chat::completions::request::VideoUrl {
+    fn input_schema() -> super::InputSchema {
+        super::InputSchema::Video(super::VideoInputSchema { description: None })
+    }
+
+    fn into_input(self) -> super::Input {
+        super::Input::RichContentPart(
+            chat::completions::request::RichContentPart::VideoUrl {
+                video_url: self,
+            },
+        )
+    }
+}
+
+impl FunctionInput for chat::completions::request::File {
+    fn input_schema() -> super::InputSchema {
+        super::InputSchema::File(super::FileInputSchema { description: None })
+    }
+
+    fn into_input(self) -> super::Input {
+        super::Input::RichContentPart(
+            chat::completions::request::RichContentPart::File { file: self },
+        )
+    }
+}
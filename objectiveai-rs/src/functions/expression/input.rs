@@ -3,6 +3,7 @@
 //! Defines the data structures that can be passed as input to Functions,
 //! along with schema types for validation.
 
+use super::{InputSchemaViolation, InputSchemaViolationKind, InputType};
 use crate::chat;
 use indexmap::IndexMap;
 use serde::de::Error as _;
@@ -394,22 +395,82 @@ pub enum InputSchema {
 }
 
 impl InputSchema {
-    /// Validates that an input value conforms to this schema.
+    /// Validates that an input conforms to this schema.
     pub fn validate_input(&self, input: &Input) -> bool {
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
         match self {
-            InputSchema::Object(schema) => schema.validate_input(input),
-            InputSchema::Array(schema) => schema.validate_input(input),
-            InputSchema::String(schema) => schema.validate_input(input),
-            InputSchema::Integer(schema) => schema.validate_input(input),
-            InputSchema::Number(schema) => schema.validate_input(input),
-            InputSchema::Boolean(schema) => schema.validate_input(input),
-            InputSchema::Image(schema) => schema.validate_input(input),
-            InputSchema::Audio(schema) => schema.validate_input(input),
-            InputSchema::Video(schema) => schema.validate_input(input),
-            InputSchema::File(schema) => schema.validate_input(input),
-            InputSchema::AnyOf(schema) => schema.validate_input(input),
+            InputSchema::Object(schema) => {
+                schema.push_violations(input, path, violations)
+            }
+            InputSchema::Array(schema) => {
+                schema.push_violations(input, path, violations)
+            }
+            InputSchema::String(schema) => {
+                schema.push_violations(input, path, violations)
+            }
+            InputSchema::Integer(schema) => {
+                schema.push_violations(input, path, violations)
+            }
+            InputSchema::Number(schema) => {
+                schema.push_violations(input, path, violations)
+            }
+            InputSchema::Boolean(schema) => {
+                schema.push_violations(input, path, violations)
+            }
+            InputSchema::Image(schema) => {
+                schema.push_violations(input, path, violations)
+            }
+            InputSchema::Audio(schema) => {
+                schema.push_violations(input, path, violations)
+            }
+            InputSchema::Video(schema) => {
+                schema.push_violations(input, path, violations)
+            }
+            InputSchema::File(schema) => {
+                schema.push_violations(input, path, violations)
+            }
+            InputSchema::AnyOf(schema) => {
+                schema.push_violations(input, path, violations)
+            }
         }
     }
+
+    /// Returns the schema with its description replaced.
+    ///
+    /// [`AnyOf`](InputSchema::AnyOf) schemas have no description and are
+    /// returned unchanged.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        let description = Some(description.into());
+        match &mut self {
+            InputSchema::Object(schema) => schema.description = description,
+            InputSchema::Array(schema) => schema.description = description,
+            InputSchema::String(schema) => schema.description = description,
+            InputSchema::Integer(schema) => schema.description = description,
+            InputSchema::Number(schema) => schema.description = description,
+            InputSchema::Boolean(schema) => schema.description = description,
+            InputSchema::Image(schema) => schema.description = description,
+            InputSchema::Audio(schema) => schema.description = description,
+            InputSchema::Video(schema) => schema.description = description,
+            InputSchema::File(schema) => schema.description = description,
+            InputSchema::AnyOf(_) => {}
+        }
+        self
+    }
 }
 
 /// Helper enum for deserializing typed schemas (those with a `type` field).
@@ -628,9 +689,35 @@ pub struct AnyOfInputSchema {
 impl AnyOfInputSchema {
     /// Validates that an input matches at least one schema in the union.
     pub fn validate_input(&self, input: &Input) -> bool {
-        self.any_of
-            .iter()
-            .any(|schema| schema.validate_input(input))
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
+        let mut any_of = Vec::with_capacity(self.any_of.len());
+        for schema in &self.any_of {
+            let mut schema_violations = Vec::new();
+            schema.push_violations(input, path, &mut schema_violations);
+            if schema_violations.is_empty() {
+                return;
+            }
+            any_of.push(schema_violations);
+        }
+        violations.push(InputSchemaViolation {
+            path: path.to_string(),
+            kind: InputSchemaViolationKind::AnyOf { violations: any_of },
+        });
     }
 }
 
@@ -651,21 +738,47 @@ pub struct ObjectInputSchema {
 impl ObjectInputSchema {
     /// Validates that an input is an object matching this schema.
     pub fn validate_input(&self, input: &Input) -> bool {
-        match input {
-            Input::Object(map) => {
-                self.properties.iter().all(|(key, schema)| {
-                    map.get(key)
-                        .map(|value| schema.validate_input(value))
-                        .unwrap_or(false)
-                }) && {
-                    if let Some(required) = &self.required {
-                        required.iter().all(|key| map.contains_key(key))
-                    } else {
-                        true
-                    }
-                }
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
+        let Input::Object(map) = input else {
+            violations.push(type_violation(InputType::Object, input, path));
+            return;
+        };
+        // every property is expected, as well as every required key
+        let required = self
+            .required
+            .iter()
+            .flatten()
+            .filter(|key| !self.properties.contains_key(*key));
+        for key in self.properties.keys().chain(required) {
+            match (map.get(key), self.properties.get(key)) {
+                (Some(value), Some(schema)) => schema.push_violations(
+                    value,
+                    &child_path(path, key),
+                    violations,
+                ),
+                (Some(_), None) => {}
+                (None, _) => violations.push(InputSchemaViolation {
+                    path: path.to_string(),
+                    kind: InputSchemaViolationKind::Required {
+                        property: key.clone(),
+                    },
+                }),
             }
-            _ => false,
         }
     }
 }
@@ -690,21 +803,55 @@ pub struct ArrayInputSchema {
 impl ArrayInputSchema {
     /// Validates that an input is an array matching this schema.
     pub fn validate_input(&self, input: &Input) -> bool {
-        match input {
-            Input::Array(array) => {
-                if let Some(min_items) = self.min_items
-                    && (array.len() as u64) < min_items
-                {
-                    false
-                } else if let Some(max_items) = self.max_items
-                    && (array.len() as u64) > max_items
-                {
-                    false
-                } else {
-                    array.iter().all(|item| self.items.validate_input(item))
-                }
-            }
-            _ => false,
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
+        let Input::Array(array) = input else {
+            violations.push(type_violation(InputType::Array, input, path));
+            return;
+        };
+        let len = array.len() as u64;
+        if let Some(min_items) = self.min_items
+            && len < min_items
+        {
+            violations.push(InputSchemaViolation {
+                path: path.to_string(),
+                kind: InputSchemaViolationKind::MinItems {
+                    min_items,
+                    actual: len,
+                },
+            });
+        }
+        if let Some(max_items) = self.max_items
+            && len > max_items
+        {
+            violations.push(InputSchemaViolation {
+                path: path.to_string(),
+                kind: InputSchemaViolationKind::MaxItems {
+                    max_items,
+                    actual: len,
+                },
+            });
+        }
+        for (i, item) in array.iter().enumerate() {
+            self.items.push_violations(
+                item,
+                &child_path(path, &i.to_string()),
+                violations,
+            );
         }
     }
 }
@@ -724,15 +871,36 @@ pub struct StringInputSchema {
 impl StringInputSchema {
     /// Validates that an input is a string matching this schema.
     pub fn validate_input(&self, input: &Input) -> bool {
-        match input {
-            Input::String(s) => {
-                if let Some(r#enum) = &self.r#enum {
-                    r#enum.contains(s)
-                } else {
-                    true
-                }
-            }
-            _ => false,
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
+        let Input::String(s) = input else {
+            violations.push(type_violation(InputType::String, input, path));
+            return;
+        };
+        if let Some(r#enum) = &self.r#enum
+            && !r#enum.contains(s)
+        {
+            violations.push(InputSchemaViolation {
+                path: path.to_string(),
+                kind: InputSchemaViolationKind::Enum {
+                    allowed: r#enum.clone(),
+                    actual: s.clone(),
+                },
+            });
         }
     }
 }
@@ -755,37 +923,59 @@ pub struct IntegerInputSchema {
 impl IntegerInputSchema {
     /// Validates that an input is an integer matching this schema.
     pub fn validate_input(&self, input: &Input) -> bool {
-        match input {
-            Input::Integer(integer) => {
-                if let Some(minimum) = self.minimum
-                    && *integer < minimum
-                {
-                    false
-                } else if let Some(maximum) = self.maximum
-                    && *integer > maximum
-                {
-                    false
-                } else {
-                    true
-                }
-            }
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
+        let integer = match input {
+            Input::Integer(integer) => *integer,
             Input::Number(number)
                 if number.is_finite() && number.fract() == 0.0 =>
             {
-                let integer = *number as i64;
-                if let Some(minimum) = self.minimum
-                    && integer < minimum
-                {
-                    false
-                } else if let Some(maximum) = self.maximum
-                    && integer > maximum
-                {
-                    false
-                } else {
-                    true
-                }
+                *number as i64
             }
-            _ => false,
+            _ => {
+                violations.push(type_violation(
+                    InputType::Integer,
+                    input,
+                    path,
+                ));
+                return;
+            }
+        };
+        if let Some(minimum) = self.minimum
+            && integer < minimum
+        {
+            violations.push(InputSchemaViolation {
+                path: path.to_string(),
+                kind: InputSchemaViolationKind::Minimum {
+                    minimum: minimum.into(),
+                    actual: integer.into(),
+                },
+            });
+        }
+        if let Some(maximum) = self.maximum
+            && integer > maximum
+        {
+            violations.push(InputSchemaViolation {
+                path: path.to_string(),
+                kind: InputSchemaViolationKind::Maximum {
+                    maximum: maximum.into(),
+                    actual: integer.into(),
+                },
+            });
         }
     }
 }
@@ -808,35 +998,51 @@ pub struct NumberInputSchema {
 impl NumberInputSchema {
     /// Validates that an input is a number matching this schema.
     pub fn validate_input(&self, input: &Input) -> bool {
-        match input {
-            Input::Integer(integer) => {
-                let number = *integer as f64;
-                if let Some(minimum) = self.minimum
-                    && number < minimum
-                {
-                    false
-                } else if let Some(maximum) = self.maximum
-                    && number > maximum
-                {
-                    false
-                } else {
-                    true
-                }
-            }
-            Input::Number(number) => {
-                if let Some(minimum) = self.minimum
-                    && *number < minimum
-                {
-                    false
-                } else if let Some(maximum) = self.maximum
-                    && *number > maximum
-                {
-                    false
-                } else {
-                    true
-                }
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
+        let number = match input {
+            Input::Integer(integer) => *integer as f64,
+            Input::Number(number) => *number,
+            _ => {
+                violations.push(type_violation(InputType::Number, input, path));
+                return;
             }
-            _ => false,
+        };
+        if let Some(minimum) = self.minimum
+            && number < minimum
+        {
+            violations.push(InputSchemaViolation {
+                path: path.to_string(),
+                kind: InputSchemaViolationKind::Minimum {
+                    minimum: json_number(minimum),
+                    actual: json_number(number),
+                },
+            });
+        }
+        if let Some(maximum) = self.maximum
+            && number > maximum
+        {
+            violations.push(InputSchemaViolation {
+                path: path.to_string(),
+                kind: InputSchemaViolationKind::Maximum {
+                    maximum: json_number(maximum),
+                    actual: json_number(number),
+                },
+            });
         }
     }
 }
@@ -853,9 +1059,24 @@ pub struct BooleanInputSchema {
 impl BooleanInputSchema {
     /// Validates that an input is a boolean.
     pub fn validate_input(&self, input: &Input) -> bool {
-        match input {
-            Input::Boolean(_) => true,
-            _ => false,
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
+        if InputType::of(input) != InputType::Boolean {
+            violations.push(type_violation(InputType::Boolean, input, path));
         }
     }
 }
@@ -872,13 +1093,24 @@ pub struct ImageInputSchema {
 impl ImageInputSchema {
     /// Validates that an input is an image.
     pub fn validate_input(&self, input: &Input) -> bool {
-        match input {
-            Input::RichContentPart(
-                chat::completions::request::RichContentPart::ImageUrl {
-                    ..
-                },
-            ) => true,
-            _ => false,
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
+        if InputType::of(input) != InputType::Image {
+            violations.push(type_violation(InputType::Image, input, path));
         }
     }
 }
@@ -895,13 +1127,24 @@ pub struct AudioInputSchema {
 impl AudioInputSchema {
     /// Validates that an input is audio content.
     pub fn validate_input(&self, input: &Input) -> bool {
-        match input {
-            Input::RichContentPart(
-                chat::completions::request::RichContentPart::InputAudio {
-                    ..
-                },
-            ) => true,
-            _ => false,
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
+        if InputType::of(input) != InputType::Audio {
+            violations.push(type_violation(InputType::Audio, input, path));
         }
     }
 }
@@ -918,18 +1161,24 @@ pub struct VideoInputSchema {
 impl VideoInputSchema {
     /// Validates that an input is video content.
     pub fn validate_input(&self, input: &Input) -> bool {
-        match input {
-            Input::RichContentPart(
-                chat::completions::request::RichContentPart::InputVideo {
-                    ..
-                },
-            ) => true,
-            Input::RichContentPart(
-                chat::completions::request::RichContentPart::VideoUrl {
-                    ..
-                },
-            ) => true,
-            _ => false,
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
+        if InputType::of(input) != InputType::Video {
+            violations.push(type_violation(InputType::Video, input, path));
         }
     }
 }
@@ -946,11 +1195,47 @@ pub struct FileInputSchema {
 impl FileInputSchema {
     /// Validates that an input is a file.
     pub fn validate_input(&self, input: &Input) -> bool {
-        match input {
-            Input::RichContentPart(
-                chat::completions::request::RichContentPart::File { .. },
-            ) => true,
-            _ => false,
+        self.violations(input).is_empty()
+    }
+
+    /// Returns every way in which an input does not conform to this schema.
+    pub fn violations(&self, input: &Input) -> Vec<InputSchemaViolation> {
+        let mut violations = Vec::new();
+        self.push_violations(input, "", &mut violations);
+        violations
+    }
+
+    fn push_violations(
+        &self,
+        input: &Input,
+        path: &str,
+        violations: &mut Vec<InputSchemaViolation>,
+    ) {
+        if InputType::of(input) != InputType::File {
+            violations.push(type_violation(InputType::File, input, path));
         }
     }
 }
+
+fn type_violation(
+    expected: InputType,
+    input: &Input,
+    path: &str,
+) -> InputSchemaViolation {
+    InputSchemaViolation {
+        path: path.to_string(),
+        kind: InputSchemaViolationKind::Type {
+            expected,
+            actual: InputType::of(input),
+        },
+    }
+}
+
+/// Appends a reference token to a JSON pointer, escaping it.
+fn child_path(path: &str, token: &str) -> String {
+    format!("{}/{}", path, token.replace('~', "~0").replace('/', "~1"))
+}
+
+fn json_number(number: f64) -> serde_json::Number {
+    serde_json::Number::from_f64(number).unwrap_or_else(|| 0.into())
+}
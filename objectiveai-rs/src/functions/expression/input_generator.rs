@@ -0,0 +1,205 @@
+//! Random generation of inputs conforming to an input schema.
+
+use crate::chat;
+use indexmap::IndexMap;
+
+impl super::InputSchema {
+    /// Generates a random input conforming to the schema.
+    ///
+    /// The same seed always generates the same input. See
+    /// [`InputGenerator`] for how values are chosen.
+    pub fn generate_input(&self, seed: u64) -> super::Input {
+        InputGenerator::new(seed).generate(self)
+    }
+}
+
+/// Generates random inputs conforming to input schemas, deterministically
+/// from a seed.
+///
+/// - Objects have every property.
+/// - Arrays have between `minItems` and `maxItems` items, or up to 4 more
+///   than `minItems` when unbounded.
+/// - Strings are one of the `enum` values if any, or short placeholders.
+/// - Numbers fall within `minimum` and `maximum`, or within 100 of the
+///   bound given, or of zero.
+/// - Images, audio, video, and files are placeholder rich content parts.
+/// - Unions generate a value for one of their schemas.
+///
+/// Schemas which no input can satisfy, such as an empty union, generate
+/// inputs which do not conform to them.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut generator = InputGenerator::new(42);
+/// let inputs = (0..100)
+///     .map(|_| generator.generate(function.input_schema()))
+///     .collect::<Vec<_>>();
+/// ```
+#[derive(Debug, Clone)]
+pub struct InputGenerator {
+    state: u64,
+}
+
+impl InputGenerator {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generates a random input conforming to `schema`.
+    pub fn generate(&mut self, schema: &super::InputSchema) -> super::Input {
+        match schema {
+            super::InputSchema::Object(schema) => {
+                let mut object =
+                    IndexMap::with_capacity(schema.properties.len());
+                for (key, property) in &schema.properties {
+                    object.insert(key.clone(), self.generate(property));
+                }
+                super::Input::Object(object)
+            }
+            super::InputSchema::Array(schema) => {
+                let min_items = schema.min_items.unwrap_or(0);
+                let max_items = schema
+                    .max_items
+                    .unwrap_or(min_items.saturating_add(4))
+                    .max(min_items);
+                let len = self.range_u64(min_items, max_items);
+                super::Input::Array(
+                    (0..len).map(|_| self.generate(&schema.items)).collect(),
+                )
+            }
+            super::InputSchema::String(schema) => match &schema.r#enum {
+                Some(r#enum) if !r#enum.is_empty() => {
+                    let i = self.range_u64(0, r#enum.len() as u64 - 1);
+                    super::Input::String(r#enum[i as usize].clone())
+                }
+                _ => super::Input::String(self.placeholder_string()),
+            },
+            super::InputSchema::Integer(schema) => {
+                let (minimum, maximum) = match (schema.minimum, schema.maximum)
+                {
+                    (Some(minimum), Some(maximum)) => {
+                        (minimum, maximum.max(minimum))
+                    }
+                    (Some(minimum), None) => {
+                        (minimum, minimum.saturating_add(100))
+                    }
+                    (None, Some(maximum)) => {
+                        (maximum.saturating_sub(100), maximum)
+                    }
+                    (None, None) => (-100, 100),
+                };
+                let offset =
+                    self.range_u64(0, minimum.abs_diff(maximum)) as i128;
+                super::Input::Integer((minimum as i128 + offset) as i64)
+            }
+            super::InputSchema::Number(schema) => {
+                let (minimum, maximum) = match (schema.minimum, schema.maximum)
+                {
+                    (Some(minimum), Some(maximum)) => {
+                        (minimum, maximum.max(minimum))
+                    }
+                    (Some(minimum), None) => (minimum, minimum + 100.0),
+                    (None, Some(maximum)) => (maximum - 100.0, maximum),
+                    (None, None) => (-100.0, 100.0),
+                };
+                let number = minimum + self.next_f64() * (maximum - minimum);
+                super::Input::Number(number.clamp(minimum, maximum))
+            }
+            super::InputSchema::Boolean(_) => {
+                super::Input::Boolean(self.next_bool())
+            }
+            super::InputSchema::Image(_) => super::Input::RichContentPart(
+                chat::completions::request::RichContentPart::ImageUrl {
+                    image_url: chat::completions::request::ImageUrl {
+                        url: format!(
+                            "https://example.com/{}.png",
+                            self.placeholder_string()
+                        ),
+                        detail: None,
+                    },
+                },
+            ),
+            super::InputSchema::Audio(_) => super::Input::RichContentPart(
+                chat::completions::request::RichContentPart::InputAudio {
+                    input_audio: chat::completions::request::InputAudio {
+                        data: "UklGRiQAAABXQVZFZm10IA==".to_string(),
+                        format: "wav".to_string(),
+                    },
+                },
+            ),
+            super::InputSchema::Video(_) => super::Input::RichContentPart(
+                chat::completions::request::RichContentPart::VideoUrl {
+                    video_url: chat::completions::request::VideoUrl {
+                        url: format!(
+                            "https://example.com/{}.mp4",
+                            self.placeholder_string()
+                        ),
+                    },
+                },
+            ),
+            super::InputSchema::File(_) => {
+                let name = self.placeholder_string();
+                super::Input::RichContentPart(
+                    chat::completions::request::RichContentPart::File {
+                        file: chat::completions::request::File {
+                            file_data: None,
+                            file_id: None,
+                            filename: Some(format!("{}.txt", name)),
+                            file_url: Some(format!(
+                                "https://example.com/{}.txt",
+                                name
+                            )),
+                        },
+                    },
+                )
+            }
+            super::InputSchema::AnyOf(schema) if !schema.any_of.is_empty() => {
+                let i = self.range_u64(0, schema.any_of.len() as u64 - 1);
+                self.generate(&schema.any_of[i as usize])
+            }
+            super::InputSchema::AnyOf(_) => {
+                super::Input::Object(IndexMap::new())
+            }
+        }
+    }
+
+    /// Returns the next random number (SplitMix64).
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number in `[0, 1)`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Returns a random number in `[minimum, maximum]`.
+    pub(crate) fn range_u64(&mut self, minimum: u64, maximum: u64) -> u64 {
+        match (maximum - minimum).checked_add(1) {
+            Some(len) => minimum + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    fn placeholder_string(&mut self) -> String {
+        const WORDS: &[&str] = &[
+            "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf",
+            "hotel", "india", "juliett", "kilo", "lima",
+        ];
+        let len = self.range_u64(1, 3);
+        (0..len)
+            .map(|_| WORDS[self.range_u64(0, WORDS.len() as u64 - 1) as usize])
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+}
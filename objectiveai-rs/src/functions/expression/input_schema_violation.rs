@@ -0,0 +1,159 @@
+//! Violations of an input schema by a Function input.
+
+use serde::{Deserialize, Serialize};
+
+/// A way in which an input does not conform to an
+/// [`InputSchema`](super::InputSchema).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputSchemaViolation {
+    /// JSON pointer to the offending value within the input. Empty for the
+    /// input itself.
+    pub path: String,
+    /// The violated constraint.
+    #[serde(flatten)]
+    pub kind: InputSchemaViolationKind,
+}
+
+impl std::fmt::Display for InputSchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "input: {}", self.kind)
+        } else {
+            write!(f, "input at `{}`: {}", self.path, self.kind)
+        }
+    }
+}
+
+/// The constraint violated by an input.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InputSchemaViolationKind {
+    /// The value is of the wrong type.
+    Type {
+        expected: InputType,
+        actual: InputType,
+    },
+    /// A required property of an object is missing.
+    Required { property: String },
+    /// A number is less than the minimum.
+    Minimum {
+        minimum: serde_json::Number,
+        actual: serde_json::Number,
+    },
+    /// A number is greater than the maximum.
+    Maximum {
+        maximum: serde_json::Number,
+        actual: serde_json::Number,
+    },
+    /// A string is not one of the allowed values.
+    Enum {
+        allowed: Vec<String>,
+        actual: String,
+    },
+    /// An array has fewer items than the minimum.
+    MinItems { min_items: u64, actual: u64 },
+    /// An array has more items than the maximum.
+    MaxItems { max_items: u64, actual: u64 },
+    /// The value matches none of the schemas of a union. Holds the
+    /// violations of each schema, in order.
+    AnyOf {
+        violations: Vec<Vec<InputSchemaViolation>>,
+    },
+}
+
+impl std::fmt::Display for InputSchemaViolationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSchemaViolationKind::Type { expected, actual } => {
+                write!(f, "expected {}, got {}", expected, actual)
+            }
+            InputSchemaViolationKind::Required { property } => {
+                write!(f, "missing required property `{}`", property)
+            }
+            InputSchemaViolationKind::Minimum { minimum, actual } => {
+                write!(f, "expected at least {}, got {}", minimum, actual)
+            }
+            InputSchemaViolationKind::Maximum { maximum, actual } => {
+                write!(f, "expected at most {}, got {}", maximum, actual)
+            }
+            InputSchemaViolationKind::Enum { allowed, actual } => {
+                write!(f, "expected one of {:?}, got {:?}", allowed, actual)
+            }
+            InputSchemaViolationKind::MinItems { min_items, actual } => {
+                write!(
+                    f,
+                    "expected at least {} items, got {}",
+                    min_items, actual
+                )
+            }
+            InputSchemaViolationKind::MaxItems { max_items, actual } => {
+                write!(
+                    f,
+                    "expected at most {} items, got {}",
+                    max_items, actual
+                )
+            }
+            InputSchemaViolationKind::AnyOf { violations } => {
+                write!(f, "matches none of {} schemas", violations.len())
+            }
+        }
+    }
+}
+
+/// The type of an input value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputType {
+    Object,
+    Array,
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Text,
+    Image,
+    Audio,
+    Video,
+    File,
+}
+
+impl InputType {
+    /// Returns the type of an input value.
+    pub fn of(input: &super::Input) -> Self {
+        use crate::chat::completions::request::RichContentPart;
+        match input {
+            super::Input::RichContentPart(part) => match part {
+                RichContentPart::Text { .. } => InputType::Text,
+                RichContentPart::ImageUrl { .. } => InputType::Image,
+                RichContentPart::InputAudio { .. } => InputType::Audio,
+                RichContentPart::InputVideo { .. }
+                | RichContentPart::VideoUrl { .. } => InputType::Video,
+                RichContentPart::File { .. } => InputType::File,
+            },
+            super::Input::Object(_) => InputType::Object,
+            super::Input::Array(_) => InputType::Array,
+            super::Input::String(_) => InputType::String,
+            super::Input::Integer(_) => InputType::Integer,
+            super::Input::Number(_) => InputType::Number,
+            super::Input::Boolean(_) => InputType::Boolean,
+        }
+    }
+}
+
+impl std::fmt::Display for InputType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            InputType::Object => "object",
+            InputType::Array => "array",
+            InputType::String => "string",
+            InputType::Integer => "integer",
+            InputType::Number => "number",
+            InputType::Boolean => "boolean",
+            InputType::Text => "text",
+            InputType::Image => "image",
+            InputType::Audio => "audio",
+            InputType::Video => "video",
+            InputType::File => "file",
+        })
+    }
+}
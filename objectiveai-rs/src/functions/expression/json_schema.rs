@@ -0,0 +1,687 @@
+//! Conversion between input schemas and standard JSON Schema.
+//!
+//! [`InputSchema::to_json_schema`] produces a JSON Schema 2020-12 document.
+//! Media types (image, audio, video, file) become references to `$defs`
+//! describing the matching [`RichContentPart`], annotated with
+//! [`MEDIA_TYPE_KEYWORD`] so that [`InputSchema::from_json_schema`] can
+//! restore them.
+//!
+//! [`RichContentPart`]: crate::chat::completions::request::RichContentPart
+
+use super::{
+    AnyOfInputSchema, ArrayInputSchema, AudioInputSchema, BooleanInputSchema,
+    FileInputSchema, ImageInputSchema, InputSchema, IntegerInputSchema,
+    NumberInputSchema, ObjectInputSchema, StringInputSchema, VideoInputSchema,
+};
+use indexmap::IndexMap;
+use serde_json::{Map, Value, json};
+
+/// The JSON Schema dialect of exported schemas.
+pub const JSON_SCHEMA_DIALECT: &str =
+    "https://json-schema.org/draft/2020-12/schema";
+
+/// Keyword marking a schema as one of the media types of an
+/// [`InputSchema`]. Its value is `image`, `audio`, `video`, or `file`.
+pub const MEDIA_TYPE_KEYWORD: &str = "x-objectiveai-media-type";
+
+/// Keywords which only annotate a schema and are ignored when importing.
+const ANNOTATIONS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "$defs",
+    "definitions",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+/// An error importing a JSON Schema into an [`InputSchema`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonSchemaError {
+    /// JSON pointer to the offending schema within the document. Empty for
+    /// the document itself.
+    pub path: String,
+    /// What is wrong with the schema.
+    pub kind: JsonSchemaErrorKind,
+}
+
+impl std::fmt::Display for JsonSchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{} at `{}`", self.kind, self.path)
+        }
+    }
+}
+
+impl std::error::Error for JsonSchemaError {}
+
+/// What is wrong with a JSON Schema which cannot be imported.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum JsonSchemaErrorKind {
+    /// The schema is not a JSON object.
+    #[error("expected a schema object")]
+    NotAnObject,
+    /// The schema has no `type`, `anyOf`, `enum`, `const`, or `$ref`.
+    #[error("missing `type`")]
+    MissingType,
+    /// The schema's `type` has no input schema equivalent.
+    #[error("unsupported type `{0}`")]
+    UnsupportedType(String),
+    /// The schema uses a keyword with no input schema equivalent.
+    #[error("unsupported keyword `{0}`")]
+    UnsupportedKeyword(String),
+    /// A property is not listed in `required`. Input schemas require every
+    /// property.
+    #[error("optional property")]
+    OptionalProperty,
+    /// A keyword has a value which cannot be imported.
+    #[error("invalid value for `{0}`")]
+    InvalidKeyword(String),
+    /// A `$ref` does not point to a schema in `$defs` or `definitions`.
+    #[error("unresolved reference `{0}`")]
+    UnresolvedRef(String),
+    /// A `$ref` refers to itself, which input schemas cannot express.
+    #[error("recursive reference `{0}`")]
+    RecursiveRef(String),
+}
+
+impl InputSchema {
+    /// Converts the schema into a JSON Schema 2020-12 document.
+    pub fn to_json_schema(&self) -> Value {
+        let mut defs = Map::new();
+        let schema = self.json_schema(&mut defs);
+        let mut document = Map::new();
+        document.insert(
+            "$schema".to_string(),
+            Value::String(JSON_SCHEMA_DIALECT.to_string()),
+        );
+        document.extend(schema);
+        if !defs.is_empty() {
+            document.insert("$defs".to_string(), Value::Object(defs));
+        }
+        Value::Object(document)
+    }
+
+    /// Imports a schema from a JSON Schema document.
+    ///
+    /// Supports the subset of JSON Schema which input schemas can express:
+    /// `type`, `properties`, `required`, `items`, `minItems`, `maxItems`,
+    /// `minimum`, `maximum`, string `enum` and `const`, `anyOf`, and `$ref`
+    /// to `$defs` or `definitions`. Annotations such as `title` and
+    /// `default` are ignored, and any other keyword is an error. As input
+    /// schemas require every property of an object, so must `required`.
+    pub fn from_json_schema(schema: &Value) -> Result<Self, JsonSchemaError> {
+        Importer {
+            root: schema,
+            refs: Vec::new(),
+        }
+        .import(schema, "")
+    }
+
+    fn json_schema(&self, defs: &mut Map<String, Value>) -> Map<String, Value> {
+        let mut schema = Map::new();
+        let description = match self {
+            InputSchema::Object(object) => {
+                schema.insert("type".to_string(), json!("object"));
+                schema.insert(
+                    "properties".to_string(),
+                    Value::Object(
+                        object
+                            .properties
+                            .iter()
+                            .map(|(key, property)| {
+                                (
+                                    key.clone(),
+                                    Value::Object(property.json_schema(defs)),
+                                )
+                            })
+                            .collect(),
+                    ),
+                );
+                // every property is required, as well as every listed key
+                let mut required = object.properties.keys().collect::<Vec<_>>();
+                for key in object.required.iter().flatten() {
+                    if !object.properties.contains_key(key) {
+                        required.push(key);
+                    }
+                }
+                schema.insert("required".to_string(), json!(required));
+                &object.description
+            }
+            InputSchema::Array(array) => {
+                schema.insert("type".to_string(), json!("array"));
+                schema.insert(
+                    "items".to_string(),
+                    Value::Object(array.items.json_schema(defs)),
+                );
+                if let Some(min_items) = array.min_items {
+                    schema.insert("minItems".to_string(), json!(min_items));
+                }
+                if let Some(max_items) = array.max_items {
+                    schema.insert("maxItems".to_string(), json!(max_items));
+                }
+                &array.description
+            }
+            InputSchema::String(string) => {
+                schema.insert("type".to_string(), json!("string"));
+                if let Some(r#enum) = &string.r#enum {
+                    schema.insert("enum".to_string(), json!(r#enum));
+                }
+                &string.description
+            }
+            InputSchema::Integer(integer) => {
+                schema.insert("type".to_string(), json!("integer"));
+                if let Some(minimum) = integer.minimum {
+                    schema.insert("minimum".to_string(), json!(minimum));
+                }
+                if let Some(maximum) = integer.maximum {
+                    schema.insert("maximum".to_string(), json!(maximum));
+                }
+                &integer.description
+            }
+            InputSchema::Number(number) => {
+                schema.insert("type".to_string(), json!("number"));
+                if let Some(minimum) = number.minimum {
+                    schema.insert("minimum".to_string(), json!(minimum));
+                }
+                if let Some(maximum) = number.maximum {
+                    schema.insert("maximum".to_string(), json!(maximum));
+                }
+                &number.description
+            }
+            InputSchema::Boolean(boolean) => {
+                schema.insert("type".to_string(), json!("boolean"));
+                &boolean.description
+            }
+            InputSchema::Image(image) => {
+                media_ref(&mut schema, defs, MediaType::Image);
+                &image.description
+            }
+            InputSchema::Audio(audio) => {
+                media_ref(&mut schema, defs, MediaType::Audio);
+                &audio.description
+            }
+            InputSchema::Video(video) => {
+                media_ref(&mut schema, defs, MediaType::Video);
+                &video.description
+            }
+            InputSchema::File(file) => {
+                media_ref(&mut schema, defs, MediaType::File);
+                &file.description
+            }
+            InputSchema::AnyOf(any_of) => {
+                schema.insert(
+                    "anyOf".to_string(),
+                    Value::Array(
+                        any_of
+                            .any_of
+                            .iter()
+                            .map(|schema| {
+                                Value::Object(schema.json_schema(defs))
+                            })
+                            .collect(),
+                    ),
+                );
+                &None
+            }
+        };
+        if let Some(description) = description {
+            schema.insert("description".to_string(), json!(description));
+        }
+        schema
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum MediaType {
+    Image,
+    Audio,
+    Video,
+    File,
+}
+
+impl MediaType {
+    fn name(self) -> &'static str {
+        match self {
+            MediaType::Image => "image",
+            MediaType::Audio => "audio",
+            MediaType::Video => "video",
+            MediaType::File => "file",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "image" => Some(MediaType::Image),
+            "audio" => Some(MediaType::Audio),
+            "video" => Some(MediaType::Video),
+            "file" => Some(MediaType::File),
+            _ => None,
+        }
+    }
+
+    fn input_schema(self, description: Option<String>) -> InputSchema {
+        match self {
+            MediaType::Image => {
+                InputSchema::Image(ImageInputSchema { description })
+            }
+            MediaType::Audio => {
+                InputSchema::Audio(AudioInputSchema { description })
+            }
+            MediaType::Video => {
+                InputSchema::Video(VideoInputSchema { description })
+            }
+            MediaType::File => {
+                InputSchema::File(FileInputSchema { description })
+            }
+        }
+    }
+
+    /// Returns the JSON Schema of the matching rich content parts.
+    fn json_schema(self) -> Value {
+        let mut schema = match self {
+            MediaType::Image => rich_content_part(
+                "image_url",
+                "image_url",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "url": { "type": "string" },
+                        "detail": {
+                            "type": "string",
+                            "enum": ["auto", "low", "high"]
+                        }
+                    },
+                    "required": ["url"]
+                }),
+            ),
+            MediaType::Audio => rich_content_part(
+                "input_audio",
+                "input_audio",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "data": { "type": "string" },
+                        "format": { "type": "string" }
+                    },
+                    "required": ["data", "format"]
+                }),
+            ),
+            MediaType::Video => {
+                let video_url = json!({
+                    "type": "object",
+                    "properties": {
+                        "url": { "type": "string" }
+                    },
+                    "required": ["url"]
+                });
+                json!({
+                    "anyOf": [
+                        rich_content_part(
+                            "input_video",
+                            "video_url",
+                            video_url.clone(),
+                        ),
+                        rich_content_part("video_url", "video_url", video_url),
+                    ]
+                })
+            }
+            MediaType::File => rich_content_part(
+                "file",
+                "file",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "file_data": { "type": "string" },
+                        "file_id": { "type": "string" },
+                        "filename": { "type": "string" },
+                        "file_url": { "type": "string" }
+                    }
+                }),
+            ),
+        };
+        schema
+            .as_object_mut()
+            .unwrap()
+            .insert(MEDIA_TYPE_KEYWORD.to_string(), json!(self.name()));
+        schema
+    }
+}
+
+fn rich_content_part(r#type: &str, key: &str, value: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "type": { "const": r#type },
+            key: value
+        },
+        "required": ["type", key]
+    })
+}
+
+/// Points `schema` to the definition of a media type, adding it to `defs`.
+fn media_ref(
+    schema: &mut Map<String, Value>,
+    defs: &mut Map<String, Value>,
+    media_type: MediaType,
+) {
+    let name = media_type.name();
+    defs.entry(name).or_insert_with(|| media_type.json_schema());
+    schema.insert("$ref".to_string(), json!(format!("#/$defs/{}", name)));
+}
+
+struct Importer<'a> {
+    root: &'a Value,
+    /// References being imported, to detect recursion.
+    refs: Vec<String>,
+}
+
+impl<'a> Importer<'a> {
+    fn import(
+        &mut self,
+        schema: &'a Value,
+        path: &str,
+    ) -> Result<InputSchema, JsonSchemaError> {
+        let error = |kind| JsonSchemaError {
+            path: path.to_string(),
+            kind,
+        };
+        let object = schema
+            .as_object()
+            .ok_or_else(|| error(JsonSchemaErrorKind::NotAnObject))?;
+        let description = match object.get("description") {
+            Some(Value::String(description)) => Some(description.clone()),
+            Some(_) => {
+                return Err(error(JsonSchemaErrorKind::InvalidKeyword(
+                    "description".to_string(),
+                )));
+            }
+            None => None,
+        };
+
+        // media types, as exported
+        if let Some(media_type) = object.get(MEDIA_TYPE_KEYWORD) {
+            return media_type
+                .as_str()
+                .and_then(MediaType::from_name)
+                .map(|media_type| media_type.input_schema(description))
+                .ok_or_else(|| {
+                    error(JsonSchemaErrorKind::InvalidKeyword(
+                        MEDIA_TYPE_KEYWORD.to_string(),
+                    ))
+                });
+        }
+
+        if let Some(reference) = object.get("$ref") {
+            check_keywords(object, &["$ref"], path)?;
+            let reference = reference.as_str().ok_or_else(|| {
+                error(JsonSchemaErrorKind::InvalidKeyword("$ref".to_string()))
+            })?;
+            let schema = self.import_ref(reference, path)?;
+            return Ok(match description {
+                Some(description) => schema.with_description(description),
+                None => schema,
+            });
+        }
+
+        if let Some(any_of) = object.get("anyOf") {
+            check_keywords(object, &["anyOf"], path)?;
+            let any_of = any_of.as_array().ok_or_else(|| {
+                error(JsonSchemaErrorKind::InvalidKeyword("anyOf".to_string()))
+            })?;
+            return Ok(InputSchema::AnyOf(AnyOfInputSchema {
+                any_of: any_of
+                    .iter()
+                    .enumerate()
+                    .map(|(i, schema)| {
+                        self.import(schema, &format!("{}/anyOf/{}", path, i))
+                    })
+                    .collect::<Result<_, _>>()?,
+            }));
+        }
+
+        let r#type = match object.get("type") {
+            Some(Value::String(r#type)) => r#type.as_str(),
+            Some(_) => {
+                return Err(error(JsonSchemaErrorKind::InvalidKeyword(
+                    "type".to_string(),
+                )));
+            }
+            // enums of strings need no type
+            None if object.contains_key("enum")
+                || object.contains_key("const") =>
+            {
+                "string"
+            }
+            None => return Err(error(JsonSchemaErrorKind::MissingType)),
+        };
+        match r#type {
+            "object" => {
+                check_keywords(
+                    object,
+                    &["type", "properties", "required", "additionalProperties"],
+                    path,
+                )?;
+                // input schemas accept additional properties
+                if let Some(additional) = object.get("additionalProperties")
+                    && additional != &Value::Bool(true)
+                {
+                    return Err(error(
+                        JsonSchemaErrorKind::UnsupportedKeyword(
+                            "additionalProperties".to_string(),
+                        ),
+                    ));
+                }
+                let properties = match object.get("properties") {
+                    Some(Value::Object(properties)) => properties
+                        .iter()
+                        .map(|(key, schema)| {
+                            let path = format!(
+                                "{}/properties/{}",
+                                path,
+                                key.replace('~', "~0").replace('/', "~1")
+                            );
+                            Ok((key.clone(), self.import(schema, &path)?))
+                        })
+                        .collect::<Result<IndexMap<_, _>, JsonSchemaError>>()?,
+                    Some(_) => {
+                        return Err(error(
+                            JsonSchemaErrorKind::InvalidKeyword(
+                                "properties".to_string(),
+                            ),
+                        ));
+                    }
+                    None => IndexMap::new(),
+                };
+                let required = match object.get("required") {
+                    Some(required) => Some(
+                        serde_json::from_value::<Vec<String>>(required.clone())
+                            .map_err(|_| {
+                                error(JsonSchemaErrorKind::InvalidKeyword(
+                                    "required".to_string(),
+                                ))
+                            })?,
+                    ),
+                    None => None,
+                };
+                // input schemas require every property
+                if let Some(key) = properties.keys().find(|key| {
+                    required
+                        .as_ref()
+                        .is_none_or(|required| !required.contains(*key))
+                }) {
+                    return Err(JsonSchemaError {
+                        path: format!(
+                            "{}/properties/{}",
+                            path,
+                            key.replace('~', "~0").replace('/', "~1")
+                        ),
+                        kind: JsonSchemaErrorKind::OptionalProperty,
+                    });
+                }
+                Ok(InputSchema::Object(ObjectInputSchema {
+                    description,
+                    properties,
+                    required,
+                }))
+            }
+            "array" => {
+                check_keywords(
+                    object,
+                    &["type", "items", "minItems", "maxItems"],
+                    path,
+                )?;
+                let items = object.get("items").ok_or_else(|| {
+                    error(JsonSchemaErrorKind::InvalidKeyword(
+                        "items".to_string(),
+                    ))
+                })?;
+                let items = self.import(items, &format!("{}/items", path))?;
+                Ok(InputSchema::Array(ArrayInputSchema {
+                    description,
+                    min_items: keyword(object, "minItems", Value::as_u64)
+                        .map_err(error)?,
+                    max_items: keyword(object, "maxItems", Value::as_u64)
+                        .map_err(error)?,
+                    items: Box::new(items),
+                }))
+            }
+            "string" => {
+                check_keywords(
+                    object,
+                    &["type", "enum", "const", "format"],
+                    path,
+                )?;
+                let mut r#enum = match object.get("enum") {
+                    Some(r#enum) => Some(
+                        serde_json::from_value::<Vec<String>>(r#enum.clone())
+                            .map_err(|_| {
+                            error(JsonSchemaErrorKind::InvalidKeyword(
+                                "enum".to_string(),
+                            ))
+                        })?,
+                    ),
+                    None => None,
+                };
+                if let Some(r#const) = object.get("const") {
+                    let r#const = r#const.as_str().ok_or_else(|| {
+                        error(JsonSchemaErrorKind::InvalidKeyword(
+                            "const".to_string(),
+                        ))
+                    })?;
+                    r#enum = Some(match r#enum {
+                        Some(r#enum) => r#enum
+                            .into_iter()
+                            .filter(|value| value == r#const)
+                            .collect(),
+                        None => vec![r#const.to_string()],
+                    });
+                }
+                Ok(InputSchema::String(StringInputSchema {
+                    description,
+                    r#enum,
+                }))
+            }
+            "integer" => {
+                check_keywords(object, &["type", "minimum", "maximum"], path)?;
+                Ok(InputSchema::Integer(IntegerInputSchema {
+                    description,
+                    minimum: keyword(object, "minimum", Value::as_i64)
+                        .map_err(error)?,
+                    maximum: keyword(object, "maximum", Value::as_i64)
+                        .map_err(error)?,
+                }))
+            }
+            "number" => {
+                check_keywords(object, &["type", "minimum", "maximum"], path)?;
+                Ok(InputSchema::Number(NumberInputSchema {
+                    description,
+                    minimum: keyword(object, "minimum", Value::as_f64)
+                        .map_err(error)?,
+                    maximum: keyword(object, "maximum", Value::as_f64)
+                        .map_err(error)?,
+                }))
+            }
+            "boolean" => {
+                check_keywords(object, &["type"], path)?;
+                Ok(InputSchema::Boolean(BooleanInputSchema { description }))
+            }
+            r#type => Err(error(JsonSchemaErrorKind::UnsupportedType(
+                r#type.to_string(),
+            ))),
+        }
+    }
+
+    fn import_ref(
+        &mut self,
+        reference: &str,
+        path: &str,
+    ) -> Result<InputSchema, JsonSchemaError> {
+        let error = |kind| JsonSchemaError {
+            path: path.to_string(),
+            kind,
+        };
+        let root = self.root;
+        let target = ["#/$defs/", "#/definitions/"]
+            .iter()
+            .find_map(|prefix| {
+                let name = reference.strip_prefix(prefix)?;
+                root.get(&prefix[2..prefix.len() - 1])?.get(unescape(name))
+            })
+            .ok_or_else(|| {
+                error(JsonSchemaErrorKind::UnresolvedRef(reference.to_string()))
+            })?;
+        if self.refs.iter().any(|r| r == reference) {
+            return Err(error(JsonSchemaErrorKind::RecursiveRef(
+                reference.to_string(),
+            )));
+        }
+        self.refs.push(reference.to_string());
+        let schema = self.import(target, &reference[1..]);
+        self.refs.pop();
+        schema
+    }
+}
+
+/// Fails if the schema has keywords other than annotations and `allowed`.
+fn check_keywords(
+    object: &Map<String, Value>,
+    allowed: &[&str],
+    path: &str,
+) -> Result<(), JsonSchemaError> {
+    match object.keys().find(|key| {
+        !ANNOTATIONS.contains(&key.as_str()) && !allowed.contains(&key.as_str())
+    }) {
+        Some(key) => Err(JsonSchemaError {
+            path: path.to_string(),
+            kind: JsonSchemaErrorKind::UnsupportedKeyword(key.clone()),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Reads an optional keyword with `f`, failing if it has the wrong type.
+fn keyword<T>(
+    object: &Map<String, Value>,
+    name: &str,
+    f: impl FnOnce(&Value) -> Option<T>,
+) -> Result<Option<T>, JsonSchemaErrorKind> {
+    match object.get(name) {
+        Some(value) => f(value).map(Some).ok_or_else(|| {
+            JsonSchemaErrorKind::InvalidKeyword(name.to_string())
+        }),
+        None => Ok(None),
+    }
+}
+
+/// Unescapes a JSON pointer reference token.
+fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
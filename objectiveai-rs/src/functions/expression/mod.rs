@@ -10,6 +10,8 @@
 //! - [`WithExpression<T>`] - Either a literal value or an expression
 //! - [`Input`] - The input data structure passed to expressions
 //! - [`Params`] - Context available during expression evaluation
+//! - [`FunctionInput`] - Rust types which convert into [`Input`]
+//! - [`InputGenerator`] - Random inputs conforming to an [`InputSchema`]
 //!
 //! # Expression Context
 //!
@@ -20,12 +22,23 @@
 
 mod error;
 mod expression;
+mod function_input;
 mod input;
+mod input_generator;
+mod input_schema_violation;
+mod json_schema;
 mod params;
 mod runtime;
 
 pub use error::*;
 pub use expression::*;
+pub use function_input::*;
 pub use input::*;
+pub use input_generator::*;
+pub use input_schema_violation::*;
+pub use json_schema::*;
 pub use params::*;
 pub use runtime::*;
+
+#[cfg(feature = "derive")]
+pub use objectiveai_derive::FunctionInput;
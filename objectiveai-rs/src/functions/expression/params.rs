@@ -109,6 +109,9 @@ pub struct VectorCompletionOutput {
     pub scores: Vec<rust_decimal::Decimal>,
     /// Total weight allocated to each response option.
     pub weights: Vec<rust_decimal::Decimal>,
+    /// Bootstrapped uncertainty of the scores. Only present when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uncertainty: Option<vector::completions::response::Uncertainty>,
 }
 
 impl VectorCompletionOutput {
@@ -127,6 +130,7 @@ impl VectorCompletionOutput {
             votes: Vec::new(),
             scores,
             weights,
+            uncertainty: None,
         }
     }
 }
@@ -139,6 +143,7 @@ impl From<vector::completions::response::streaming::VectorCompletionChunk>
             votes,
             scores,
             weights,
+            uncertainty,
             ..
         }: vector::completions::response::streaming::VectorCompletionChunk,
     ) -> Self {
@@ -146,6 +151,7 @@ impl From<vector::completions::response::streaming::VectorCompletionChunk>
             votes,
             scores,
             weights,
+            uncertainty,
         }
     }
 }
@@ -158,6 +164,7 @@ impl From<vector::completions::response::unary::VectorCompletion>
             votes,
             scores,
             weights,
+            uncertainty,
             ..
         }: vector::completions::response::unary::VectorCompletion,
     ) -> Self {
@@ -165,6 +172,7 @@ impl From<vector::completions::response::unary::VectorCompletion>
             votes,
             scores,
             weights,
+            uncertainty,
         }
     }
 }
@@ -42,6 +42,23 @@ impl Function {
         }
     }
 
+    /// Returns every way in which the input does not conform to the
+    /// function's input schema.
+    ///
+    /// Returns `None` for inline functions, which have no input schema, and
+    /// an empty list if the input is valid.
+    pub fn input_violations(
+        &self,
+        input: &super::expression::Input,
+    ) -> Option<Vec<super::expression::InputSchemaViolation>> {
+        match self {
+            Function::Remote(remote_function) => {
+                Some(remote_function.input_schema().violations(input))
+            }
+            Function::Inline(_) => None,
+        }
+    }
+
     /// Compiles the `input_maps` expressions to transform input into a 2D array.
     ///
     /// Evaluates the `input_maps` expressions to transform the input into a 2D array
@@ -0,0 +1,287 @@
+//! Fuzzing of Function expressions against generated inputs.
+
+use rust_decimal::Decimal;
+
+/// Options for [`Function::fuzz`](super::Function::fuzz).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzOptions {
+    /// Number of inputs to generate.
+    pub inputs: usize,
+    /// Seed for generating inputs and synthetic task outputs.
+    pub seed: u64,
+    /// Length of the synthetic outputs of vector function tasks, whose
+    /// output length is not known without fetching the function.
+    pub vector_function_output_length: usize,
+}
+
+impl Default for FuzzOptions {
+    fn default() -> Self {
+        Self {
+            inputs: 100,
+            seed: 0,
+            vector_function_output_length: 2,
+        }
+    }
+}
+
+/// The compilation stage at which a fuzzed input failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuzzStage {
+    /// [`Function::compile_input_maps`](super::Function::compile_input_maps).
+    InputMaps,
+    /// [`Function::compile_tasks`](super::Function::compile_tasks).
+    Tasks,
+    /// [`Function::compile_output_length`](super::Function::compile_output_length).
+    OutputLength,
+    /// [`Function::compile_input_split`](super::Function::compile_input_split).
+    InputSplit,
+    /// [`Function::compile_input_merge`](super::Function::compile_input_merge),
+    /// given every split input.
+    InputMerge,
+    /// [`Function::compile_output`](super::Function::compile_output), given
+    /// synthetic task outputs.
+    Output,
+}
+
+impl std::fmt::Display for FuzzStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FuzzStage::InputMaps => "input_maps",
+            FuzzStage::Tasks => "tasks",
+            FuzzStage::OutputLength => "output_length",
+            FuzzStage::InputSplit => "input_split",
+            FuzzStage::InputMerge => "input_merge",
+            FuzzStage::Output => "output",
+        })
+    }
+}
+
+/// Why a fuzzed input failed.
+#[derive(Debug, thiserror::Error)]
+pub enum FuzzError {
+    /// An expression failed to compile.
+    #[error(transparent)]
+    Expression(#[from] super::expression::ExpressionError),
+    /// The output expression compiled to an invalid output. See
+    /// [`CompiledFunctionOutput::valid`](super::expression::CompiledFunctionOutput::valid).
+    #[error("invalid output: {0:?}")]
+    InvalidOutput(super::expression::FunctionOutput),
+}
+
+/// An input for which a Function's expressions failed.
+#[derive(Debug)]
+pub struct FuzzFailure {
+    /// The generated input.
+    pub input: super::expression::Input,
+    /// The stage which failed.
+    pub stage: FuzzStage,
+    /// Why the stage failed.
+    pub error: FuzzError,
+}
+
+impl std::fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} (input: {})",
+            self.stage,
+            self.error,
+            serde_json::to_string(&self.input).unwrap_or_default()
+        )
+    }
+}
+
+impl super::Function {
+    /// Compiles every expression of the function over random inputs
+    /// conforming to `input_schema`, returning the failures.
+    ///
+    /// Remote functions are fuzzed against their own input schema when
+    /// `input_schema` is `None`. Inline functions have no input schema, so
+    /// fuzzing them without one returns no failures.
+    ///
+    /// For each input, compiles `input_maps`, `tasks`, `output_length`,
+    /// `input_split`, and `input_merge` (over every split input), then
+    /// `output` given random task outputs shaped like the compiled tasks.
+    /// An input stops at its first failure. The same options always
+    /// generate the same inputs.
+    pub fn fuzz(
+        &self,
+        input_schema: Option<&super::expression::InputSchema>,
+        options: FuzzOptions,
+    ) -> Vec<FuzzFailure> {
+        let Some(input_schema) = input_schema.or(self.input_schema()) else {
+            return Vec::new();
+        };
+        let mut generator =
+            super::expression::InputGenerator::new(options.seed);
+        let mut failures = Vec::new();
+        for _ in 0..options.inputs {
+            let input = generator.generate(input_schema);
+            if let Err((stage, error)) =
+                self.fuzz_input(&input, &mut generator, &options)
+            {
+                failures.push(FuzzFailure {
+                    input,
+                    stage,
+                    error,
+                });
+            }
+        }
+        failures
+    }
+
+    fn fuzz_input(
+        &self,
+        input: &super::expression::Input,
+        generator: &mut super::expression::InputGenerator,
+        options: &FuzzOptions,
+    ) -> Result<(), (FuzzStage, FuzzError)> {
+        fn at<E: Into<FuzzError>>(
+            stage: FuzzStage,
+        ) -> impl FnOnce(E) -> (FuzzStage, FuzzError) {
+            move |e| (stage, e.into())
+        }
+
+        self.clone()
+            .compile_input_maps(input)
+            .map_err(at(FuzzStage::InputMaps))?;
+        let tasks = self
+            .clone()
+            .compile_tasks(input)
+            .map_err(at(FuzzStage::Tasks))?;
+        self.clone()
+            .compile_output_length(input)
+            .map_err(at(FuzzStage::OutputLength))?;
+        if let Some(split) = self
+            .clone()
+            .compile_input_split(input)
+            .map_err(at(FuzzStage::InputSplit))?
+        {
+            self.clone()
+                .compile_input_merge(&super::expression::Input::Array(split))
+                .map_err(at(FuzzStage::InputMerge))?;
+        }
+
+        let task_outputs = tasks
+            .iter()
+            .map(|task| {
+                task.as_ref().map(|task| {
+                    super::expression::TaskOutput::Owned(synthetic_task_output(
+                        task, generator, options,
+                    ))
+                })
+            })
+            .collect::<Vec<_>>();
+        let compiled = self
+            .clone()
+            .compile_output(input, &task_outputs)
+            .map_err(at(FuzzStage::Output))?;
+        if !compiled.valid {
+            return Err((
+                FuzzStage::Output,
+                FuzzError::InvalidOutput(compiled.output),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Returns a random output shaped like the output of a compiled task.
+fn synthetic_task_output(
+    task: &super::CompiledTask,
+    generator: &mut super::expression::InputGenerator,
+    options: &FuzzOptions,
+) -> super::expression::TaskOutputOwned {
+    match task {
+        super::CompiledTask::One(super::Task::VectorCompletion(task)) => {
+            super::expression::TaskOutputOwned::VectorCompletion(
+                synthetic_vector_completion_output(task, generator),
+            )
+        }
+        super::CompiledTask::One(task) => {
+            super::expression::TaskOutputOwned::Function(
+                synthetic_function_output(task, generator, options),
+            )
+        }
+        super::CompiledTask::Many(tasks) => match tasks.first() {
+            Some(super::Task::VectorCompletion(_)) | None => {
+                super::expression::TaskOutputOwned::MapVectorCompletion(
+                    tasks
+                        .iter()
+                        .filter_map(|task| match task {
+                            super::Task::VectorCompletion(task) => {
+                                Some(synthetic_vector_completion_output(
+                                    task, generator,
+                                ))
+                            }
+                            _ => None,
+                        })
+                        .collect(),
+                )
+            }
+            Some(_) => super::expression::TaskOutputOwned::MapFunction(
+                tasks
+                    .iter()
+                    .map(|task| {
+                        synthetic_function_output(task, generator, options)
+                    })
+                    .collect(),
+            ),
+        },
+    }
+}
+
+fn synthetic_vector_completion_output(
+    task: &super::VectorCompletionTask,
+    generator: &mut super::expression::InputGenerator,
+) -> super::expression::VectorCompletionOutput {
+    let len = task.responses.len();
+    super::expression::VectorCompletionOutput {
+        votes: Vec::new(),
+        scores: random_distribution(len, generator),
+        weights: vec![Decimal::ZERO; len],
+        uncertainty: None,
+    }
+}
+
+fn synthetic_function_output(
+    task: &super::Task,
+    generator: &mut super::expression::InputGenerator,
+    options: &FuzzOptions,
+) -> super::expression::FunctionOutput {
+    match task {
+        super::Task::ScalarFunction(_) => {
+            super::expression::FunctionOutput::Scalar(random_decimal(generator))
+        }
+        super::Task::VectorFunction(_) => {
+            super::expression::FunctionOutput::Vector(random_distribution(
+                options.vector_function_output_length,
+                generator,
+            ))
+        }
+        super::Task::VectorCompletion(task) => {
+            super::expression::FunctionOutput::Vector(
+                synthetic_vector_completion_output(task, generator).scores,
+            )
+        }
+    }
+}
+
+/// Returns a random decimal in `[0, 1]`.
+fn random_decimal(
+    generator: &mut super::expression::InputGenerator,
+) -> Decimal {
+    Decimal::new(generator.range_u64(0, 1_000_000) as i64, 6)
+}
+
+/// Returns `len` random decimals summing to 1.
+fn random_distribution(
+    len: usize,
+    generator: &mut super::expression::InputGenerator,
+) -> Vec<Decimal> {
+    let weights = (0..len)
+        .map(|_| Decimal::from(generator.range_u64(1, 1_000_000)))
+        .collect::<Vec<_>>();
+    let sum = weights.iter().sum::<Decimal>();
+    weights.into_iter().map(|weight| weight / sum).collect()
+}
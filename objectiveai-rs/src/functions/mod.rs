@@ -31,6 +31,7 @@
 //!
 //! - [`Function::compile_tasks`] - Resolves task expressions to show final tasks for a given input
 //! - [`Function::compile_output`] - Computes the final output given input and task outputs
+//! - [`Function::fuzz`] - Compiles every expression over random inputs to find failures
 //!
 //! # Submodules
 //!
@@ -38,15 +39,19 @@
 //! - [`expression`] - JMESPath expression evaluation engine
 //! - [`profiles`] - Profile management and computation
 
+mod builder;
 pub mod executions;
 pub mod expression;
 mod function;
+mod fuzz;
 mod profile;
 pub mod profiles;
 pub mod response;
 mod task;
 
+pub use builder::*;
 pub use function::*;
+pub use fuzz::*;
 pub use profile::*;
 pub use task::*;
 
@@ -55,3 +60,6 @@ mod http;
 
 #[cfg(feature = "http")]
 pub use http::*;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
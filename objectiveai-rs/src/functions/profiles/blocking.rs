@@ -0,0 +1,40 @@
+//! Blocking HTTP functions for Profile endpoints.
+
+use crate::{BlockingHttpClient, HttpError};
+
+/// Blocking version of [`super::list_profiles`].
+pub fn list_profiles(
+    client: &BlockingHttpClient,
+) -> Result<super::response::ListProfile, HttpError> {
+    client.block_on(super::list_profiles(&client.inner))
+}
+
+/// Blocking version of [`super::get_profile`].
+pub fn get_profile(
+    client: &BlockingHttpClient,
+    owner: &str,
+    repository: &str,
+    commit: Option<&str>,
+) -> Result<super::response::GetProfile, HttpError> {
+    client.block_on(super::get_profile(
+        &client.inner,
+        owner,
+        repository,
+        commit,
+    ))
+}
+
+/// Blocking version of [`super::get_profile_usage`].
+pub fn get_profile_usage(
+    client: &BlockingHttpClient,
+    powner: &str,
+    prepository: &str,
+    pcommit: Option<&str>,
+) -> Result<super::response::UsageProfile, HttpError> {
+    client.block_on(super::get_profile_usage(
+        &client.inner,
+        powner,
+        prepository,
+        pcommit,
+    ))
+}
@@ -0,0 +1,30 @@
+//! Blocking HTTP functions for profile computations.
+
+use crate::{BlockingHttpClient, BlockingStream, HttpError};
+
+/// Blocking version of [`super::compute_profile_unary`].
+pub fn compute_profile_unary(
+    client: &BlockingHttpClient,
+    request: super::request::Request,
+) -> Result<super::response::unary::FunctionProfileComputation, HttpError> {
+    client.block_on(super::compute_profile_unary(&client.inner, request))
+}
+
+/// Blocking version of [`super::compute_profile_streaming`].
+pub fn compute_profile_streaming(
+    client: &BlockingHttpClient,
+    request: super::request::Request,
+) -> Result<
+    BlockingStream<
+        Result<
+            super::response::streaming::FunctionProfileComputationChunk,
+            HttpError,
+        >,
+    >,
+    HttpError,
+> {
+    client.block_on_stream(super::compute_profile_streaming(
+        &client.inner,
+        request,
+    ))
+}
@@ -1,4 +1,4 @@
-use crate::{HttpClient, HttpError};
+use crate::{HttpClient, HttpError, MaybeSend};
 use futures::Stream;
 
 pub async fn compute_profile_unary(
@@ -50,7 +50,7 @@ pub async fn compute_profile_streaming(
             HttpError,
         >,
     >
-    + Send
+    + MaybeSend
     + 'static
     + use<>,
     HttpError,
@@ -0,0 +1,131 @@
+//! Blocking HTTP client for the ObjectiveAI API.
+
+use futures::{Stream, StreamExt};
+use std::sync::Arc;
+
+/// Blocking HTTP client for making requests to the ObjectiveAI API.
+///
+/// Wraps an [`HttpClient`](super::HttpClient) and a private Tokio runtime
+/// with one worker thread which drives its requests, so that no runtime is
+/// needed by the caller. The worker keeps connections and open streams
+/// making progress between calls, as `reqwest::blocking` does. Every async
+/// function of the SDK has a blocking counterpart of the same name in the
+/// `blocking` submodule of its module, taking this client instead. Streaming
+/// functions return a [`BlockingStream`], which yields chunks as an
+/// iterator.
+///
+/// Like `reqwest::blocking`, this client must not be used from within an
+/// async runtime, as blocking on a future there panics.
+///
+/// # Example
+///
+/// ```ignore
+/// let client = BlockingHttpClient::new(
+///     reqwest::Client::new(),
+///     None, // Use default API base
+///     Some("your-api-key"),
+///     None, // user_agent
+///     None, // x_title
+///     None, // referer
+/// )?;
+/// let stream = chat::completions::blocking::create_chat_completion_streaming(
+///     &client, params,
+/// )?;
+/// for chunk in stream {
+///     println!("{:?}", chunk?);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlockingHttpClient {
+    /// The async client which performs the requests.
+    pub inner: super::HttpClient,
+    /// The runtime which drives the async client.
+    runtime: Arc<tokio::runtime::Runtime>,
+}
+
+impl BlockingHttpClient {
+    /// Creates a new blocking HTTP client.
+    ///
+    /// Takes the same arguments as [`HttpClient::new`](super::HttpClient::new).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the runtime cannot be created.
+    pub fn new(
+        http_client: reqwest::Client,
+        api_base: Option<impl Into<String>>,
+        api_key: Option<impl Into<String>>,
+        user_agent: Option<impl Into<String>>,
+        x_title: Option<impl Into<String>>,
+        referer: Option<impl Into<String>>,
+    ) -> std::io::Result<Self> {
+        Self::from_async(super::HttpClient::new(
+            http_client,
+            api_base,
+            api_key,
+            user_agent,
+            x_title,
+            referer,
+        ))
+    }
+
+    /// Creates a blocking HTTP client from an async one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the runtime cannot be created.
+    pub fn from_async(inner: super::HttpClient) -> std::io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("objectiveai-blocking")
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Runs a future to completion on the client's runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Runs a future which returns a stream to completion, and wraps the
+    /// stream in a [`BlockingStream`] driven by the client's runtime.
+    pub fn block_on_stream<S, E>(
+        &self,
+        future: impl Future<Output = Result<S, E>>,
+    ) -> Result<BlockingStream<S::Item>, E>
+    where
+        S: Stream + Send + 'static,
+    {
+        let stream = self.block_on(future)?;
+        Ok(BlockingStream {
+            runtime: self.runtime.clone(),
+            stream: stream.boxed(),
+        })
+    }
+}
+
+/// A stream of the ObjectiveAI API, consumed as a blocking iterator.
+///
+/// Each call to [`next`](Iterator::next) blocks until the next chunk arrives.
+pub struct BlockingStream<T> {
+    runtime: Arc<tokio::runtime::Runtime>,
+    stream: futures::stream::BoxStream<'static, T>,
+}
+
+impl<T> Iterator for BlockingStream<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl<T> std::fmt::Debug for BlockingStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockingStream").finish_non_exhaustive()
+    }
+}
@@ -3,6 +3,7 @@
 use crate::error;
 use eventsource_stream::Event as MessageEvent;
 use futures::{Stream, StreamExt};
+#[cfg(not(target_arch = "wasm32"))]
 use reqwest_eventsource::{Event, RequestBuilderExt};
 
 /// HTTP client for making requests to the ObjectiveAI API.
@@ -144,20 +145,7 @@ impl HttpClient {
                 Err(e) => Err(super::HttpError::DeserializationError(e)),
             }
         } else {
-            match response.text().await {
-                Ok(text) => Err(super::HttpError::BadStatus {
-                    code,
-                    body: match serde_json::from_str::<serde_json::Value>(&text)
-                    {
-                        Ok(body) => body,
-                        Err(_) => serde_json::Value::String(text),
-                    },
-                }),
-                Err(_) => Err(super::HttpError::BadStatus {
-                    code,
-                    body: serde_json::Value::Null,
-                }),
-            }
+            Err(bad_status(code, response).await)
         }
     }
 
@@ -193,20 +181,7 @@ impl HttpClient {
         if code.is_success() {
             Ok(())
         } else {
-            match response.text().await {
-                Ok(text) => Err(super::HttpError::BadStatus {
-                    code,
-                    body: match serde_json::from_str::<serde_json::Value>(&text)
-                    {
-                        Ok(body) => body,
-                        Err(_) => serde_json::Value::String(text),
-                    },
-                }),
-                Err(_) => Err(super::HttpError::BadStatus {
-                    code,
-                    body: serde_json::Value::Null,
-                }),
-            }
+            Err(bad_status(code, response).await)
         }
     }
 
@@ -232,6 +207,7 @@ impl HttpClient {
     ///
     /// Returns [`super::HttpError`] if the stream cannot be established. Individual
     /// stream items may also contain errors if chunks fail to deserialize.
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn send_streaming<
         T: serde::de::DeserializeOwned + Send + 'static,
         P: AsRef<str> + Send,
@@ -248,62 +224,127 @@ impl HttpClient {
         + use<T, P, B>,
         super::HttpError,
     > {
-        Ok(
-            self.request(method, path.as_ref(), body)
-                .eventsource()?
-                .then(|result| async {
-                    match result {
-                        Ok(Event::Open) => None,
-                        Ok(Event::Message(MessageEvent { data, .. }))
-                            if data == "[DONE]"
-                                || data.starts_with(":")
-                                || data.is_empty() =>
-                        {
-                            None
-                        }
-                        Ok(Event::Message(MessageEvent { data, .. })) => {
-                            let mut de =
-                                serde_json::Deserializer::from_str(&data);
-                            Some(
-                                match serde_path_to_error::deserialize::<_, T>(
-                                    &mut de,
-                                ) {
-                                    Ok(value) => Ok(value),
-                                    Err(e) => match serde_json::from_str::<error::ResponseError>(&data) {
-                                        Ok(err) => Err(super::HttpError::ApiError(err)),
-                                        Err(_) => Err(super::HttpError::DeserializationError(e)),
-                                    },
-                                }
-                            )
-                        }
-                        Err(reqwest_eventsource::Error::InvalidStatusCode(
-                            code,
-                            response,
-                        )) => match response.text().await {
-                            Ok(body) => {
-                                Some(Err(super::HttpError::BadStatus {
-                                    code,
-                                    body: match serde_json::from_str::<
-                                        serde_json::Value,
-                                    >(
-                                        &body
-                                    ) {
-                                        Ok(body) => body,
-                                        Err(_) => {
-                                            serde_json::Value::String(body)
-                                        }
-                                    },
-                                }))
-                            }
-                            Err(_) => Some(Err(super::HttpError::BadStatus {
-                                code,
-                                body: serde_json::Value::Null,
-                            })),
-                        },
-                        Err(e) => Some(Err(super::HttpError::StreamError(e))),
+        Ok(self
+            .request(method, path.as_ref(), body)
+            .eventsource()?
+            .then(|result| async {
+                match result {
+                    Ok(Event::Open) => None,
+                    Ok(Event::Message(MessageEvent { data, .. }))
+                        if data == "[DONE]"
+                            || data.starts_with(":")
+                            || data.is_empty() =>
+                    {
+                        None
+                    }
+                    Ok(Event::Message(MessageEvent { data, .. })) => {
+                        Some(deserialize_chunk(&data))
                     }
-                })
-                .filter_map(|x| async { x }),
-        )
+                    Err(reqwest_eventsource::Error::InvalidStatusCode(
+                        code,
+                        response,
+                    )) => Some(Err(bad_status(code, response).await)),
+                    Err(e) => Some(Err(super::HttpError::StreamError(e))),
+                }
+            })
+            .filter_map(|x| async { x }))
+    }
+
+    /// Sends a streaming API call using Server-Sent Events (SSE).
+    ///
+    /// Reads the events from the `fetch` response's `ReadableStream`,
+    /// filtering and deserializing them as on native targets. Unlike on
+    /// native targets, the stream ends when the response ends, and a
+    /// non-success status is returned as an error rather than as the first
+    /// item of the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`super::HttpError`] if the request fails or returns a
+    /// non-success status. Individual stream items may also contain errors if
+    /// chunks fail to deserialize.
+    #[cfg(target_arch = "wasm32")]
+    pub async fn send_streaming<
+        T: serde::de::DeserializeOwned + Send + 'static,
+        P: AsRef<str> + Send,
+        B: serde::Serialize + Send,
+    >(
+        &self,
+        method: reqwest::Method,
+        path: P,
+        body: Option<B>,
+    ) -> Result<
+        impl Stream<Item = Result<T, super::HttpError>> + 'static + use<T, P, B>,
+        super::HttpError,
+    > {
+        use eventsource_stream::Eventsource;
+        let response = self
+            .http_client
+            .execute(
+                self.request(method, path.as_ref(), body)
+                    .build()
+                    .map_err(super::HttpError::RequestError)?,
+            )
+            .await
+            .map_err(super::HttpError::HttpError)?;
+        let code = response.status();
+        if !code.is_success() {
+            return Err(bad_status(code, response).await);
+        }
+        Ok(response.bytes_stream().eventsource().filter_map(
+            |result| async move {
+                match result {
+                    Ok(MessageEvent { data, .. })
+                        if data == "[DONE]"
+                            || data.starts_with(":")
+                            || data.is_empty() =>
+                    {
+                        None
+                    }
+                    Ok(MessageEvent { data, .. }) => {
+                        Some(deserialize_chunk(&data))
+                    }
+                    Err(e) => Some(Err(super::HttpError::StreamError(e))),
+                }
+            },
+        ))
+    }
+}
+
+/// Builds the error for a non-success response, parsing its body as JSON if
+/// possible.
+async fn bad_status(
+    code: reqwest::StatusCode,
+    response: reqwest::Response,
+) -> super::HttpError {
+    match response.text().await {
+        Ok(text) => super::HttpError::BadStatus {
+            code,
+            body: match serde_json::from_str::<serde_json::Value>(&text) {
+                Ok(body) => body,
+                Err(_) => serde_json::Value::String(text),
+            },
+        },
+        Err(_) => super::HttpError::BadStatus {
+            code,
+            body: serde_json::Value::Null,
+        },
+    }
+}
+
+/// Deserializes the data of an SSE message, which is either a chunk or an
+/// API error.
+// returns the same unboxed error as the public functions which call it
+#[allow(clippy::result_large_err)]
+fn deserialize_chunk<T: serde::de::DeserializeOwned>(
+    data: &str,
+) -> Result<T, super::HttpError> {
+    let mut de = serde_json::Deserializer::from_str(data);
+    match serde_path_to_error::deserialize::<_, T>(&mut de) {
+        Ok(value) => Ok(value),
+        Err(e) => match serde_json::from_str::<error::ResponseError>(data) {
+            Ok(err) => Err(super::HttpError::ApiError(err)),
+            Err(_) => Err(super::HttpError::DeserializationError(e)),
+        },
     }
 }
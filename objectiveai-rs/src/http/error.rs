@@ -2,6 +2,22 @@
 
 use crate::error;
 
+/// Error reading from an SSE stream.
+#[cfg(not(target_arch = "wasm32"))]
+pub type StreamError = reqwest_eventsource::Error;
+
+/// Error reading from an SSE stream.
+#[cfg(target_arch = "wasm32")]
+pub type StreamError = eventsource_stream::EventStreamError<reqwest::Error>;
+
+/// Error preparing a streaming request. Only occurs on native targets.
+#[cfg(not(target_arch = "wasm32"))]
+pub type StreamingRequestError = reqwest_eventsource::CannotCloneRequestError;
+
+/// Error preparing a streaming request. Only occurs on native targets.
+#[cfg(target_arch = "wasm32")]
+pub type StreamingRequestError = std::convert::Infallible;
+
 /// Errors that can occur during HTTP operations.
 #[derive(thiserror::Error, Debug)]
 pub enum HttpError {
@@ -22,7 +38,7 @@ pub enum HttpError {
 
     /// Error occurred while reading from an SSE stream.
     #[error("error fetching stream: {0}")]
-    StreamError(#[from] reqwest_eventsource::Error),
+    StreamError(#[from] StreamError),
 
     /// Failed to build the HTTP request.
     #[error("request error: {0}")]
@@ -32,7 +48,7 @@ pub enum HttpError {
     ///
     /// Occurs when the request cannot be cloned for SSE retry logic.
     #[error("streaming request error: {0}")]
-    StreamingRequestError(#[from] reqwest_eventsource::CannotCloneRequestError),
+    StreamingRequestError(#[from] StreamingRequestError),
 
     /// General HTTP transport error (network, timeout, etc.).
     #[error("http error: {0}")]
@@ -41,6 +57,10 @@ pub enum HttpError {
     /// The API returned a structured error response.
     #[error(transparent)]
     ApiError(#[from] error::ResponseError),
+
+    /// The stream ended before its final chunk was received.
+    #[error("stream ended before completing")]
+    StreamIncomplete,
 }
 
 impl error::StatusError for HttpError {
@@ -48,12 +68,18 @@ impl error::StatusError for HttpError {
         match self {
             HttpError::DeserializationError(_) => 500,
             HttpError::BadStatus { code, .. } => code.as_u16(),
+            #[cfg(not(target_arch = "wasm32"))]
             HttpError::StreamError(reqwest_eventsource::Error::Transport(
                 e,
             )) => e.status().map(|s| s.as_u16()).unwrap_or(500),
+            #[cfg(not(target_arch = "wasm32"))]
             HttpError::StreamError(
                 reqwest_eventsource::Error::InvalidStatusCode(code, _),
             ) => code.as_u16(),
+            #[cfg(target_arch = "wasm32")]
+            HttpError::StreamError(
+                eventsource_stream::EventStreamError::Transport(e),
+            ) => e.status().map(|s| s.as_u16()).unwrap_or(500),
             HttpError::StreamError(_) => 500,
             HttpError::RequestError(e) => {
                 e.status().map(|s| s.as_u16()).unwrap_or(500)
@@ -63,6 +89,7 @@ impl error::StatusError for HttpError {
                 e.status().map(|s| s.as_u16()).unwrap_or(500)
             }
             HttpError::ApiError(e) => e.status(),
+            HttpError::StreamIncomplete => 500,
         }
     }
 
@@ -98,6 +125,9 @@ impl error::StatusError for HttpError {
                     "kind": "api_error",
                     "error": e.message(),
                 }),
+                HttpError::StreamIncomplete => serde_json::json!({
+                    "kind": "stream_incomplete",
+                }),
             }
         }))
     }
@@ -0,0 +1,20 @@
+//! `Send` bound which is dropped on WebAssembly.
+
+/// `Send` on native targets, and implemented by every type on `wasm32`,
+/// where the browser's futures and streams are not `Send`.
+///
+/// Streams returned by the HTTP functions are bounded by this trait, so that
+/// native callers can still move them across threads.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// Implemented by every type on `wasm32`, where the browser's futures and
+/// streams are not `Send`.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T: ?Sized> MaybeSend for T {}
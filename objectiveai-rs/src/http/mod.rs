@@ -8,13 +8,28 @@
 //!
 //! This module is only available when the `http` feature is enabled (default).
 //!
+//! # WebAssembly
+//!
+//! On `wasm32` targets, requests go through the browser's `fetch` API and
+//! streams are read from the response's `ReadableStream`. Browser futures
+//! are not `Send`, so streams are only bounded by [`MaybeSend`].
+//!
 //! # Components
 //!
 //! - [`HttpClient`] - The main client for making API requests
 //! - [`HttpError`] - Error types for HTTP operations
+//! - [`BlockingHttpClient`] - Blocking client, with the `blocking` feature
 
 mod client;
 mod error;
+mod maybe_send;
 
 pub use client::*;
 pub use error::*;
+pub use maybe_send::*;
+
+#[cfg(feature = "blocking")]
+mod blocking;
+
+#[cfg(feature = "blocking")]
+pub use blocking::*;
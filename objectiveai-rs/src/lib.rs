@@ -14,7 +14,12 @@
 //!
 //! # Features
 //!
-//! - `http` (default): Enables the HTTP client for making API requests
+//! - `http` (default): Enables the HTTP client for making API requests, also
+//!   on `wasm32` through the browser's `fetch` API
+//! - `derive`: Enables `#[derive(FunctionInput)]` for typed Function input
+//! - `blocking`: Enables [`BlockingHttpClient`], with a blocking counterpart of
+//!   every HTTP function in the `blocking` submodule of its module
+//! - `executor`: Enables client-side Function execution with pluggable vector completions
 //!
 //! # Modules
 //!
@@ -30,6 +35,10 @@
 //! When the `http` feature is enabled:
 //! - [`HttpClient`] - HTTP client for API requests
 //! - [`HttpError`] - HTTP error types
+//!
+//! When the `blocking` feature is enabled:
+//! - [`BlockingHttpClient`] - Blocking HTTP client for API requests
+//! - [`BlockingStream`] - Blocking iterator over streamed chunks
 
 pub mod auth;
 pub mod chat;
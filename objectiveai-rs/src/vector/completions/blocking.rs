@@ -0,0 +1,28 @@
+//! Blocking HTTP functions for vector completions.
+
+use crate::{BlockingHttpClient, BlockingStream, HttpError};
+
+/// Blocking version of [`super::create_vector_completion_unary`].
+pub fn create_vector_completion_unary(
+    client: &BlockingHttpClient,
+    params: super::request::VectorCompletionCreateParams,
+) -> Result<super::response::unary::VectorCompletion, HttpError> {
+    client
+        .block_on(super::create_vector_completion_unary(&client.inner, params))
+}
+
+/// Blocking version of [`super::create_vector_completion_streaming`].
+pub fn create_vector_completion_streaming(
+    client: &BlockingHttpClient,
+    params: super::request::VectorCompletionCreateParams,
+) -> Result<
+    BlockingStream<
+        Result<super::response::streaming::VectorCompletionChunk, HttpError>,
+    >,
+    HttpError,
+> {
+    client.block_on_stream(super::create_vector_completion_streaming(
+        &client.inner,
+        params,
+    ))
+}
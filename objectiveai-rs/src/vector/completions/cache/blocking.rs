@@ -0,0 +1,19 @@
+//! Blocking HTTP functions for the vector completion cache.
+
+use crate::{BlockingHttpClient, HttpError};
+
+/// Blocking version of [`super::get_completion_votes`].
+pub fn get_completion_votes(
+    client: &BlockingHttpClient,
+    id: &str,
+) -> Result<super::response::CompletionVotes, HttpError> {
+    client.block_on(super::get_completion_votes(&client.inner, id))
+}
+
+/// Blocking version of [`super::get_cache_vote`].
+pub fn get_cache_vote(
+    client: &BlockingHttpClient,
+    request: &super::request::CacheVoteRequest<'_>,
+) -> Result<super::response::CacheVote, HttpError> {
+    client.block_on(super::get_cache_vote(&client.inner, request))
+}
@@ -1,6 +1,6 @@
 //! HTTP client functions for vector completions.
 
-use crate::{HttpClient, HttpError};
+use crate::{HttpClient, HttpError, MaybeSend};
 use futures::Stream;
 
 /// Creates a vector completion and waits for the complete response.
@@ -30,7 +30,7 @@ pub async fn create_vector_completion_streaming(
             HttpError,
         >,
     >
-    + Send
+    + MaybeSend
     + 'static
     + use<>,
     HttpError,
@@ -0,0 +1,193 @@
+//! Builder for vector completion requests.
+
+use crate::{chat, ensemble};
+
+/// Builds a [`VectorCompletionCreateParams`](super::VectorCompletionCreateParams).
+///
+/// The Ensemble and profile are required up front. [`build`](Self::build)
+/// validates that there are at least two responses, that the profile has
+/// two or more positive weights of at most 1, that an inline Ensemble is
+/// valid, and that the profile has one weight per LLM of an inline Ensemble.
+/// Weights apply to the Ensemble's LLMs once deduplicated and sorted by ID.
+///
+/// # Example
+///
+/// ```ignore
+/// let request = VectorCompletionCreateParamsBuilder::new(
+///     Ensemble::Id("ens_abc123".to_string()),
+///     vec![dec!(0.5), dec!(0.5)],
+/// )
+/// .message(Message::User(UserMessage {
+///     content: RichContent::Text("Which is funnier?".to_string()),
+///     name: None,
+/// }))
+/// .response(RichContent::Text("A knock-knock joke".to_string()))
+/// .response(RichContent::Text("A pun".to_string()))
+/// .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct VectorCompletionCreateParamsBuilder {
+    params: super::VectorCompletionCreateParams,
+}
+
+impl VectorCompletionCreateParamsBuilder {
+    /// Creates a builder voting with the given Ensemble and profile weights.
+    pub fn new(
+        ensemble: super::Ensemble,
+        profile: Vec<rust_decimal::Decimal>,
+    ) -> Self {
+        Self {
+            params: super::VectorCompletionCreateParams {
+                retry: None,
+                from_cache: None,
+                from_rng: None,
+                messages: Vec::new(),
+                provider: None,
+                ensemble,
+                profile,
+                seed: None,
+                stream: None,
+                tools: None,
+                responses: Vec::new(),
+                uncertainty: None,
+                backoff_max_elapsed_time: None,
+                first_chunk_timeout: None,
+                other_chunk_timeout: None,
+            },
+        }
+    }
+
+    /// Adds a message to the prompt.
+    pub fn message(
+        mut self,
+        message: chat::completions::request::Message,
+    ) -> Self {
+        self.params.messages.push(message);
+        self
+    }
+
+    /// Adds a possible response for the LLMs to vote for.
+    pub fn response(
+        mut self,
+        response: chat::completions::request::RichContent,
+    ) -> Self {
+        self.params.responses.push(response);
+        self
+    }
+
+    /// Adds a tool provided to the LLMs as read-only context.
+    pub fn tool(mut self, tool: chat::completions::request::Tool) -> Self {
+        self.params.tools.get_or_insert_with(Vec::new).push(tool);
+        self
+    }
+
+    /// Reuses votes from a previous vector completion with this ID.
+    pub fn retry(mut self, retry: impl Into<String>) -> Self {
+        self.params.retry = Some(retry.into());
+        self
+    }
+
+    /// Sets whether to use cached votes when available.
+    pub fn from_cache(mut self, from_cache: bool) -> Self {
+        self.params.from_cache = Some(from_cache);
+        self
+    }
+
+    /// Sets whether remaining votes are generated randomly.
+    pub fn from_rng(mut self, from_rng: bool) -> Self {
+        self.params.from_rng = Some(from_rng);
+        self
+    }
+
+    /// Sets the provider routing preferences.
+    pub fn provider(
+        mut self,
+        provider: chat::completions::request::Provider,
+    ) -> Self {
+        self.params.provider = Some(provider);
+        self
+    }
+
+    /// Sets the random seed.
+    pub fn seed(mut self, seed: i64) -> Self {
+        self.params.seed = Some(seed);
+        self
+    }
+
+    /// Sets whether to stream the response.
+    pub fn stream(mut self, stream: bool) -> Self {
+        self.params.stream = Some(stream);
+        self
+    }
+
+    /// Requests bootstrapped confidence intervals on the scores.
+    pub fn uncertainty(mut self, uncertainty: super::Uncertainty) -> Self {
+        self.params.uncertainty = Some(uncertainty);
+        self
+    }
+
+    /// Sets the maximum elapsed time (ms) for exponential backoff retries.
+    pub fn backoff_max_elapsed_time(
+        mut self,
+        backoff_max_elapsed_time: u64,
+    ) -> Self {
+        self.params.backoff_max_elapsed_time = Some(backoff_max_elapsed_time);
+        self
+    }
+
+    /// Sets the timeout (ms) for receiving the first chunk.
+    pub fn first_chunk_timeout(mut self, first_chunk_timeout: u64) -> Self {
+        self.params.first_chunk_timeout = Some(first_chunk_timeout);
+        self
+    }
+
+    /// Sets the timeout (ms) between subsequent chunks.
+    pub fn other_chunk_timeout(mut self, other_chunk_timeout: u64) -> Self {
+        self.params.other_chunk_timeout = Some(other_chunk_timeout);
+        self
+    }
+
+    /// Validates the request and returns it.
+    pub fn build(self) -> Result<super::VectorCompletionCreateParams, String> {
+        if self.params.responses.len() < 2 {
+            return Err(format!(
+                "expected two or more responses, got {}",
+                self.params.responses.len()
+            ));
+        }
+        if let Some(uncertainty) = &self.params.uncertainty {
+            uncertainty.validate()?;
+        }
+        if self
+            .params
+            .profile
+            .iter()
+            .any(|weight| *weight > rust_decimal::Decimal::ONE)
+        {
+            return Err("profile weights must be at most 1".to_string());
+        }
+        if self
+            .params
+            .profile
+            .iter()
+            .filter(|weight| **weight > rust_decimal::Decimal::ZERO)
+            .count()
+            < 2
+        {
+            return Err(
+                "profile must have two or more positive weights".to_string()
+            );
+        }
+        if let super::Ensemble::Provided(base) = &self.params.ensemble {
+            let ensemble = ensemble::Ensemble::try_from(base.clone())?;
+            if self.params.profile.len() != ensemble.llms.len() {
+                return Err(format!(
+                    "profile length must match ensemble length, expected {}, got {}",
+                    ensemble.llms.len(),
+                    self.params.profile.len()
+                ));
+            }
+        }
+        Ok(self.params)
+    }
+}
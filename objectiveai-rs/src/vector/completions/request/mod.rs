@@ -2,9 +2,14 @@
 //!
 //! - [`VectorCompletionCreateParams`] - The main request structure
 //! - [`Ensemble`] - Ensemble specification for the request
+//! - [`Uncertainty`] - Bootstrapped uncertainty options
 
+mod builder;
 mod ensemble;
+mod uncertainty;
 mod vector_completion_create_params;
 
+pub use builder::*;
 pub use ensemble::*;
+pub use uncertainty::*;
 pub use vector_completion_create_params::*;
@@ -0,0 +1,61 @@
+//! Uncertainty estimation options for vector completions.
+
+use serde::{Deserialize, Serialize};
+
+/// Options for bootstrapping confidence intervals on vector completion scores.
+///
+/// When present on a request, the final response includes an
+/// [`Uncertainty`](crate::vector::completions::response::Uncertainty)
+/// computed by resampling the ensemble's weighted votes.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Uncertainty {
+    /// Number of bootstrap resamples. Defaults to 1000, at most 10000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub samples: Option<u64>,
+    /// Confidence level of the reported intervals, strictly between 0 and 1.
+    /// Defaults to 0.95.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence_level: Option<rust_decimal::Decimal>,
+}
+
+impl Uncertainty {
+    /// Default number of bootstrap resamples.
+    pub const DEFAULT_SAMPLES: u64 = 1000;
+    /// Maximum number of bootstrap resamples.
+    pub const MAX_SAMPLES: u64 = 10000;
+    /// Default confidence level.
+    pub const DEFAULT_CONFIDENCE_LEVEL: rust_decimal::Decimal =
+        rust_decimal::dec!(0.95);
+
+    /// Returns the number of resamples, applying the default.
+    pub fn samples(&self) -> u64 {
+        self.samples.unwrap_or(Self::DEFAULT_SAMPLES)
+    }
+
+    /// Returns the confidence level, applying the default.
+    pub fn confidence_level(&self) -> rust_decimal::Decimal {
+        self.confidence_level
+            .unwrap_or(Self::DEFAULT_CONFIDENCE_LEVEL)
+    }
+
+    /// Validates the options.
+    pub fn validate(&self) -> Result<(), String> {
+        let samples = self.samples();
+        if samples == 0 || samples > Self::MAX_SAMPLES {
+            return Err(format!(
+                "`uncertainty.samples` must be between 1 and {}",
+                Self::MAX_SAMPLES
+            ));
+        }
+        let confidence_level = self.confidence_level();
+        if confidence_level <= rust_decimal::Decimal::ZERO
+            || confidence_level >= rust_decimal::Decimal::ONE
+        {
+            return Err(
+                "`uncertainty.confidence_level` must be between 0 and 1"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+}
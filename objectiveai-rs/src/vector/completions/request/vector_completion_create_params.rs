@@ -45,6 +45,10 @@ pub struct VectorCompletionCreateParams {
     pub tools: Option<Vec<chat::completions::request::Tool>>,
     /// The possible responses the LLMs can vote for.
     pub responses: Vec<chat::completions::request::RichContent>,
+    /// If present, the final response includes bootstrapped confidence
+    /// intervals on the scores.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uncertainty: Option<super::Uncertainty>,
 
     // --- Retry configuration ---
 
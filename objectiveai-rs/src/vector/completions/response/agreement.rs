@@ -0,0 +1,46 @@
+//! Inter-LLM agreement statistics for vector completions.
+
+use serde::{Deserialize, Serialize};
+
+/// Statistics describing how much the ensemble's LLMs agree with each other.
+///
+/// Computed from the votes of a completed vector completion. Probabilistic
+/// votes (from logprobs) are treated as soft ratings. Votes drawn from the RNG
+/// are left out, and every other vote counts with its profile weight.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Agreement {
+    /// Multi-rater kappa over the votes, in [-1, 1].
+    ///
+    /// A single vector completion is a single rated subject, so chance
+    /// agreement is taken as uniform over the responses (free-marginal
+    /// Fleiss' kappa). 1 is perfect agreement, 0 is chance-level agreement.
+    /// Absent when fewer than two votes have a positive weight.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fleiss_kappa: Option<rust_decimal::Decimal>,
+    /// Weighted mean Jensen-Shannon divergence (base 2) between every pair of
+    /// votes, in [0, 1]. 0 means all votes are identical. Absent when fewer
+    /// than two votes have a positive weight.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mean_pairwise_jensen_shannon_divergence: Option<rust_decimal::Decimal>,
+    /// Entropy of the final scores normalized by the maximum possible
+    /// entropy, in [0, 1]. 0 means all weight is on one response, 1 means
+    /// the scores are uniform.
+    pub normalized_score_entropy: rust_decimal::Decimal,
+    /// Agreement of each vote not drawn from the RNG with the weighted
+    /// consensus (the final scores).
+    pub llms: Vec<LlmAgreement>,
+}
+
+/// Agreement of a single LLM's vote with the weighted consensus.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmAgreement {
+    /// The model that produced the vote.
+    pub model: String,
+    /// Index of the LLM configuration within the ensemble.
+    pub ensemble_index: u64,
+    /// Flattened index accounting for LLM counts in the ensemble.
+    pub flat_ensemble_index: u64,
+    /// One minus the Jensen-Shannon divergence (base 2) between the vote and
+    /// the final scores, in [0, 1]. 1 means the vote matches the consensus.
+    pub agreement: rust_decimal::Decimal,
+}
@@ -4,11 +4,17 @@
 //! - [`streaming`] - Incremental chunk-based responses
 //! - [`Vote`] - Individual LLM vote data
 //! - [`Usage`] - Aggregated token and cost statistics
+//! - [`Uncertainty`] - Bootstrapped confidence intervals on scores
+//! - [`Agreement`] - Inter-LLM agreement statistics
 
+mod agreement;
 pub mod streaming;
+mod uncertainty;
 pub mod unary;
 mod usage;
 mod vote;
 
+pub use agreement::*;
+pub use uncertainty::*;
 pub use usage::*;
 pub use vote::*;
@@ -28,6 +28,13 @@ pub struct VectorCompletionChunk {
     /// Aggregated usage statistics. Typically present only in the final chunk.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<response::Usage>,
+    /// Bootstrapped uncertainty of the scores. Only present in the final
+    /// chunk, and only when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uncertainty: Option<response::Uncertainty>,
+    /// Inter-LLM agreement statistics. Only present in the final chunk.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agreement: Option<response::Agreement>,
 }
 
 impl VectorCompletionChunk {
@@ -52,12 +59,15 @@ impl VectorCompletionChunk {
             ensemble: String::new(),
             object: super::Object::default(),
             usage: None,
+            uncertainty: None,
+            agreement: None,
         }
     }
 
     /// Accumulates another chunk into this one.
     ///
-    /// Updates scores, weights, and usage, appends new votes.
+    /// Updates scores, weights, usage, uncertainty, and agreement, appends new
+    /// votes.
     pub fn push(
         &mut self,
         VectorCompletionChunk {
@@ -66,6 +76,8 @@ impl VectorCompletionChunk {
             scores,
             weights,
             usage,
+            uncertainty,
+            agreement,
             ..
         }: &VectorCompletionChunk,
     ) {
@@ -82,6 +94,12 @@ impl VectorCompletionChunk {
             }
             _ => {}
         }
+        if let Some(uncertainty) = uncertainty {
+            self.uncertainty = Some(uncertainty.clone());
+        }
+        if let Some(agreement) = agreement {
+            self.agreement = Some(agreement.clone());
+        }
     }
 
     fn push_completions(
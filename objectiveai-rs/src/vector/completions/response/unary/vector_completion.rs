@@ -29,6 +29,12 @@ pub struct VectorCompletion {
     pub object: super::Object,
     /// Aggregated token and cost usage across all completions.
     pub usage: response::Usage,
+    /// Bootstrapped uncertainty of the scores. Only present when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uncertainty: Option<response::Uncertainty>,
+    /// Inter-LLM agreement statistics.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agreement: Option<response::Agreement>,
 }
 
 impl VectorCompletion {
@@ -53,6 +59,8 @@ impl VectorCompletion {
             ensemble: String::new(),
             object: super::Object::default(),
             usage: response::Usage::default(),
+            uncertainty: None,
+            agreement: None,
         }
     }
 }
@@ -69,6 +77,8 @@ impl From<response::streaming::VectorCompletionChunk> for VectorCompletion {
             ensemble,
             object,
             usage,
+            uncertainty,
+            agreement,
         }: response::streaming::VectorCompletionChunk,
     ) -> Self {
         Self {
@@ -84,6 +94,8 @@ impl From<response::streaming::VectorCompletionChunk> for VectorCompletion {
             ensemble,
             object: object.into(),
             usage: usage.unwrap_or_default(),
+            uncertainty,
+            agreement,
         }
     }
 }
@@ -0,0 +1,31 @@
+//! Uncertainty statistics for vector completion scores.
+
+use serde::{Deserialize, Serialize};
+
+/// Bootstrapped uncertainty of a vector completion's scores.
+///
+/// Computed by resampling the ensemble's votes with replacement and, for
+/// probabilistic (logprobs) votes, sampling a discrete selection from each
+/// vote's distribution. Only present when requested.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Uncertainty {
+    /// Confidence interval for each response's score. Same length as `scores`.
+    pub intervals: Vec<ScoreInterval>,
+    /// Fraction of resamples in which the highest-scoring response remained
+    /// the highest-scoring response. Near 1 when the ensemble is decisive,
+    /// near `1 / responses` when the split is noise.
+    pub decisiveness: rust_decimal::Decimal,
+    /// Number of bootstrap resamples used.
+    pub samples: u64,
+    /// Confidence level of the intervals.
+    pub confidence_level: rust_decimal::Decimal,
+}
+
+/// A confidence interval for a single response's score.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ScoreInterval {
+    /// Lower bound of the score.
+    pub lower: rust_decimal::Decimal,
+    /// Upper bound of the score.
+    pub upper: rust_decimal::Decimal,
+}
@@ -31,6 +31,10 @@ pub struct Usage {
     /// Total cost including upstream provider charges. Only differs from `cost`
     /// when using BYOK (Bring Your Own Key).
     pub total_cost: rust_decimal::Decimal,
+    /// Total milliseconds the completions spent waiting for upstream rate
+    /// limits before being sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_wait_time: Option<u64>,
 }
 
 impl Usage {
@@ -92,6 +96,9 @@ impl Usage {
             _ => {}
         }
         self.total_cost += other.total_cost;
+        if let Some(other_queue_wait_time) = other.queue_wait_time {
+            *self.queue_wait_time.get_or_insert(0) += other_queue_wait_time;
+        }
     }
 
     /// Appends usage from a chat completion response.
@@ -137,5 +144,8 @@ impl Usage {
             _ => {}
         }
         self.total_cost += other.total_cost;
+        if let Some(other_queue_wait_time) = other.queue_wait_time {
+            *self.queue_wait_time.get_or_insert(0) += other_queue_wait_time;
+        }
     }
 }